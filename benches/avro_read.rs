@@ -53,6 +53,7 @@ fn read_batch(buffer: &[u8], size: usize) -> Result<()> {
         ),
         avro_schema,
         Arc::new(schema),
+        None,
     );
 
     let mut rows = 0;
@@ -10,11 +10,10 @@ use arrow2::io::csv::write;
 use arrow2::record_batch::RecordBatch;
 
 fn write_batch(batch: &RecordBatch) -> Result<()> {
-    let writer = &mut write::WriterBuilder::new().from_writer(vec![]);
-
-    write::write_header(writer, batch.schema())?;
+    let writer = &mut vec![];
 
     let options = write::SerializeOptions::default();
+    write::write_header(writer, batch.schema(), &options)?;
     write::write_batch(writer, batch, &options)
 }
 
@@ -0,0 +1,82 @@
+use crossbeam_channel::unbounded;
+
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::Arc;
+use std::thread;
+use std::time::SystemTime;
+
+use arrow2::error::Result;
+use arrow2::io::avro::read;
+use arrow2::record_batch::RecordBatch;
+use fallible_streaming_iterator::FallibleStreamingIterator;
+
+fn parallel_read(path: &str) -> Result<Vec<RecordBatch>> {
+    let file = &mut BufReader::new(File::open(path)?);
+    let (avro_schemas, schema, compression, marker) = read::read_metadata(file)?;
+    let schema = Arc::new(schema);
+    let avro_schemas = Arc::new(avro_schemas);
+    let projection = Arc::new(vec![true; schema.fields().len()]);
+
+    // prepare a channel to send compressed blocks to threads
+    let (tx, rx) = unbounded();
+
+    let start = SystemTime::now();
+    // read the blocks on this thread (IO bounded)
+    let mut blocks = read::BlockStreamIterator::new(file, marker);
+    let mut index = 0;
+    while let Some((block, rows)) = blocks.next()? {
+        tx.send((index, block.clone(), *rows)).unwrap();
+        index += 1;
+    }
+    drop(tx);
+    println!("produced {} blocks - {:?}", index, start.elapsed().unwrap());
+
+    let mut children = Vec::new();
+    // use 3 consumers to decompress and deserialize the blocks (CPU bounded)
+    for _ in 0..3 {
+        let rx_consumer = rx.clone();
+        let schema = schema.clone();
+        let avro_schemas = avro_schemas.clone();
+        let projection = projection.clone();
+        let child = thread::spawn(move || {
+            let mut decompressed = vec![];
+            let mut batches = vec![];
+            for (index, mut block, rows) in rx_consumer.iter() {
+                read::decompress_block(&mut block, &mut decompressed, compression)?;
+                let batch = read::deserialize(
+                    &decompressed,
+                    rows,
+                    schema.clone(),
+                    &avro_schemas,
+                    &projection,
+                )?;
+                batches.push((index, batch));
+            }
+            Result::Ok(batches)
+        });
+        children.push(child);
+    }
+
+    // reassemble the batches in the order of the blocks
+    let mut batches = vec![];
+    for child in children {
+        batches.extend(child.join().unwrap()?);
+    }
+    batches.sort_by_key(|(index, _)| *index);
+    println!("Finished - {:?}", start.elapsed().unwrap());
+
+    Ok(batches.into_iter().map(|(_, batch)| batch).collect())
+}
+
+fn main() -> Result<()> {
+    use std::env;
+    let args: Vec<String> = env::args().collect();
+    let file_path = &args[1];
+
+    let batches = parallel_read(file_path)?;
+    for batch in batches {
+        println!("{}", batch.num_rows())
+    }
+    Ok(())
+}
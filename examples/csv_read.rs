@@ -7,28 +7,28 @@ fn read_path(path: &str, projection: Option<&[usize]>) -> Result<RecordBatch> {
     // thus owns the read head.
     let mut reader = read::ReaderBuilder::new().from_path(path)?;
 
-    // Infers the schema using the default inferer. The inferer is just a function that maps a string
-    // to a `DataType`.
-    let schema = read::infer_schema(&mut reader, None, true, &read::infer)?;
-
-    // allocate space to read from CSV to. The size of this vec denotes how many rows are read.
-    let mut rows = vec![read::ByteRecord::default(); 100];
-
-    // skip 0 (excluding the header) and read up to 100 rows.
-    // this is IO-intensive and performs minimal CPU work. In particular,
-    // no deserialization is performed.
-    let rows_read = read::read_rows(&mut reader, 0, &mut rows)?;
-    let rows = &rows[..rows_read];
-
-    // parse the batches into a `RecordBatch`. This is CPU-intensive, has no IO,
+    // Infers the schema from the first 100 rows using the default inferer. The inferer is just
+    // a function that maps a string to a `DataType`.
+    // The rows read during inference are returned, so that they can be deserialized.
+    let options = read::InferSchemaOptions {
+        max_rows: Some(100),
+        has_header: true,
+        ..Default::default()
+    };
+    let (schema, rows) = read::infer_schema(&mut reader, &options, &read::infer)?;
+
+    // parse the rows into a `RecordBatch`. This is CPU-intensive, has no IO,
     // and can be performed on a different thread by passing `rows` through a channel.
-    read::deserialize_batch(
-        rows,
+    // Further rows can be read with `read::read_rows`, which is IO-intensive and performs
+    // minimal CPU work. In particular, no deserialization is performed.
+    let (batch, _) = read::deserialize_batch(
+        &rows,
         schema.fields(),
         projection,
         0,
-        read::deserialize_column,
-    )
+        &read::DeserializeOptions::default(),
+    )?;
+    Ok(batch)
 }
 
 fn main() -> Result<()> {
@@ -19,17 +19,20 @@ async fn main() -> Result<()> {
 
     let mut reader = AsyncReaderBuilder::new().create_reader(file);
 
-    let schema = Arc::new(infer_schema(&mut reader, None, true, &infer).await?);
-
-    let mut rows = vec![ByteRecord::default(); 100];
-    let rows_read = read_rows(&mut reader, 0, &mut rows).await?;
-
-    let batch = deserialize_batch(
-        &rows[..rows_read],
+    let options = InferSchemaOptions {
+        max_rows: Some(100),
+        has_header: true,
+        ..Default::default()
+    };
+    let (schema, rows) = infer_schema(&mut reader, &options, &infer).await?;
+    let schema = Arc::new(schema);
+
+    let (batch, _) = deserialize_batch(
+        &rows,
         schema.fields(),
         None,
         0,
-        deserialize_column,
+        &DeserializeOptions::default(),
     )?;
     println!("{}", batch.column(0));
     Ok(())
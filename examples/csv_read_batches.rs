@@ -0,0 +1,22 @@
+use std::fs::File;
+
+use arrow2::error::Result;
+use arrow2::io::csv::read;
+use arrow2::record_batch::RecordBatchReader;
+
+fn main() -> Result<()> {
+    use std::env;
+    let args: Vec<String> = env::args().collect();
+
+    let file_path = &args[1];
+
+    // infers the schema from the first 1000 rows and reads batches of 1024 rows,
+    // deserializing chunks of the file on 4 threads.
+    let reader = read::BatchReaderBuilder::new().build_parallel(File::open(file_path)?, 4)?;
+    println!("{:?}", reader.schema());
+
+    for batch in reader {
+        println!("{}", batch?.num_rows())
+    }
+    Ok(())
+}
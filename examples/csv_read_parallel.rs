@@ -15,13 +15,20 @@ fn parallel_read(path: &str) -> Result<Vec<RecordBatch>> {
     let (tx, rx) = unbounded();
 
     let mut reader = read::ReaderBuilder::new().from_path(path)?;
-    let schema = read::infer_schema(&mut reader, Some(batch_size * 10), has_header, &read::infer)?;
+    let options = read::InferSchemaOptions {
+        max_rows: Some(batch_size * 10),
+        has_header,
+        ..Default::default()
+    };
+    let (schema, inferred_rows) = read::infer_schema(&mut reader, &options, &read::infer)?;
     let schema = Arc::new(schema);
 
     let start = SystemTime::now();
     // spawn a thread to produce `Vec<ByteRecords>` (IO bounded)
     let child = thread::spawn(move || {
-        let mut line_number = 0;
+        // the rows read during inference are sent first
+        let mut line_number = inferred_rows.len();
+        tx.send((inferred_rows, line_number)).unwrap();
         let mut size = 1;
         while size > 0 {
             let mut rows = vec![read::ByteRecord::default(); batch_size];
@@ -42,12 +49,12 @@ fn parallel_read(path: &str) -> Result<Vec<RecordBatch>> {
             let (rows, line_number) = rx_consumer.recv().unwrap();
             let start = SystemTime::now();
             println!("consumer start - {}", line_number);
-            let batch = read::deserialize_batch(
+            let (batch, _) = read::deserialize_batch(
                 &rows,
                 consumer_schema.fields(),
                 projection,
                 0,
-                read::deserialize_column,
+                &read::DeserializeOptions::default(),
             )
             .unwrap();
             println!(
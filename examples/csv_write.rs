@@ -9,11 +9,11 @@ use arrow2::{
 };
 
 fn write_batch(path: &str, batches: &[RecordBatch]) -> Result<()> {
-    let writer = &mut write::WriterBuilder::new().from_path(path)?;
-
-    write::write_header(writer, batches[0].schema())?;
+    let writer = &mut std::fs::File::create(path)?;
 
     let options = write::SerializeOptions::default();
+    write::write_header(writer, batches[0].schema(), &options)?;
+
     batches
         .iter()
         .try_for_each(|batch| write::write_batch(writer, batch, &options))
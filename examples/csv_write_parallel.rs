@@ -1,3 +1,4 @@
+use std::io::Write;
 use std::sync::mpsc;
 use std::sync::mpsc::{Receiver, Sender};
 use std::sync::Arc;
@@ -15,8 +16,8 @@ fn parallel_write(path: &str, batches: [RecordBatch; 2]) -> Result<()> {
     let options = write::SerializeOptions::default();
 
     // write a header
-    let writer = &mut write::WriterBuilder::new().from_path(path)?;
-    write::write_header(writer, batches[0].schema())?;
+    let writer = &mut std::fs::File::create(path)?;
+    write::write_header(writer, batches[0].schema(), &options)?;
 
     // prepare a channel to send serialized records from threads
     let (tx, rx): (Sender<_>, Receiver<_>) = mpsc::channel();
@@ -39,9 +40,7 @@ fn parallel_write(path: &str, batches: [RecordBatch; 2]) -> Result<()> {
     for _ in 0..2 {
         // block: assumes that the order of batches matter.
         let records = rx.recv().unwrap();
-        records
-            .iter()
-            .try_for_each(|record| writer.write_byte_record(record))?
+        writer.write_all(&records)?;
     }
 
     for child in children {
@@ -0,0 +1,95 @@
+use crate::array::{Array, BooleanArray};
+use crate::datatypes::DataType;
+use crate::error::{ArrowError, Result};
+use crate::scalar::{BooleanScalar, Scalar};
+
+/// Returns whether any non-null value of the boolean array is `true`.
+///
+/// Returns `None` if the array is empty or only contains null values.
+/// ```
+/// use arrow2::{
+///   array::BooleanArray,
+///   compute::aggregate::any_boolean,
+/// };
+///
+/// let a = BooleanArray::from(vec![Some(false), None, Some(true)]);
+/// assert_eq!(any_boolean(&a), Some(true))
+/// ```
+pub fn any_boolean(array: &BooleanArray) -> Option<bool> {
+    // short circuit if all nulls / zero length array
+    if array.null_count() == array.len() {
+        return None;
+    }
+
+    // a bit is set iff its slot is valid and `true`
+    let set = match array.validity() {
+        Some(validity) => array.len() - (array.values() & validity).null_count(),
+        None => array.len() - array.values().null_count(),
+    };
+    Some(set > 0)
+}
+
+/// Returns whether all non-null values of the boolean array are `true`.
+///
+/// Returns `None` if the array is empty or only contains null values.
+/// ```
+/// use arrow2::{
+///   array::BooleanArray,
+///   compute::aggregate::all_boolean,
+/// };
+///
+/// let a = BooleanArray::from(vec![Some(true), None, Some(false)]);
+/// assert_eq!(all_boolean(&a), Some(false))
+/// ```
+pub fn all_boolean(array: &BooleanArray) -> Option<bool> {
+    // short circuit if all nulls / zero length array
+    if array.null_count() == array.len() {
+        return None;
+    }
+
+    // a bit is unset iff its slot is valid and `false`
+    let unset = match array.validity() {
+        Some(validity) => (array.values() | &!validity).null_count(),
+        None => array.values().null_count(),
+    };
+    Some(unset == 0)
+}
+
+/// Whether [`any`] is valid for `data_type`
+pub fn can_any(data_type: &DataType) -> bool {
+    data_type == &DataType::Boolean
+}
+
+/// Whether [`all`] is valid for `data_type`
+pub fn can_all(data_type: &DataType) -> bool {
+    data_type == &DataType::Boolean
+}
+
+fn as_boolean<'a>(array: &'a dyn Array, op: &str) -> Result<&'a BooleanArray> {
+    if array.data_type() != &DataType::Boolean {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "The `{}` operator does not support type `{}`",
+            op,
+            array.data_type(),
+        )));
+    }
+    Ok(array.as_any().downcast_ref::<BooleanArray>().unwrap())
+}
+
+/// Returns whether any non-null value of [`Array`] is `true`. The scalar is null when all
+/// elements are null.
+/// # Error
+/// Errors iff the type does not support this operation.
+pub fn any(array: &dyn Array) -> Result<Box<dyn Scalar>> {
+    let array = as_boolean(array, "any")?;
+    Ok(Box::new(BooleanScalar::new(any_boolean(array))))
+}
+
+/// Returns whether all non-null values of [`Array`] are `true`. The scalar is null when all
+/// elements are null.
+/// # Error
+/// Errors iff the type does not support this operation.
+pub fn all(array: &dyn Array) -> Result<Box<dyn Scalar>> {
+    let array = as_boolean(array, "all")?;
+    Ok(Box::new(BooleanScalar::new(all_boolean(array))))
+}
@@ -31,7 +31,7 @@ pub trait SimdOrd<T> {
 }
 
 /// Helper macro to perform min/max of binarys.
-fn min_max_binary<O: Offset, F: Fn(&[u8], &[u8]) -> bool>(
+fn reduce_binary<O: Offset, F: Fn(&[u8], &[u8]) -> bool>(
     array: &BinaryArray<O>,
     cmp: F,
 ) -> Option<&[u8]> {
@@ -67,7 +67,7 @@ fn min_max_binary<O: Offset, F: Fn(&[u8], &[u8]) -> bool>(
 }
 
 /// Helper macro to perform min/max of strings
-fn min_max_string<O: Offset, F: Fn(&str, &str) -> bool>(
+fn reduce_string<O: Offset, F: Fn(&str, &str) -> bool>(
     array: &Utf8Array<O>,
     cmp: F,
 ) -> Option<&str> {
@@ -270,24 +270,121 @@ where
     })
 }
 
+fn nonnull_min_max_primitive<T>(values: &[T]) -> (T, T)
+where
+    T: NativeType + Simd,
+    T::Simd: SimdOrd<T>,
+{
+    let chunks = values.chunks_exact(T::Simd::LANES);
+    let remainder = chunks.remainder();
+
+    let (min, max) = chunks.fold(
+        (T::Simd::new_min(), T::Simd::new_max()),
+        |(min, max), chunk| {
+            let chunk = T::Simd::from_chunk(chunk);
+            (min.min(chunk), max.max(chunk))
+        },
+    );
+
+    let min = min.min(T::Simd::from_incomplete_chunk(remainder, T::Simd::MAX));
+    let max = max.max(T::Simd::from_incomplete_chunk(remainder, T::Simd::MIN));
+
+    (min.min_element(), max.max_element())
+}
+
+/// Returns the mask of the valid slots of `validity_chunk`.
+#[inline]
+fn validity_mask<T: Simd>(
+    validity_chunk: <T::Simd as NativeSimd>::Chunk,
+) -> <T::Simd as NativeSimd>::Mask {
+    <T::Simd as NativeSimd>::Mask::from_chunk(validity_chunk)
+}
+
+fn null_min_max_primitive_impl<T, I>(values: &[T], mut validity_masks: I) -> (T, T)
+where
+    T: NativeType + Simd,
+    T::Simd: SimdOrd<T>,
+    I: BitChunkIterExact<<<T as Simd>::Simd as NativeSimd>::Chunk>,
+{
+    let mut chunks = values.chunks_exact(T::Simd::LANES);
+
+    let (min, max) = chunks.by_ref().zip(validity_masks.by_ref()).fold(
+        (T::Simd::new_min(), T::Simd::new_max()),
+        |(min, max), (chunk, validity_chunk)| {
+            let chunk = T::Simd::from_chunk(chunk);
+            (
+                min.min(chunk.select(validity_mask::<T>(validity_chunk), T::Simd::new_min())),
+                max.max(chunk.select(validity_mask::<T>(validity_chunk), T::Simd::new_max())),
+            )
+        },
+    );
+
+    let validity_chunk = validity_masks.remainder();
+    let remainder = T::Simd::from_incomplete_chunk(chunks.remainder(), T::Simd::MAX);
+    let min = min.min(remainder.select(validity_mask::<T>(validity_chunk), T::Simd::new_min()));
+    let remainder = T::Simd::from_incomplete_chunk(chunks.remainder(), T::Simd::MIN);
+    let max = max.max(remainder.select(validity_mask::<T>(validity_chunk), T::Simd::new_max()));
+
+    (min.min_element(), max.max_element())
+}
+
+/// # Panics
+/// iff `values.len() != bitmap.len()`.
+fn null_min_max_primitive<T>(values: &[T], bitmap: &Bitmap) -> (T, T)
+where
+    T: NativeType + Simd,
+    T::Simd: SimdOrd<T>,
+{
+    let (slice, offset, length) = bitmap.as_slice();
+    if offset == 0 {
+        let validity_masks = BitChunksExact::<<T::Simd as NativeSimd>::Chunk>::new(slice, length);
+        null_min_max_primitive_impl(values, validity_masks)
+    } else {
+        let validity_masks = bitmap.chunks::<<T::Simd as NativeSimd>::Chunk>();
+        null_min_max_primitive_impl(values, validity_masks)
+    }
+}
+
+/// Returns the minimum and maximum values in the array in a single pass, according to the
+/// natural order. See [`min_primitive`] and [`max_primitive`] for the handling of NaN.
+pub fn min_max_primitive<T>(array: &PrimitiveArray<T>) -> Option<(T, T)>
+where
+    T: NativeType + Simd,
+    T::Simd: SimdOrd<T>,
+{
+    let null_count = array.null_count();
+
+    // Includes case array.len() == 0
+    if null_count == array.len() {
+        return None;
+    }
+    let values = array.values();
+
+    Some(if let Some(validity) = array.validity() {
+        null_min_max_primitive(values, validity)
+    } else {
+        nonnull_min_max_primitive(values)
+    })
+}
+
 /// Returns the maximum value in the binary array, according to the natural order.
 pub fn max_binary<O: Offset>(array: &BinaryArray<O>) -> Option<&[u8]> {
-    min_max_binary(array, |a, b| a < b)
+    reduce_binary(array, |a, b| a < b)
 }
 
 /// Returns the minimum value in the binary array, according to the natural order.
 pub fn min_binary<O: Offset>(array: &BinaryArray<O>) -> Option<&[u8]> {
-    min_max_binary(array, |a, b| a > b)
+    reduce_binary(array, |a, b| a > b)
 }
 
 /// Returns the maximum value in the string array, according to the natural order.
 pub fn max_string<O: Offset>(array: &Utf8Array<O>) -> Option<&str> {
-    min_max_string(array, |a, b| a < b)
+    reduce_string(array, |a, b| a < b)
 }
 
 /// Returns the minimum value in the string array, according to the natural order.
 pub fn min_string<O: Offset>(array: &Utf8Array<O>) -> Option<&str> {
-    min_max_string(array, |a, b| a > b)
+    reduce_string(array, |a, b| a > b)
 }
 
 /// Returns the minimum value in the boolean array.
@@ -340,6 +437,62 @@ pub fn max_boolean(array: &BooleanArray) -> Option<bool> {
         .or(Some(false))
 }
 
+/// Returns the minimum and maximum of `iter`'s non-null items in a single pass.
+fn reduce_min_max<T: PartialOrd + Copy, I: Iterator<Item = Option<T>>>(iter: I) -> Option<(T, T)> {
+    iter.flatten().fold(None, |acc, item| match acc {
+        None => Some((item, item)),
+        Some((min, max)) => Some((
+            if item < min { item } else { min },
+            if item > max { item } else { max },
+        )),
+    })
+}
+
+/// Returns the minimum and maximum values in the binary array in a single pass, according to
+/// the natural order.
+pub fn min_max_binary<O: Offset>(array: &BinaryArray<O>) -> Option<(&[u8], &[u8])> {
+    reduce_min_max(array.iter())
+}
+
+/// Returns the minimum and maximum values in the string array in a single pass, according to
+/// the natural order.
+pub fn min_max_string<O: Offset>(array: &Utf8Array<O>) -> Option<(&str, &str)> {
+    reduce_min_max(array.iter())
+}
+
+/// Returns the minimum and maximum values in the boolean array in a single pass.
+///
+/// ```
+/// use arrow2::{
+///   array::BooleanArray,
+///   compute::aggregate::min_max_boolean,
+/// };
+///
+/// let a = BooleanArray::from(vec![Some(true), None, Some(false)]);
+/// assert_eq!(min_max_boolean(&a), Some((false, true)))
+/// ```
+pub fn min_max_boolean(array: &BooleanArray) -> Option<(bool, bool)> {
+    // short circuit if all nulls / zero length array
+    if array.null_count() == array.len() {
+        return None;
+    }
+
+    let mut has_false = false;
+    let mut has_true = false;
+    // short circuit as soon as both values were seen
+    for value in array.iter().flatten() {
+        if value {
+            has_true = true;
+        } else {
+            has_false = true;
+        }
+        if has_false && has_true {
+            break;
+        }
+    }
+    Some((!has_false, has_true))
+}
+
 macro_rules! dyn_primitive {
     ($ty:ty, $array:expr, $f:ident) => {{
         let array = $array
@@ -441,3 +594,117 @@ pub fn min(array: &dyn Array) -> Result<Box<dyn Scalar>> {
         }
     })
 }
+
+fn split<T>(min_max: Option<(T, T)>) -> (Option<T>, Option<T>) {
+    match min_max {
+        Some((min, max)) => (Some(min), Some(max)),
+        None => (None, None),
+    }
+}
+
+macro_rules! dyn_min_max_primitive {
+    ($ty:ty, $array:expr) => {{
+        let array = $array
+            .as_any()
+            .downcast_ref::<PrimitiveArray<$ty>>()
+            .unwrap();
+        let (min, max) = split(min_max_primitive::<$ty>(array));
+        (
+            Box::new(PrimitiveScalar::<$ty>::new($array.data_type().clone(), min))
+                as Box<dyn Scalar>,
+            Box::new(PrimitiveScalar::<$ty>::new($array.data_type().clone(), max))
+                as Box<dyn Scalar>,
+        )
+    }};
+}
+
+macro_rules! dyn_min_max_generic {
+    ($array_ty:ty, $scalar_ty:ty, $array:expr, $f:ident) => {{
+        let array = $array.as_any().downcast_ref::<$array_ty>().unwrap();
+        let (min, max) = split($f(array));
+        (
+            Box::new(<$scalar_ty>::new(min)) as Box<dyn Scalar>,
+            Box::new(<$scalar_ty>::new(max)) as Box<dyn Scalar>,
+        )
+    }};
+}
+
+/// Whether [`min`], [`max`] and [`min_max`] are valid for `data_type`
+pub fn can_min_max(data_type: &DataType) -> bool {
+    use DataType::*;
+    matches!(
+        data_type,
+        Boolean
+            | Int8
+            | Int16
+            | Int32
+            | Date32
+            | Time32(_)
+            | Interval(IntervalUnit::YearMonth)
+            | Int64
+            | Date64
+            | Time64(_)
+            | Timestamp(_, _)
+            | Duration(_)
+            | UInt8
+            | UInt16
+            | UInt32
+            | UInt64
+            | Float32
+            | Float64
+            | Utf8
+            | LargeUtf8
+            | Binary
+            | LargeBinary
+    )
+}
+
+/// Returns the minimum and maximum of [`Array`] in a single pass. The scalars are null when
+/// all elements are null.
+/// # Error
+/// Errors iff the type does not support this operation.
+pub fn min_max(array: &dyn Array) -> Result<(Box<dyn Scalar>, Box<dyn Scalar>)> {
+    Ok(match array.data_type() {
+        DataType::Boolean => {
+            dyn_min_max_generic!(BooleanArray, BooleanScalar, array, min_max_boolean)
+        }
+        DataType::Int8 => dyn_min_max_primitive!(i8, array),
+        DataType::Int16 => dyn_min_max_primitive!(i16, array),
+        DataType::Int32
+        | DataType::Date32
+        | DataType::Time32(_)
+        | DataType::Interval(IntervalUnit::YearMonth) => {
+            dyn_min_max_primitive!(i32, array)
+        }
+        DataType::Int64
+        | DataType::Date64
+        | DataType::Time64(_)
+        | DataType::Timestamp(_, _)
+        | DataType::Duration(_) => dyn_min_max_primitive!(i64, array),
+        DataType::UInt8 => dyn_min_max_primitive!(u8, array),
+        DataType::UInt16 => dyn_min_max_primitive!(u16, array),
+        DataType::UInt32 => dyn_min_max_primitive!(u32, array),
+        DataType::UInt64 => dyn_min_max_primitive!(u64, array),
+        DataType::Float16 => unreachable!(),
+        DataType::Float32 => dyn_min_max_primitive!(f32, array),
+        DataType::Float64 => dyn_min_max_primitive!(f64, array),
+        DataType::Utf8 => {
+            dyn_min_max_generic!(Utf8Array<i32>, Utf8Scalar<i32>, array, min_max_string)
+        }
+        DataType::LargeUtf8 => {
+            dyn_min_max_generic!(Utf8Array<i64>, Utf8Scalar<i64>, array, min_max_string)
+        }
+        DataType::Binary => {
+            dyn_min_max_generic!(BinaryArray<i32>, BinaryScalar<i32>, array, min_max_binary)
+        }
+        DataType::LargeBinary => {
+            dyn_min_max_generic!(BinaryArray<i64>, BinaryScalar<i64>, array, min_max_binary)
+        }
+        _ => {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "The `min_max` operator does not support type `{}`",
+                array.data_type(),
+            )))
+        }
+    })
+}
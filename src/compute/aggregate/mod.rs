@@ -5,6 +5,18 @@ pub use sum::*;
 mod min_max;
 pub use min_max::*;
 
+mod product;
+pub use product::*;
+
+mod stats;
+pub use stats::*;
+
+mod mode;
+pub use mode::*;
+
+mod boolean;
+pub use boolean::*;
+
 mod memory;
 pub use memory::*;
 mod simd;
@@ -0,0 +1,90 @@
+use crate::array::{new_null_array, Array, PrimitiveArray};
+use crate::datatypes::{DataType, IntervalUnit};
+use crate::error::{ArrowError, Result};
+use crate::scalar::{new_scalar, Scalar};
+use crate::types::NativeType;
+
+use super::super::group_by::groups;
+
+/// Returns the index of the first occurrence of the most frequent non-null value of `array`.
+fn arg_mode(array: &dyn Array) -> Result<Option<usize>> {
+    if array.null_count() == array.len() {
+        return Ok(None);
+    }
+    let groups = groups(&[array])?;
+
+    let mut counts = vec![0usize; groups.len()];
+    groups
+        .ids
+        .iter()
+        .enumerate()
+        .filter(|(row, _)| array.is_valid(*row))
+        .for_each(|(_, id)| counts[*id] += 1);
+
+    // groups are in the order they first appear, so ties resolve to the first value
+    let mut best = 0;
+    counts.iter().enumerate().for_each(|(id, count)| {
+        if *count > counts[best] {
+            best = id;
+        }
+    });
+    Ok(Some(groups.first[best]))
+}
+
+/// Returns the most frequent non-null value in the array. When multiple values are equally
+/// frequent, the one that appears first is returned. Floats are compared by their bit pattern.
+///
+/// Returns `None` if the array is empty or only contains null values.
+/// # Errors
+/// This function errors iff the type of `array` is not supported (see [`can_mode`]).
+pub fn mode_primitive<T: NativeType>(array: &PrimitiveArray<T>) -> Result<Option<T>> {
+    Ok(arg_mode(array)?.map(|index| array.value(index)))
+}
+
+/// Whether [`mode`] is valid for `data_type`
+pub fn can_mode(data_type: &DataType) -> bool {
+    use DataType::*;
+    matches!(
+        data_type,
+        Boolean
+            | Int8
+            | Int16
+            | Int32
+            | Date32
+            | Time32(_)
+            | Interval(IntervalUnit::YearMonth)
+            | Int64
+            | Date64
+            | Time64(_)
+            | Timestamp(_, _)
+            | Duration(_)
+            | UInt8
+            | UInt16
+            | UInt32
+            | UInt64
+            | Float32
+            | Float64
+            | Utf8
+            | LargeUtf8
+            | Binary
+            | LargeBinary
+    )
+}
+
+/// Returns the most frequent non-null element of `array` as a [`Scalar`] of the same type
+/// as `array`. The scalar is null when all elements are null. See [`mode_primitive`] for
+/// how ties are resolved.
+/// # Error
+/// Errors iff the type does not support this operation.
+pub fn mode(array: &dyn Array) -> Result<Box<dyn Scalar>> {
+    if !can_mode(array.data_type()) {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "The `mode` operator does not support type `{}`",
+            array.data_type(),
+        )));
+    }
+    Ok(match arg_mode(array)? {
+        Some(index) => new_scalar(array, index),
+        None => new_scalar(new_null_array(array.data_type().clone(), 1).as_ref(), 0),
+    })
+}
@@ -0,0 +1,159 @@
+use std::ops::Mul;
+
+use multiversion::multiversion;
+use num_traits::One;
+
+use crate::bitmap::utils::{BitChunkIterExact, BitChunksExact};
+use crate::datatypes::DataType;
+use crate::error::{ArrowError, Result};
+use crate::scalar::*;
+use crate::types::simd::*;
+use crate::types::NativeType;
+use crate::{
+    array::{Array, PrimitiveArray},
+    bitmap::Bitmap,
+};
+
+/// Object that can reduce itself to the product of its lanes. This is used in the context of
+/// SIMD to reduce a MD (e.g. `[f32; 16]`) into a single number (`f32`).
+pub trait Product<T> {
+    /// Reduces this element to a single value.
+    fn simd_product(self) -> T;
+}
+
+#[multiversion]
+#[clone(target = "x86_64+avx")]
+fn nonnull_product<T>(values: &[T]) -> T
+where
+    T: NativeType + Simd + One,
+    T::Simd: Product<T> + Mul<Output = T::Simd>,
+{
+    let (head, simd_vals, tail) = T::Simd::align(values);
+
+    // the head and tail are shorter than a lane: multiplying them as SIMD wraps integers
+    let head = T::Simd::from_incomplete_chunk(head, T::one());
+    let tail = T::Simd::from_incomplete_chunk(tail, T::one());
+    let mut reduced = head * tail;
+    for chunk in simd_vals {
+        reduced = reduced * *chunk;
+    }
+
+    reduced.simd_product()
+}
+
+/// # Panics
+/// iff `values.len() != bitmap.len()`.
+#[multiversion]
+#[clone(target = "x86_64+avx")]
+fn null_product_impl<T, I>(values: &[T], mut validity_masks: I) -> T
+where
+    T: NativeType + Simd + One,
+    T::Simd: Mul<Output = T::Simd> + Product<T>,
+    I: BitChunkIterExact<<<T as Simd>::Simd as NativeSimd>::Chunk>,
+{
+    let mut chunks = values.chunks_exact(T::Simd::LANES);
+    let ones = T::Simd::from_incomplete_chunk(&[], T::one());
+
+    let product =
+        chunks
+            .by_ref()
+            .zip(validity_masks.by_ref())
+            .fold(ones, |acc, (chunk, validity_chunk)| {
+                let chunk = T::Simd::from_chunk(chunk);
+                let mask = <T::Simd as NativeSimd>::Mask::from_chunk(validity_chunk);
+                let selected = chunk.select(mask, ones);
+                acc * selected
+            });
+
+    let remainder = T::Simd::from_incomplete_chunk(chunks.remainder(), T::one());
+    let mask = <T::Simd as NativeSimd>::Mask::from_chunk(validity_masks.remainder());
+    let remainder = remainder.select(mask, ones);
+    let reduced = product * remainder;
+
+    reduced.simd_product()
+}
+
+/// # Panics
+/// iff `values.len() != bitmap.len()`.
+fn null_product<T>(values: &[T], bitmap: &Bitmap) -> T
+where
+    T: NativeType + Simd + One,
+    T::Simd: Mul<Output = T::Simd> + Product<T>,
+{
+    let (slice, offset, length) = bitmap.as_slice();
+    if offset == 0 {
+        let validity_masks = BitChunksExact::<<T::Simd as NativeSimd>::Chunk>::new(slice, length);
+        null_product_impl(values, validity_masks)
+    } else {
+        let validity_masks = bitmap.chunks::<<T::Simd as NativeSimd>::Chunk>();
+        null_product_impl(values, validity_masks)
+    }
+}
+
+/// Returns the product of the values in the array. The product of integers wraps around
+/// on overflow.
+///
+/// Returns `None` if the array is empty or only contains null values.
+pub fn product_primitive<T>(array: &PrimitiveArray<T>) -> Option<T>
+where
+    T: NativeType + Simd + One,
+    T::Simd: Mul<Output = T::Simd> + Product<T>,
+{
+    let null_count = array.null_count();
+
+    if null_count == array.len() {
+        return None;
+    }
+
+    match array.validity() {
+        None => Some(nonnull_product(array.values())),
+        Some(bitmap) => Some(null_product(array.values(), bitmap)),
+    }
+}
+
+macro_rules! dyn_product {
+    ($ty:ty, $array:expr) => {{
+        let array = $array
+            .as_any()
+            .downcast_ref::<PrimitiveArray<$ty>>()
+            .unwrap();
+        Box::new(PrimitiveScalar::<$ty>::new(
+            $array.data_type().clone(),
+            product_primitive::<$ty>(array),
+        ))
+    }};
+}
+
+/// Whether [`product`] is valid for `data_type`
+pub fn can_product(data_type: &DataType) -> bool {
+    use DataType::*;
+    matches!(
+        data_type,
+        Int8 | Int16 | Int32 | Int64 | UInt8 | UInt16 | UInt32 | UInt64 | Float32 | Float64
+    )
+}
+
+/// Returns the product of all elements in `array` as a [`Scalar`] of the same type as `array`.
+/// The product of integers wraps around on overflow.
+/// # Error
+/// Errors iff the operation is not supported.
+pub fn product(array: &dyn Array) -> Result<Box<dyn Scalar>> {
+    Ok(match array.data_type() {
+        DataType::Int8 => dyn_product!(i8, array),
+        DataType::Int16 => dyn_product!(i16, array),
+        DataType::Int32 => dyn_product!(i32, array),
+        DataType::Int64 => dyn_product!(i64, array),
+        DataType::UInt8 => dyn_product!(u8, array),
+        DataType::UInt16 => dyn_product!(u16, array),
+        DataType::UInt32 => dyn_product!(u32, array),
+        DataType::UInt64 => dyn_product!(u64, array),
+        DataType::Float32 => dyn_product!(f32, array),
+        DataType::Float64 => dyn_product!(f64, array),
+        _ => {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "The `product` operator does not support type `{}`",
+                array.data_type(),
+            )))
+        }
+    })
+}
@@ -1,8 +1,9 @@
-use std::ops::Add;
+use std::ops::{Add, Mul};
 
 use crate::types::simd::*;
 
 use super::super::min_max::SimdOrd;
+use super::super::product::Product;
 use super::super::sum::Sum;
 
 macro_rules! simd_add {
@@ -53,6 +54,41 @@ simd_add!(i64x8, i64, 8, wrapping_add);
 simd_add!(f32x16, f32, 16, add);
 simd_add!(f64x8, f64, 8, add);
 
+macro_rules! simd_mul {
+    ($simd:tt, $type:ty, $lanes:expr, $mul:tt) => {
+        impl std::ops::Mul for $simd {
+            type Output = Self;
+
+            #[inline]
+            fn mul(self, rhs: Self) -> Self::Output {
+                let mut result = Self::default();
+                for i in 0..$lanes {
+                    result[i] = <$type>::$mul(self[i], rhs[i]);
+                }
+                result
+            }
+        }
+
+        impl Product<$type> for $simd {
+            #[inline]
+            fn simd_product(self) -> $type {
+                self.0.iter().copied().fold(1 as $type, <$type>::$mul)
+            }
+        }
+    };
+}
+
+simd_mul!(u8x64, u8, 64, wrapping_mul);
+simd_mul!(u16x32, u16, 32, wrapping_mul);
+simd_mul!(u32x16, u32, 16, wrapping_mul);
+simd_mul!(u64x8, u64, 8, wrapping_mul);
+simd_mul!(i8x64, i8, 64, wrapping_mul);
+simd_mul!(i16x32, i16, 32, wrapping_mul);
+simd_mul!(i32x16, i32, 16, wrapping_mul);
+simd_mul!(i64x8, i64, 8, wrapping_mul);
+simd_mul!(f32x16, f32, 16, mul);
+simd_mul!(f64x8, f64, 8, mul);
+
 macro_rules! simd_ord_int {
     ($simd:tt, $type:ty) => {
         impl SimdOrd<$type> for $simd {
@@ -1,6 +1,7 @@
 use crate::types::simd::*;
 
 use super::super::min_max::SimdOrd;
+use super::super::product::Product;
 use super::super::sum::Sum;
 
 macro_rules! simd_sum {
@@ -25,6 +26,28 @@ simd_sum!(i16x32, i16, wrapping_sum);
 simd_sum!(i32x16, i32, wrapping_sum);
 simd_sum!(i64x8, i64, wrapping_sum);
 
+macro_rules! simd_product {
+    ($simd:tt, $type:ty, $product:tt) => {
+        impl Product<$type> for $simd {
+            #[inline]
+            fn simd_product(self) -> $type {
+                self.$product()
+            }
+        }
+    };
+}
+
+simd_product!(f32x16, f32, product);
+simd_product!(f64x8, f64, product);
+simd_product!(u8x64, u8, wrapping_product);
+simd_product!(u16x32, u16, wrapping_product);
+simd_product!(u32x16, u32, wrapping_product);
+simd_product!(u64x8, u64, wrapping_product);
+simd_product!(i8x64, i8, wrapping_product);
+simd_product!(i16x32, i16, wrapping_product);
+simd_product!(i32x16, i32, wrapping_product);
+simd_product!(i64x8, i64, wrapping_product);
+
 macro_rules! simd_ord_int {
     ($simd:tt, $type:ty) => {
         impl SimdOrd<$type> for $simd {
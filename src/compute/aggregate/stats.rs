@@ -0,0 +1,271 @@
+use std::cmp::Ordering;
+
+use num_traits::AsPrimitive;
+
+use crate::array::{ord::total_cmp_f64, Array, PrimitiveArray};
+use crate::datatypes::DataType;
+use crate::error::{ArrowError, Result};
+use crate::scalar::{PrimitiveScalar, Scalar};
+use crate::types::NativeType;
+
+/// How [`quantile`] computes a quantile that lies between two values `i < j`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuantileInterpolation {
+    /// `i + (j - i) * fraction`, where `fraction` is the fractional part of the quantile's index
+    Linear,
+    /// `i`
+    Lower,
+    /// `j`
+    Higher,
+    /// `(i + j) / 2`
+    Midpoint,
+    /// `i` or `j`, whichever is nearest; ties are resolved to the even index
+    Nearest,
+}
+
+/// Returns the number of non-null elements in `array`. Valid for all types.
+pub fn count(array: &dyn Array) -> usize {
+    array.len() - array.null_count()
+}
+
+/// Returns the sum of the non-null values of `array` as `f64`, so that it does not overflow.
+/// This is intentionally a scalar loop: [`sum_primitive`](super::sum_primitive) is vectorized
+/// but accumulates in `T`, which overflows for integers (e.g. the sum of `i8`s).
+fn sum_as_f64<T: NativeType + AsPrimitive<f64>>(array: &PrimitiveArray<T>) -> f64 {
+    match array.validity() {
+        None => array.values().iter().map(|x| x.as_()).sum(),
+        Some(validity) => array
+            .values()
+            .iter()
+            .zip(validity.iter())
+            .filter(|(_, is_valid)| *is_valid)
+            .map(|(x, _)| x.as_())
+            .sum(),
+    }
+}
+
+/// Returns the arithmetic mean of the non-null values in the array.
+/// Values are summed as `f64` without SIMD, so that integers do not overflow.
+///
+/// Returns `None` if the array is empty or only contains null values.
+pub fn mean_primitive<T>(array: &PrimitiveArray<T>) -> Option<f64>
+where
+    T: NativeType + AsPrimitive<f64>,
+{
+    let count = count(array);
+    if count == 0 {
+        return None;
+    }
+    Some(sum_as_f64(array) / count as f64)
+}
+
+/// Returns the variance of the non-null values in the array, whose sum of squared deviations
+/// is divided by `n - ddof` ("delta degrees of freedom"), `n` being the number of
+/// non-null values. Use `ddof = 0` for the population variance and `ddof = 1` for the
+/// sample variance. Like [`mean_primitive`], it is computed as `f64` without SIMD.
+///
+/// Returns `None` if the array does not have more than `ddof` non-null values.
+pub fn variance_primitive<T>(array: &PrimitiveArray<T>, ddof: usize) -> Option<f64>
+where
+    T: NativeType + AsPrimitive<f64>,
+{
+    let count = count(array);
+    if count <= ddof {
+        return None;
+    }
+    let mean = sum_as_f64(array) / count as f64;
+
+    let squares: f64 = array
+        .iter()
+        .flatten()
+        .map(|x| {
+            let deviation = x.as_() - mean;
+            deviation * deviation
+        })
+        .sum();
+    Some(squares / (count - ddof) as f64)
+}
+
+/// Returns the standard deviation of the non-null values in the array, i.e. the square root
+/// of its [`variance_primitive`] with `ddof`.
+///
+/// Returns `None` if the array does not have more than `ddof` non-null values.
+pub fn stddev_primitive<T>(array: &PrimitiveArray<T>, ddof: usize) -> Option<f64>
+where
+    T: NativeType + AsPrimitive<f64>,
+{
+    variance_primitive(array, ddof).map(|x| x.sqrt())
+}
+
+/// Returns the `quantile` (in `[0, 1]`) of the non-null values in the array, interpolating
+/// between the two nearest values according to `interpolation`.
+/// Values are ordered by IEEE 754's total order, i.e. NaN is greater than any other value.
+///
+/// Returns `None` if the array is empty or only contains null values.
+/// # Errors
+/// This function errors iff `quantile` is not in `[0, 1]`.
+pub fn quantile_primitive<T>(
+    array: &PrimitiveArray<T>,
+    quantile: f64,
+    interpolation: QuantileInterpolation,
+) -> Result<Option<f64>>
+where
+    T: NativeType + AsPrimitive<f64>,
+{
+    if !(0.0..=1.0).contains(&quantile) {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "The quantile must be in [0, 1], but it is {}",
+            quantile
+        )));
+    }
+    let mut values = array
+        .iter()
+        .flatten()
+        .map(|x| x.as_())
+        .collect::<Vec<f64>>();
+    if values.is_empty() {
+        return Ok(None);
+    }
+
+    let index = quantile * (values.len() - 1) as f64;
+    let lower = index.floor() as usize;
+    let higher = index.ceil() as usize;
+    let fraction = index - lower as f64;
+
+    // partial sort: the lower value is in place and all values after it are not smaller
+    let (_, lower_value, higher_values) = values.select_nth_unstable_by(lower, total_cmp_f64);
+    let lower_value = *lower_value;
+    if higher == lower {
+        return Ok(Some(lower_value));
+    }
+    let higher_value = *higher_values
+        .iter()
+        .min_by(|x, y| total_cmp_f64(x, y))
+        .unwrap();
+
+    Ok(Some(match interpolation {
+        QuantileInterpolation::Linear => lower_value + (higher_value - lower_value) * fraction,
+        QuantileInterpolation::Lower => lower_value,
+        QuantileInterpolation::Higher => higher_value,
+        QuantileInterpolation::Midpoint => (lower_value + higher_value) / 2.0,
+        QuantileInterpolation::Nearest => match fraction.partial_cmp(&0.5) {
+            Some(Ordering::Less) => lower_value,
+            Some(Ordering::Greater) => higher_value,
+            _ if lower % 2 == 0 => lower_value,
+            _ => higher_value,
+        },
+    }))
+}
+
+/// Returns the median of the non-null values in the array, i.e. its 0.5 quantile with
+/// [`QuantileInterpolation::Linear`].
+///
+/// Returns `None` if the array is empty or only contains null values.
+pub fn median_primitive<T>(array: &PrimitiveArray<T>) -> Option<f64>
+where
+    T: NativeType + AsPrimitive<f64>,
+{
+    quantile_primitive(array, 0.5, QuantileInterpolation::Linear).unwrap()
+}
+
+macro_rules! dyn_stat {
+    ($array:expr, $op:expr, | $_:tt $T:ident | $($body:tt)*) => ({
+        macro_rules! __with_ty__ {( $_ $T:ident ) => ( $($body)* )}
+        let value: Option<f64> = match $array.data_type() {
+            DataType::Int8 => __with_ty__! { i8 },
+            DataType::Int16 => __with_ty__! { i16 },
+            DataType::Int32 => __with_ty__! { i32 },
+            DataType::Int64 => __with_ty__! { i64 },
+            DataType::UInt8 => __with_ty__! { u8 },
+            DataType::UInt16 => __with_ty__! { u16 },
+            DataType::UInt32 => __with_ty__! { u32 },
+            DataType::UInt64 => __with_ty__! { u64 },
+            DataType::Float32 => __with_ty__! { f32 },
+            DataType::Float64 => __with_ty__! { f64 },
+            _ => {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "The `{}` operator does not support type `{}`",
+                    $op,
+                    $array.data_type(),
+                )))
+            }
+        };
+        Ok(Box::new(PrimitiveScalar::<f64>::new(DataType::Float64, value)) as Box<dyn Scalar>)
+    });
+}
+
+fn is_numeric(data_type: &DataType) -> bool {
+    use DataType::*;
+    matches!(
+        data_type,
+        Int8 | Int16 | Int32 | Int64 | UInt8 | UInt16 | UInt32 | UInt64 | Float32 | Float64
+    )
+}
+
+/// Whether [`mean`] is valid for `data_type`
+pub fn can_mean(data_type: &DataType) -> bool {
+    is_numeric(data_type)
+}
+
+/// Whether [`variance`] and [`stddev`] are valid for `data_type`
+pub fn can_variance(data_type: &DataType) -> bool {
+    is_numeric(data_type)
+}
+
+/// Whether [`quantile`] and [`median`] are valid for `data_type`
+pub fn can_quantile(data_type: &DataType) -> bool {
+    is_numeric(data_type)
+}
+
+/// Returns the arithmetic mean of the non-null elements of `array` as a `Float64` [`Scalar`].
+/// The scalar is null when all elements are null.
+/// # Error
+/// Errors iff the operation is not supported.
+pub fn mean(array: &dyn Array) -> Result<Box<dyn Scalar>> {
+    dyn_stat!(array, "mean", |$T| {
+        mean_primitive::<$T>(array.as_any().downcast_ref().unwrap())
+    })
+}
+
+/// Returns the variance of the non-null elements of `array` with `ddof` as a `Float64`
+/// [`Scalar`]. See [`variance_primitive`] for details.
+/// # Error
+/// Errors iff the operation is not supported.
+pub fn variance(array: &dyn Array, ddof: usize) -> Result<Box<dyn Scalar>> {
+    dyn_stat!(array, "variance", |$T| {
+        variance_primitive::<$T>(array.as_any().downcast_ref().unwrap(), ddof)
+    })
+}
+
+/// Returns the standard deviation of the non-null elements of `array` with `ddof` as a
+/// `Float64` [`Scalar`]. See [`variance_primitive`] for details.
+/// # Error
+/// Errors iff the operation is not supported.
+pub fn stddev(array: &dyn Array, ddof: usize) -> Result<Box<dyn Scalar>> {
+    dyn_stat!(array, "stddev", |$T| {
+        stddev_primitive::<$T>(array.as_any().downcast_ref().unwrap(), ddof)
+    })
+}
+
+/// Returns the `quantile` of the non-null elements of `array` as a `Float64` [`Scalar`].
+/// See [`quantile_primitive`] for details.
+/// # Error
+/// Errors iff the operation is not supported or `quantile` is not in `[0, 1]`.
+pub fn quantile(
+    array: &dyn Array,
+    quantile: f64,
+    interpolation: QuantileInterpolation,
+) -> Result<Box<dyn Scalar>> {
+    dyn_stat!(array, "quantile", |$T| {
+        quantile_primitive::<$T>(array.as_any().downcast_ref().unwrap(), quantile, interpolation)?
+    })
+}
+
+/// Returns the median of the non-null elements of `array` as a `Float64` [`Scalar`].
+/// # Error
+/// Errors iff the operation is not supported.
+pub fn median(array: &dyn Array) -> Result<Box<dyn Scalar>> {
+    dyn_stat!(array, "median", |$T| {
+        median_primitive::<$T>(array.as_any().downcast_ref().unwrap())
+    })
+}
@@ -0,0 +1,325 @@
+//! Contains the hash-based [`group_by`] operator, that aggregates columns by the
+//! unique rows of one or more key columns.
+//!
+//! ```
+//! use arrow2::array::{Array, Int32Array, Utf8Array};
+//! use arrow2::compute::group_by::{group_by, Aggregation};
+//! # use arrow2::error::Result;
+//! # fn main() -> Result<()> {
+//! let keys = Utf8Array::<i32>::from_slice(["a", "b", "a"]);
+//! let values = Int32Array::from(&[Some(1), Some(2), None]);
+//!
+//! let (keys, values) = group_by(&[&keys], &[(&values, Aggregation::Sum)])?;
+//! assert_eq!(keys[0].as_ref(), &Utf8Array::<i32>::from_slice(["a", "b"]) as &dyn Array);
+//! assert_eq!(values[0].as_ref(), &Int32Array::from_slice([1, 2]) as &dyn Array);
+//! # Ok(())
+//! # }
+//! ```
+use std::cmp::Ordering;
+use std::ops::Add;
+
+use hash_hasher::HashedMap;
+use num_traits::AsPrimitive;
+
+use crate::array::{ord::build_compare, Array, PrimitiveArray};
+use crate::bitmap::MutableBitmap;
+use crate::datatypes::DataType;
+use crate::error::{ArrowError, Result};
+use crate::types::NativeType;
+
+use super::take::take;
+use super::utils::{build_row_eq, hash_rows};
+
+/// The groups of the rows of one or more key columns: rows whose keys are equal belong
+/// to the same group. Null keys are equal to each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Groups {
+    /// The group of each row, in `0..self.len()`
+    pub ids: Vec<usize>,
+    /// The first row of each group, in the order the groups first appear
+    pub first: Vec<usize>,
+}
+
+impl Groups {
+    /// The number of groups
+    pub fn len(&self) -> usize {
+        self.first.len()
+    }
+
+    /// Whether there are no groups, i.e. the keys have no rows
+    pub fn is_empty(&self) -> bool {
+        self.first.is_empty()
+    }
+
+    /// The last row of each group
+    pub fn last(&self) -> Vec<usize> {
+        let mut last = self.first.clone();
+        self.ids
+            .iter()
+            .enumerate()
+            .for_each(|(row, id)| last[*id] = row);
+        last
+    }
+}
+
+fn check_lengths<'a, I: Iterator<Item = &'a dyn Array>>(mut columns: I) -> Result<usize> {
+    let len = columns.next().map(|x| x.len()).unwrap_or_default();
+    if columns.any(|x| x.len() != len) {
+        return Err(ArrowError::InvalidArgumentError(
+            "Grouping requires all columns to have the same length".to_string(),
+        ));
+    }
+    Ok(len)
+}
+
+/// Returns the [`Groups`] of the rows of `keys`.
+/// Supported key types are the types supported by [`hash`](super::hash::hash)
+/// that have a natural order, floats (compared by their bit pattern) and dictionaries
+/// of them.
+/// # Errors
+/// This function errors iff `keys` is empty, the keys have different lengths or a key's
+/// type is not supported.
+pub fn groups(keys: &[&dyn Array]) -> Result<Groups> {
+    if keys.is_empty() {
+        return Err(ArrowError::InvalidArgumentError(
+            "Grouping requires at least one key".to_string(),
+        ));
+    }
+    let len = check_lengths(keys.iter().copied())?;
+    let hashes = hash_rows(keys)?;
+    let eq = build_row_eq(keys, keys)?;
+
+    // the latest group of each hash; groups with the same hash are chained via `previous`
+    let mut map = HashedMap::<u64, usize>::default();
+    let mut previous: Vec<Option<usize>> = vec![];
+    let mut ids = Vec::with_capacity(len);
+    let mut first = vec![];
+    for (row, hash) in hashes.into_iter().enumerate() {
+        let mut candidate = map.get(&hash).copied();
+        let id = loop {
+            match candidate {
+                Some(group) if eq(first[group], row) => break group,
+                Some(group) => candidate = previous[group],
+                None => {
+                    let group = first.len();
+                    first.push(row);
+                    previous.push(map.insert(hash, group));
+                    break group;
+                }
+            }
+        };
+        ids.push(id);
+    }
+    Ok(Groups { ids, first })
+}
+
+/// An aggregation of the values of a group
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Aggregation {
+    /// The number of non-null values, as `UInt64`
+    Count,
+    /// The sum of the non-null values, of the same type as the values. Integers wrap
+    /// around on overflow.
+    Sum,
+    /// The minimum of the non-null values
+    Min,
+    /// The maximum of the non-null values
+    Max,
+    /// The mean of the non-null values, as `Float64`
+    Mean,
+    /// The value (possibly null) of the first row
+    First,
+    /// The value (possibly null) of the last row
+    Last,
+}
+
+macro_rules! with_match_numeric_type {(
+    $data_type:expr, | $_:tt $T:ident | $($body:tt)*
+) => ({
+    macro_rules! __with_ty__ {( $_ $T:ident ) => ( $($body)* )}
+    use crate::datatypes::DataType::*;
+    match $data_type {
+        Int8 => __with_ty__! { i8 },
+        Int16 => __with_ty__! { i16 },
+        Int32 => __with_ty__! { i32 },
+        Int64 => __with_ty__! { i64 },
+        UInt8 => __with_ty__! { u8 },
+        UInt16 => __with_ty__! { u16 },
+        UInt32 => __with_ty__! { u32 },
+        UInt64 => __with_ty__! { u64 },
+        Float32 => __with_ty__! { f32 },
+        Float64 => __with_ty__! { f64 },
+        other => return Err(ArrowError::NotYetImplemented(format!(
+            "Aggregating values of type {:?} is not supported",
+            other
+        )))
+    }
+})}
+
+fn count(array: &dyn Array, groups: &Groups) -> PrimitiveArray<u64> {
+    let mut counts = vec![0u64; groups.len()];
+    if array.null_count() == array.len() {
+        // e.g. `NullArray`, whose validity is `None`
+    } else if let Some(validity) = array.validity() {
+        groups
+            .ids
+            .iter()
+            .zip(validity.iter())
+            .filter(|(_, is_valid)| *is_valid)
+            .for_each(|(id, _)| counts[*id] += 1);
+    } else {
+        groups.ids.iter().for_each(|id| counts[*id] += 1);
+    }
+    PrimitiveArray::<u64>::from_data(DataType::UInt64, counts.into(), None)
+}
+
+/// Addition of the values of [`Aggregation::Sum`]: integers wrap around on overflow.
+trait SumAdd: NativeType {
+    fn sum_add(self, rhs: Self) -> Self;
+}
+
+macro_rules! sum_add {
+    ($type:ty, $add:tt) => {
+        impl SumAdd for $type {
+            #[inline]
+            fn sum_add(self, rhs: Self) -> Self {
+                <$type>::$add(self, rhs)
+            }
+        }
+    };
+}
+
+sum_add!(u8, wrapping_add);
+sum_add!(u16, wrapping_add);
+sum_add!(u32, wrapping_add);
+sum_add!(u64, wrapping_add);
+sum_add!(i8, wrapping_add);
+sum_add!(i16, wrapping_add);
+sum_add!(i32, wrapping_add);
+sum_add!(i64, wrapping_add);
+sum_add!(i128, wrapping_add);
+sum_add!(f32, add);
+sum_add!(f64, add);
+
+fn sum_primitive<T: SumAdd>(array: &PrimitiveArray<T>, groups: &Groups) -> PrimitiveArray<T> {
+    let mut values = vec![T::default(); groups.len()];
+    let mut validity = MutableBitmap::from_len_zeroed(groups.len());
+    groups.ids.iter().zip(array.iter()).for_each(|(id, value)| {
+        if let Some(value) = value {
+            values[*id] = values[*id].sum_add(*value);
+            validity.set(*id, true);
+        }
+    });
+    PrimitiveArray::<T>::from_data(array.data_type().clone(), values.into(), validity.into())
+}
+
+fn mean_primitive<T>(array: &PrimitiveArray<T>, groups: &Groups) -> PrimitiveArray<f64>
+where
+    T: NativeType + AsPrimitive<f64>,
+{
+    let mut sums = vec![0.0f64; groups.len()];
+    let mut counts = vec![0usize; groups.len()];
+    groups.ids.iter().zip(array.iter()).for_each(|(id, value)| {
+        if let Some(value) = value {
+            sums[*id] += value.as_();
+            counts[*id] += 1;
+        }
+    });
+    let iter = sums
+        .iter()
+        .zip(counts.iter())
+        .map(|(sum, count)| (*count > 0).then(|| sum / *count as f64));
+    PrimitiveArray::<f64>::from_trusted_len_iter(iter)
+}
+
+/// Returns the value of each group that is first in the order `ordering` of its type.
+fn extreme(array: &dyn Array, groups: &Groups, ordering: Ordering) -> Result<Box<dyn Array>> {
+    let mut args: Vec<Option<usize>> = vec![None; groups.len()];
+    if array.null_count() != array.len() {
+        let cmp = build_compare(array, array)?;
+        groups
+            .ids
+            .iter()
+            .enumerate()
+            .filter(|(row, _)| array.is_valid(*row))
+            .for_each(|(row, id)| match args[*id] {
+                Some(current) if cmp(row, current) != ordering => {}
+                _ => args[*id] = Some(row),
+            });
+    }
+    let indices = PrimitiveArray::<u64>::from_trusted_len_iter(
+        args.iter().map(|arg| arg.map(|arg| arg as u64)),
+    );
+    take(array, &indices)
+}
+
+fn take_rows(array: &dyn Array, rows: &[usize]) -> Result<Box<dyn Array>> {
+    let indices =
+        PrimitiveArray::<u64>::from_trusted_len_values_iter(rows.iter().map(|row| *row as u64));
+    take(array, &indices)
+}
+
+/// Aggregates `values` by `groups`, returning an array with one slot per group.
+///
+/// The sum and mean are supported for numeric types; the sum of a group without non-null
+/// values is null and the sum of integers wraps around on overflow. The minimum and maximum are supported for types with a natural order
+/// (see [`build_compare`]); floats follow IEEE 754's total order.
+/// # Errors
+/// This function errors iff `values`'s length differs from the number of rows in `groups`
+/// or the aggregation is not supported for its type.
+pub fn aggregate(
+    values: &dyn Array,
+    groups: &Groups,
+    aggregation: Aggregation,
+) -> Result<Box<dyn Array>> {
+    if values.len() != groups.ids.len() {
+        return Err(ArrowError::InvalidArgumentError(
+            "Aggregating requires the values to have the same length as the keys".to_string(),
+        ));
+    }
+    Ok(match aggregation {
+        Aggregation::Count => Box::new(count(values, groups)) as Box<dyn Array>,
+        Aggregation::Sum => {
+            if let DataType::Decimal(_, _) = values.data_type() {
+                let values = values.as_any().downcast_ref().unwrap();
+                Box::new(sum_primitive::<i128>(values, groups)) as Box<dyn Array>
+            } else {
+                with_match_numeric_type!(values.data_type(), |$T| {
+                    let values = values.as_any().downcast_ref().unwrap();
+                    Box::new(sum_primitive::<$T>(values, groups)) as Box<dyn Array>
+                })
+            }
+        }
+        Aggregation::Mean => with_match_numeric_type!(values.data_type(), |$T| {
+            let values = values.as_any().downcast_ref().unwrap();
+            Box::new(mean_primitive::<$T>(values, groups)) as Box<dyn Array>
+        }),
+        Aggregation::Min => extreme(values, groups, Ordering::Less)?,
+        Aggregation::Max => extreme(values, groups, Ordering::Greater)?,
+        Aggregation::First => take_rows(values, &groups.first)?,
+        Aggregation::Last => take_rows(values, &groups.last())?,
+    })
+}
+
+/// The unique keys and the aggregated values returned by [`group_by`]
+pub type GroupBy = (Vec<Box<dyn Array>>, Vec<Box<dyn Array>>);
+
+/// Groups the rows of `keys` and aggregates each of `values` by their groups, returning the
+/// unique keys (in the order they first appear) and the aggregated values.
+/// See [`groups`] and [`aggregate`] for the supported types.
+/// # Errors
+/// This function errors iff `keys` is empty, the columns have different lengths or a type
+/// is not supported.
+pub fn group_by(keys: &[&dyn Array], values: &[(&dyn Array, Aggregation)]) -> Result<GroupBy> {
+    let groups = groups(keys)?;
+
+    let keys = keys
+        .iter()
+        .map(|key| take_rows(*key, &groups.first))
+        .collect::<Result<Vec<_>>>()?;
+    let values = values
+        .iter()
+        .map(|(values, aggregation)| aggregate(*values, &groups, *aggregation))
+        .collect::<Result<Vec<_>>>()?;
+    Ok((keys, values))
+}
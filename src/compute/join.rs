@@ -0,0 +1,257 @@
+//! Contains the [`hash_join`] operator, that computes the indices of the matching rows of
+//! two sets of key columns, and [`join`], that joins two [`RecordBatch`]es.
+//!
+//! ```
+//! use arrow2::array::{Int32Array, UInt32Array};
+//! use arrow2::compute::join::{hash_join, JoinType};
+//! # use arrow2::error::Result;
+//! # fn main() -> Result<()> {
+//! let left = Int32Array::from_slice([1, 2, 3]);
+//! let right = Int32Array::from_slice([3, 1, 1]);
+//!
+//! let (left, right) = hash_join::<u32>(&[&left], &[&right], JoinType::Inner, false)?;
+//! assert_eq!(left, UInt32Array::from_slice([2, 0, 0]));
+//! assert_eq!(right, UInt32Array::from_slice([0, 1, 2]));
+//! # Ok(())
+//! # }
+//! ```
+use std::sync::Arc;
+
+use hash_hasher::HashedMap;
+
+use crate::array::{Array, PrimitiveArray};
+use crate::bitmap::{Bitmap, MutableBitmap};
+use crate::datatypes::{Field, Schema};
+use crate::error::{ArrowError, Result};
+use crate::record_batch::RecordBatch;
+use crate::types::Index;
+
+use super::take::take;
+use super::utils::{build_row_eq, hash_rows};
+
+/// The type of a join between a left and a right set of rows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JoinType {
+    /// The pairs of matching rows
+    Inner,
+    /// The pairs of matching rows and the left rows without a match
+    Left,
+    /// The pairs of matching rows and the right rows without a match
+    Right,
+    /// The pairs of matching rows and the left and right rows without a match
+    Full,
+    /// The left rows with at least one match
+    Semi,
+    /// The left rows without a match
+    Anti,
+}
+
+/// Returns whether each row of `columns` has a null slot in any column.
+fn has_nulls(columns: &[&dyn Array], len: usize) -> Option<Vec<bool>> {
+    let columns = columns
+        .iter()
+        .filter(|column| column.null_count() > 0)
+        .collect::<Vec<_>>();
+    if columns.is_empty() {
+        return None;
+    }
+    Some(
+        (0..len)
+            .map(|row| columns.iter().any(|column| column.is_null(row)))
+            .collect(),
+    )
+}
+
+fn check_keys(keys: &[&dyn Array]) -> Result<usize> {
+    let len = keys.first().map(|x| x.len()).unwrap_or_default();
+    if keys.iter().any(|x| x.len() != len) {
+        return Err(ArrowError::InvalidArgumentError(
+            "Joining requires all key columns of a side to have the same length".to_string(),
+        ));
+    }
+    Ok(len)
+}
+
+fn to_index<I: Index>(row: usize) -> Result<I> {
+    I::from_usize(row).ok_or_else(|| {
+        ArrowError::InvalidArgumentError(format!(
+            "The row {} of the join does not fit in the index type",
+            row
+        ))
+    })
+}
+
+fn to_indices<I: Index>(rows: &[Option<usize>]) -> Result<PrimitiveArray<I>> {
+    let indices = rows
+        .iter()
+        .map(|row| row.map(to_index::<I>).transpose())
+        .collect::<Result<Vec<_>>>()?;
+    Ok(PrimitiveArray::<I>::from_trusted_len_iter(
+        indices.into_iter(),
+    ))
+}
+
+/// Computes the join of the rows of the keys `left` and `right`, returning the indices of the
+/// left and right rows of each output row, to be used by [`take`].
+///
+/// A hash table is built from the `left` keys and probed with the `right` keys. Matching pairs
+/// are returned in the order of the right rows followed, for left and full joins, by the left
+/// rows without a match. The indices of rows without a match in the other side are null.
+/// For semi and anti joins only the left indices are meaningful; the right indices are null.
+///
+/// Rows whose keys have a null are only equal to each other when `null_equals_null` is true.
+/// # Errors
+/// This function errors iff the keys are empty, `left` and `right` have a different number
+/// of columns or types, the key columns of a side have different lengths, a key's type is not
+/// supported (see [`groups`](super::group_by::groups)) or a row does not fit in `I`.
+pub fn hash_join<I: Index>(
+    left: &[&dyn Array],
+    right: &[&dyn Array],
+    join_type: JoinType,
+    null_equals_null: bool,
+) -> Result<(PrimitiveArray<I>, PrimitiveArray<I>)> {
+    if left.is_empty() || left.len() != right.len() {
+        return Err(ArrowError::InvalidArgumentError(
+            "Joining requires the same (non-zero) number of key columns on both sides".to_string(),
+        ));
+    }
+    let left_len = check_keys(left)?;
+    let right_len = check_keys(right)?;
+    let eq = build_row_eq(left, right)?;
+
+    let (left_nulls, right_nulls) = if null_equals_null {
+        (None, None)
+    } else {
+        (has_nulls(left, left_len), has_nulls(right, right_len))
+    };
+    let is_null = |nulls: &Option<Vec<bool>>, row: usize| {
+        nulls.as_ref().map(|nulls| nulls[row]).unwrap_or(false)
+    };
+
+    // build: the first left row of each hash; rows with the same hash are chained via `next`.
+    // Rows are inserted in reverse order so that chains are in increasing order.
+    let mut map = HashedMap::<u64, usize>::default();
+    let mut next: Vec<Option<usize>> = vec![None; left_len];
+    let hashes = hash_rows(left)?;
+    for (row, hash) in hashes.into_iter().enumerate().rev() {
+        if !is_null(&left_nulls, row) {
+            next[row] = map.insert(hash, row);
+        }
+    }
+
+    // probe
+    let mut left_rows = vec![];
+    let mut right_rows = vec![];
+    let mut matched = MutableBitmap::from_len_zeroed(left_len);
+    let hashes = hash_rows(right)?;
+    for (row, hash) in hashes.into_iter().enumerate() {
+        let mut has_match = false;
+        if !is_null(&right_nulls, row) {
+            let mut candidate = map.get(&hash).copied();
+            while let Some(left_row) = candidate {
+                if eq(left_row, row) {
+                    has_match = true;
+                    matched.set(left_row, true);
+                    if !matches!(join_type, JoinType::Semi | JoinType::Anti) {
+                        left_rows.push(Some(left_row));
+                        right_rows.push(Some(row));
+                    }
+                }
+                candidate = next[left_row];
+            }
+        }
+        if !has_match && matches!(join_type, JoinType::Right | JoinType::Full) {
+            left_rows.push(None);
+            right_rows.push(Some(row));
+        }
+    }
+
+    let matched: Bitmap = matched.into();
+    match join_type {
+        JoinType::Left | JoinType::Full | JoinType::Anti => matched
+            .iter()
+            .enumerate()
+            .filter(|(_, is_matched)| !*is_matched)
+            .for_each(|(row, _)| left_rows.push(Some(row))),
+        JoinType::Semi => matched
+            .iter()
+            .enumerate()
+            .filter(|(_, is_matched)| *is_matched)
+            .for_each(|(row, _)| left_rows.push(Some(row))),
+        JoinType::Inner | JoinType::Right => {}
+    }
+    right_rows.resize(left_rows.len(), None);
+
+    Ok((to_indices(&left_rows)?, to_indices(&right_rows)?))
+}
+
+fn key_columns<'a>(batch: &'a RecordBatch, names: &[&str]) -> Result<Vec<&'a dyn Array>> {
+    names
+        .iter()
+        .map(|name| {
+            let index = batch.schema().index_of(name)?;
+            Ok(batch.column(index).as_ref())
+        })
+        .collect()
+}
+
+fn take_batch(
+    batch: &RecordBatch,
+    indices: &PrimitiveArray<u64>,
+    is_nullable: bool,
+    fields: &mut Vec<Field>,
+    columns: &mut Vec<Arc<dyn Array>>,
+) -> Result<()> {
+    for (field, column) in batch.schema().fields().iter().zip(batch.columns()) {
+        let mut field = field.clone();
+        field.nullable |= is_nullable;
+        fields.push(field);
+        columns.push(take(column.as_ref(), indices)?.into());
+    }
+    Ok(())
+}
+
+/// Joins `left` and `right` on the columns named `left_on` and `right_on` via [`hash_join`].
+///
+/// The result has the columns of `left` followed by the columns of `right`, except for semi
+/// and anti joins, whose result only has the columns of `left`. Fields become nullable when
+/// their side may have rows without a match.
+/// # Errors
+/// This function errors iff a column does not exist or [`hash_join`] errors.
+pub fn join(
+    left: &RecordBatch,
+    right: &RecordBatch,
+    left_on: &[&str],
+    right_on: &[&str],
+    join_type: JoinType,
+    null_equals_null: bool,
+) -> Result<RecordBatch> {
+    let (left_indices, right_indices) = hash_join::<u64>(
+        &key_columns(left, left_on)?,
+        &key_columns(right, right_on)?,
+        join_type,
+        null_equals_null,
+    )?;
+
+    let mut fields = vec![];
+    let mut columns = vec![];
+    let left_nullable = matches!(join_type, JoinType::Right | JoinType::Full);
+    take_batch(
+        left,
+        &left_indices,
+        left_nullable,
+        &mut fields,
+        &mut columns,
+    )?;
+    if !matches!(join_type, JoinType::Semi | JoinType::Anti) {
+        let right_nullable = matches!(join_type, JoinType::Left | JoinType::Full);
+        take_batch(
+            right,
+            &right_indices,
+            right_nullable,
+            &mut fields,
+            &mut columns,
+        )?;
+    }
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+}
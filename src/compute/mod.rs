@@ -23,8 +23,10 @@ pub mod comparison;
 pub mod concat;
 pub mod contains;
 pub mod filter;
+pub mod group_by;
 pub mod hash;
 pub mod if_then_else;
+pub mod join;
 pub mod length;
 pub mod limit;
 pub mod nullif;
@@ -33,6 +35,7 @@ pub mod sort;
 pub mod substring;
 pub mod take;
 pub mod temporal;
+pub mod unique;
 mod utils;
 pub mod window;
 
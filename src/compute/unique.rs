@@ -0,0 +1,181 @@
+//! Contains operators to compute the distinct values of an array ([`unique`]), their
+//! frequencies ([`value_counts`]) and whether they are repeated ([`is_duplicated`]), as well
+//! as their multi-column counterparts over the rows of a [`RecordBatch`].
+//!
+//! Nulls are a value like any other, i.e. all nulls are equal to each other.
+//! See [`groups`] for the supported types.
+//!
+//! ```
+//! use arrow2::array::{Array, Utf8Array};
+//! use arrow2::compute::unique::{count_distinct, unique};
+//! # use arrow2::error::Result;
+//! # fn main() -> Result<()> {
+//! let array = Utf8Array::<i32>::from(&[Some("a"), None, Some("b"), Some("a"), None]);
+//!
+//! let result = unique(&array)?;
+//! assert_eq!(
+//!     result.as_ref(),
+//!     &Utf8Array::<i32>::from(&[Some("a"), None, Some("b")]) as &dyn Array
+//! );
+//! assert_eq!(count_distinct(&array)?, 3);
+//! # Ok(())
+//! # }
+//! ```
+use std::sync::Arc;
+
+use crate::array::{Array, BooleanArray, PrimitiveArray, StructArray};
+use crate::bitmap::Bitmap;
+use crate::datatypes::{DataType, Field, Schema};
+use crate::error::Result;
+use crate::record_batch::RecordBatch;
+
+use super::group_by::{groups, Groups};
+use super::take::take;
+
+fn take_first(array: &dyn Array, groups: &Groups) -> Result<Box<dyn Array>> {
+    let indices = PrimitiveArray::<u64>::from_trusted_len_values_iter(
+        groups.first.iter().map(|row| *row as u64),
+    );
+    take(array, &indices)
+}
+
+/// The number of rows of each group
+fn counts(groups: &Groups) -> Vec<u64> {
+    let mut counts = vec![0u64; groups.len()];
+    groups.ids.iter().for_each(|id| counts[*id] += 1);
+    counts
+}
+
+fn is_unique_groups(groups: &Groups) -> Bitmap {
+    let counts = counts(groups);
+    Bitmap::from_trusted_len_iter(groups.ids.iter().map(|id| counts[*id] == 1))
+}
+
+fn batch_columns(batch: &RecordBatch) -> Vec<&dyn Array> {
+    batch.columns().iter().map(|x| x.as_ref()).collect()
+}
+
+/// Returns the distinct values of `array`, in the order they first appear.
+/// # Errors
+/// This function errors iff the type of `array` is not supported.
+pub fn unique(array: &dyn Array) -> Result<Box<dyn Array>> {
+    let groups = groups(&[array])?;
+    take_first(array, &groups)
+}
+
+/// Returns the number of distinct values of `array`, including null.
+/// # Errors
+/// This function errors iff the type of `array` is not supported.
+pub fn count_distinct(array: &dyn Array) -> Result<usize> {
+    Ok(groups(&[array])?.len())
+}
+
+/// Returns a [`StructArray`] with the distinct values of `array` (field `"values"`), in the
+/// order they first appear, and the number of times each appears (field `"counts"`).
+/// # Errors
+/// This function errors iff the type of `array` is not supported.
+pub fn value_counts(array: &dyn Array) -> Result<StructArray> {
+    let groups = groups(&[array])?;
+    let values: Arc<dyn Array> = take_first(array, &groups)?.into();
+    let counts = PrimitiveArray::<u64>::from_data(DataType::UInt64, counts(&groups).into(), None);
+
+    let fields = vec![
+        Field::new("values", array.data_type().clone(), true),
+        Field::new("counts", DataType::UInt64, false),
+    ];
+    Ok(StructArray::from_data(
+        DataType::Struct(fields),
+        vec![values, Arc::new(counts)],
+        None,
+    ))
+}
+
+/// Returns whether each value of `array` appears only once in `array`.
+/// # Errors
+/// This function errors iff the type of `array` is not supported.
+pub fn is_unique(array: &dyn Array) -> Result<BooleanArray> {
+    let groups = groups(&[array])?;
+    Ok(BooleanArray::from_data(
+        DataType::Boolean,
+        is_unique_groups(&groups),
+        None,
+    ))
+}
+
+/// Returns whether each value of `array` appears more than once in `array`.
+/// # Errors
+/// This function errors iff the type of `array` is not supported.
+pub fn is_duplicated(array: &dyn Array) -> Result<BooleanArray> {
+    let groups = groups(&[array])?;
+    Ok(BooleanArray::from_data(
+        DataType::Boolean,
+        !&is_unique_groups(&groups),
+        None,
+    ))
+}
+
+fn unique_columns(columns: &[&dyn Array], groups: &Groups) -> Result<Vec<Arc<dyn Array>>> {
+    columns
+        .iter()
+        .map(|column| take_first(*column, groups).map(|x| x.into()))
+        .collect()
+}
+
+/// Returns the distinct rows of `batch`, in the order they first appear.
+/// # Errors
+/// This function errors iff `batch` has no columns or the type of a column is not supported.
+pub fn unique_batch(batch: &RecordBatch) -> Result<RecordBatch> {
+    let columns = batch_columns(batch);
+    let groups = groups(&columns)?;
+    RecordBatch::try_new(batch.schema().clone(), unique_columns(&columns, &groups)?)
+}
+
+/// Returns the number of distinct rows of `batch`.
+/// # Errors
+/// This function errors iff `batch` has no columns or the type of a column is not supported.
+pub fn count_distinct_batch(batch: &RecordBatch) -> Result<usize> {
+    Ok(groups(&batch_columns(batch))?.len())
+}
+
+/// Returns the distinct rows of `batch`, in the order they first appear, with an additional
+/// column `"counts"` with the number of times each row appears.
+/// # Errors
+/// This function errors iff `batch` has no columns or the type of a column is not supported.
+pub fn value_counts_batch(batch: &RecordBatch) -> Result<RecordBatch> {
+    let columns = batch_columns(batch);
+    let groups = groups(&columns)?;
+    let mut columns = unique_columns(&columns, &groups)?;
+    let counts = PrimitiveArray::<u64>::from_data(DataType::UInt64, counts(&groups).into(), None);
+    columns.push(Arc::new(counts));
+
+    let mut fields = batch.schema().fields().clone();
+    fields.push(Field::new("counts", DataType::UInt64, false));
+    RecordBatch::try_new(
+        Arc::new(Schema::new_from(fields, batch.schema().metadata().clone())),
+        columns,
+    )
+}
+
+/// Returns whether each row of `batch` appears only once in `batch`.
+/// # Errors
+/// This function errors iff `batch` has no columns or the type of a column is not supported.
+pub fn is_unique_batch(batch: &RecordBatch) -> Result<BooleanArray> {
+    let groups = groups(&batch_columns(batch))?;
+    Ok(BooleanArray::from_data(
+        DataType::Boolean,
+        is_unique_groups(&groups),
+        None,
+    ))
+}
+
+/// Returns whether each row of `batch` appears more than once in `batch`.
+/// # Errors
+/// This function errors iff `batch` has no columns or the type of a column is not supported.
+pub fn is_duplicated_batch(batch: &RecordBatch) -> Result<BooleanArray> {
+    let groups = groups(&batch_columns(batch))?;
+    Ok(BooleanArray::from_data(
+        DataType::Boolean,
+        !&is_unique_groups(&groups),
+        None,
+    ))
+}
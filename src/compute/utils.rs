@@ -15,12 +15,22 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::cmp::Ordering;
+
+use num_traits::ToPrimitive;
+
 use crate::{
-    array::{BooleanArray, Offset, Utf8Array},
+    array::{
+        ord::build_compare, Array, BooleanArray, DictionaryArray, Offset, PrimitiveArray, Utf8Array,
+    },
     bitmap::Bitmap,
-    datatypes::DataType,
+    datatypes::{DataType, PhysicalType, PrimitiveType},
+    error::Result,
 };
 
+use super::arity::unary;
+use super::hash::hash;
+
 pub fn combine_validities(lhs: Option<&Bitmap>, rhs: Option<&Bitmap>) -> Option<Bitmap> {
     match (lhs, rhs) {
         (Some(lhs), None) => Some(lhs.clone()),
@@ -45,3 +55,99 @@ pub fn unary_utf8_boolean<O: Offset, F: Fn(&str) -> bool>(
     let values = Bitmap::from_trusted_len_iter(iterator);
     BooleanArray::from_data(DataType::Boolean, values, validity)
 }
+
+/// The hash of a null slot when hashing rows.
+const NULL_HASH: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// Returns the hash of each slot of `array`, where all null slots have the same hash.
+fn hash_column(array: &dyn Array) -> Result<Vec<u64>> {
+    if let PhysicalType::Dictionary(key_type) = array.data_type().to_physical_type() {
+        return match_integer_type!(key_type, |$T| {
+            let array = array
+                .as_any()
+                .downcast_ref::<DictionaryArray<$T>>()
+                .unwrap();
+            // a dictionary's values are hashed once and referenced by its keys
+            let values = hash_column(array.values().as_ref())?;
+            Ok(array
+                .keys()
+                .iter()
+                .map(|key| key.map(|key| values[key.to_usize().unwrap()]).unwrap_or(NULL_HASH))
+                .collect())
+        });
+    }
+    let hashes = match array.data_type().to_physical_type() {
+        // floats are hashed by their bit pattern, consistent with their total order
+        PhysicalType::Primitive(PrimitiveType::Float32) => {
+            let array = array
+                .as_any()
+                .downcast_ref::<PrimitiveArray<f32>>()
+                .unwrap();
+            hash(&unary(array, |x| x.to_bits(), DataType::UInt32))?
+        }
+        PhysicalType::Primitive(PrimitiveType::Float64) => {
+            let array = array
+                .as_any()
+                .downcast_ref::<PrimitiveArray<f64>>()
+                .unwrap();
+            hash(&unary(array, |x| x.to_bits(), DataType::UInt64))?
+        }
+        _ => hash(array)?,
+    };
+    Ok(hashes
+        .iter()
+        .map(|x| x.copied().unwrap_or(NULL_HASH))
+        .collect())
+}
+
+/// Returns the hash of each row of `columns`, i.e. the combined hash of the slots of all
+/// columns at the same index.
+/// # Errors
+/// This function errors iff a column can't be hashed.
+pub fn hash_rows(columns: &[&dyn Array]) -> Result<Vec<u64>> {
+    let mut columns = columns.iter();
+    let mut hashes = columns
+        .next()
+        .map(|column| hash_column(*column))
+        .transpose()?
+        .unwrap_or_default();
+    for column in columns {
+        hash_column(*column)?
+            .into_iter()
+            .zip(hashes.iter_mut())
+            .for_each(|(x, hash)| {
+                *hash = (hash.rotate_left(5) ^ x).wrapping_mul(0x517C_C1B7_2722_0A95)
+            });
+    }
+    Ok(hashes)
+}
+
+/// Returns whether the row `i` of a set of columns equals the row `j` of another set.
+pub type RowEq = Box<dyn Fn(usize, usize) -> bool>;
+
+/// Returns a [`RowEq`] between the rows of `left` and the rows of `right`. Two null slots
+/// are equal.
+/// # Errors
+/// This function errors iff the columns of `left` and `right` have different types or
+/// their type can't be compared.
+pub fn build_row_eq(left: &[&dyn Array], right: &[&dyn Array]) -> Result<RowEq> {
+    let columns = left
+        .iter()
+        .zip(right.iter())
+        .map(|(left, right)| {
+            let cmp = build_compare(*left, *right)?;
+            let left = left.validity().cloned();
+            let right = right.validity().cloned();
+            Ok(Box::new(move |i, j| {
+                let left = left.as_ref().map(|x| x.get_bit(i)).unwrap_or(true);
+                let right = right.as_ref().map(|x| x.get_bit(j)).unwrap_or(true);
+                match (left, right) {
+                    (true, true) => cmp(i, j) == Ordering::Equal,
+                    (false, false) => true,
+                    _ => false,
+                }
+            }) as RowEq)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Box::new(move |i, j| columns.iter().all(|eq| eq(i, j))))
+}
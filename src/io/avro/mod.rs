@@ -2,9 +2,29 @@
 //! Read and write from and to Apache Avro
 
 pub mod read;
+#[cfg(feature = "io_avro_async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "io_avro_async")))]
+pub mod read_async;
+pub mod write;
 
 use crate::error::ArrowError;
 
+/// The compression codecs of Avro's blocks, as declared in the file's header.
+/// Except for [`Compression::Deflate`], they require the feature `io_avro_compression`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Compression {
+    /// Deflate (RFC 1951)
+    Deflate,
+    /// Snappy, followed by the CRC32 checksum of the uncompressed block
+    Snappy,
+    /// Zstandard
+    Zstandard,
+    /// Bzip2
+    Bzip2,
+    /// Xz (LZMA2)
+    Xz,
+}
+
 impl From<avro_rs::SerError> for ArrowError {
     fn from(error: avro_rs::SerError) -> Self {
         ArrowError::External("".to_string(), Box::new(error))
@@ -11,14 +11,39 @@ use crate::record_batch::RecordBatch;
 use crate::types::months_days_ns;
 
 use super::nested::*;
+use super::resolve::Resolution;
 use super::util;
 
+/// Returns the index of the `"null"` variant and the other variant of a nullable union,
+/// or `None` if `schema` is not a nullable union.
+fn nullable_variants(schema: &AvroSchema) -> Option<(i64, &AvroSchema)> {
+    if let AvroSchema::Union(union) = schema {
+        if let [first, second] = union.variants() {
+            return match (first, second) {
+                (AvroSchema::Null, other) => Some((0, other)),
+                (other, AvroSchema::Null) => Some((1, other)),
+                _ => None,
+            };
+        }
+    }
+    None
+}
+
+/// Returns the non-null variant of `schema` when it is a nullable union, or itself otherwise.
+fn unwrap_nullable(schema: &AvroSchema) -> &AvroSchema {
+    nullable_variants(schema)
+        .map(|(_, schema)| schema)
+        .unwrap_or(schema)
+}
+
 fn make_mutable(
     data_type: &DataType,
-    avro_schema: Option<&AvroSchema>,
+    avro_schema: &AvroSchema,
     capacity: usize,
 ) -> Result<Box<dyn MutableArray>> {
+    let avro_schema = unwrap_nullable(avro_schema);
     Ok(match data_type.to_physical_type() {
+        PhysicalType::Null => Box::new(MutableNullArray::new(data_type.clone())),
         PhysicalType::Boolean => {
             Box::new(MutableBooleanArray::with_capacity(capacity)) as Box<dyn MutableArray>
         }
@@ -33,7 +58,7 @@ fn make_mutable(
             Box::new(MutableUtf8Array::<i32>::with_capacity(capacity)) as Box<dyn MutableArray>
         }
         PhysicalType::Dictionary(_) => {
-            if let Some(AvroSchema::Enum { symbols, .. }) = avro_schema {
+            if let AvroSchema::Enum { symbols, .. } = avro_schema {
                 let values = Utf8Array::<i32>::from_slice(symbols);
                 Box::new(FixedItemsUtf8Dictionary::with_capacity(values, capacity))
                     as Box<dyn MutableArray>
@@ -43,15 +68,73 @@ fn make_mutable(
         }
         _ => match data_type {
             DataType::List(inner) => {
-                let values = make_mutable(inner.data_type(), None, 0)?;
-                Box::new(DynMutableListArray::<i32>::new_with_capacity(
-                    values, capacity,
+                let items = if let AvroSchema::Array(items) = avro_schema {
+                    items.as_ref()
+                } else {
+                    unreachable!()
+                };
+                let values = make_mutable(inner.data_type(), items, 0)?;
+                Box::new(DynMutableListArray::<i32>::new_from(
+                    values,
+                    data_type.clone(),
+                    capacity,
                 )) as Box<dyn MutableArray>
             }
             DataType::FixedSizeBinary(size) => Box::new(MutableFixedSizeBinaryArray::with_capacity(
                 *size as usize,
                 capacity,
             )) as Box<dyn MutableArray>,
+            DataType::Struct(fields) => {
+                let avro_fields = if let AvroSchema::Record { fields, .. } = avro_schema {
+                    fields
+                } else {
+                    unreachable!()
+                };
+                let values = fields
+                    .iter()
+                    .zip(avro_fields.iter())
+                    .map(|(field, avro_field)| {
+                        make_mutable(field.data_type(), &avro_field.schema, capacity)
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Box::new(DynMutableStructArray::new(values, data_type.clone()))
+                    as Box<dyn MutableArray>
+            }
+            DataType::Map(field, _) => {
+                let values_schema = if let AvroSchema::Map(values) = avro_schema {
+                    values.as_ref()
+                } else {
+                    unreachable!()
+                };
+                let fields = StructArray::get_fields(field.data_type());
+                let values = vec![
+                    Box::new(MutableUtf8Array::<i32>::new()) as Box<dyn MutableArray>,
+                    make_mutable(fields[1].data_type(), values_schema, 0)?,
+                ];
+                let entries = DynMutableStructArray::new(values, field.data_type().clone());
+                Box::new(DynMutableMapArray::new(
+                    entries,
+                    data_type.clone(),
+                    capacity,
+                )) as Box<dyn MutableArray>
+            }
+            DataType::Union(fields, _, UnionMode::Dense) => {
+                let variants = if let AvroSchema::Union(union) = avro_schema {
+                    union.variants()
+                } else {
+                    unreachable!()
+                };
+                let fields = fields
+                    .iter()
+                    .zip(variants.iter())
+                    .map(|(field, variant)| make_mutable(field.data_type(), variant, 0))
+                    .collect::<Result<Vec<_>>>()?;
+                Box::new(DynMutableUnionArray::new(
+                    fields,
+                    data_type.clone(),
+                    capacity,
+                )) as Box<dyn MutableArray>
+            }
             other => {
                 return Err(ArrowError::NotYetImplemented(format!(
                     "Deserializing type {:?} is still not implemented",
@@ -62,47 +145,171 @@ fn make_mutable(
     })
 }
 
+#[inline]
+fn read_len(block: &mut &[u8]) -> Result<usize> {
+    util::zigzag_i64(block)?.try_into().map_err(|_| {
+        ArrowError::ExternalFormat("Avro format contains a non-usize number of bytes".to_string())
+    })
+}
+
+/// Splits the first `len` bytes from `block`.
+/// # Errors
+/// Errors iff `block` has less than `len` bytes.
+#[inline]
+fn read_bytes<'a>(block: &mut &'a [u8], len: usize) -> Result<&'a [u8]> {
+    if block.len() < len {
+        return Err(ArrowError::ExternalFormat(format!(
+            "Avro block ended unexpectedly: {} bytes were expected but only {} remain",
+            len,
+            block.len()
+        )));
+    }
+    let (data, remaining) = block.split_at(len);
+    *block = remaining;
+    Ok(data)
+}
+
+/// Reads the number of items of the next block of an Avro array or map,
+/// skipping the block's size in bytes when it is present.
+#[inline]
+fn read_block_len(block: &mut &[u8]) -> Result<usize> {
+    let len = util::zigzag_i64(block)?;
+    if len < 0 {
+        // https://avro.apache.org/docs/current/spec.html#binary_encode_complex
+        // a negative count is followed by the size of the block in bytes, which we do not need
+        let _ = util::zigzag_i64(block)?;
+    }
+    Ok(len.abs() as usize)
+}
+
+/// Reads Avro's decimal, a big-endian two's-complement integer, into an `i128`.
+fn read_decimal<'a>(mut block: &'a [u8], avro_schema: &AvroSchema) -> Result<(i128, &'a [u8])> {
+    let len = match avro_schema {
+        AvroSchema::Decimal { inner, .. } => match inner.as_ref() {
+            AvroSchema::Fixed { size, .. } => *size,
+            _ => read_len(&mut block)?,
+        },
+        _ => unreachable!(),
+    };
+    if len > 16 {
+        return Err(ArrowError::ExternalFormat(
+            "Avro decimals with more than 16 bytes are not supported".to_string(),
+        ));
+    }
+    let data = read_bytes(&mut block, len)?;
+
+    // sign-extend the value to 16 bytes
+    let is_negative = data.first().map(|x| x & 0x80 != 0).unwrap_or(false);
+    let mut bytes = if is_negative { [0xFF; 16] } else { [0; 16] };
+    bytes[16 - len..].copy_from_slice(data);
+    Ok((i128::from_be_bytes(bytes), block))
+}
+
 #[inline]
 fn deserialize_item<'a>(
     array: &mut dyn MutableArray,
-    is_nullable: bool,
+    avro_schema: &AvroSchema,
     mut block: &'a [u8],
 ) -> Result<&'a [u8]> {
-    if is_nullable {
-        // variant 0 is always the null in a union array
-        if util::zigzag_i64(&mut block)? == 0 {
+    // nullable items are encoded as a union with "null"
+    let avro_schema = if let Some((null_variant, avro_schema)) = nullable_variants(avro_schema) {
+        if util::zigzag_i64(&mut block)? == null_variant {
             array.push_null();
             return Ok(block);
         }
-    }
+        avro_schema
+    } else {
+        avro_schema
+    };
 
     let data_type = array.data_type();
     match data_type {
-        DataType::List(inner) => {
-            let is_nullable = inner.is_nullable();
+        DataType::List(_) => {
+            let items = if let AvroSchema::Array(items) = avro_schema {
+                items.as_ref()
+            } else {
+                unreachable!()
+            };
             let array = array
                 .as_mut_any()
                 .downcast_mut::<DynMutableListArray<i32>>()
                 .unwrap();
             loop {
-                let len = util::zigzag_i64(&mut block)? as usize;
-
+                let len = read_block_len(&mut block)?;
                 if len == 0 {
                     break;
                 }
 
                 let values = array.mut_values();
                 for _ in 0..len {
-                    block = deserialize_item(values, is_nullable, block)?;
+                    block = deserialize_item(values, items, block)?;
+                }
+            }
+            array.try_push_valid()?;
+        }
+        DataType::Map(_, _) => {
+            let values_schema = if let AvroSchema::Map(values) = avro_schema {
+                values.as_ref()
+            } else {
+                unreachable!()
+            };
+            let array = array
+                .as_mut_any()
+                .downcast_mut::<DynMutableMapArray>()
+                .unwrap();
+            loop {
+                let len = read_block_len(&mut block)?;
+                if len == 0 {
+                    break;
+                }
+
+                let entries = array.mut_values();
+                for _ in 0..len {
+                    let fields = entries.mut_values();
+                    block = deserialize_item(fields[0].as_mut(), &AvroSchema::String, block)?;
+                    block = deserialize_item(fields[1].as_mut(), values_schema, block)?;
+                    entries.try_push_valid()?;
                 }
-                array.try_push_valid()?;
             }
+            array.try_push_valid()?;
+        }
+        DataType::Struct(_) => {
+            let avro_fields = if let AvroSchema::Record { fields, .. } = avro_schema {
+                fields
+            } else {
+                unreachable!()
+            };
+            let array = array
+                .as_mut_any()
+                .downcast_mut::<DynMutableStructArray>()
+                .unwrap();
+            let values = array.mut_values();
+            for (values, avro_field) in values.iter_mut().zip(avro_fields.iter()) {
+                block = deserialize_item(values.as_mut(), &avro_field.schema, block)?;
+            }
+            array.try_push_valid()?;
+        }
+        DataType::Union(_, _, _) => {
+            let variants = if let AvroSchema::Union(union) = avro_schema {
+                union.variants()
+            } else {
+                unreachable!()
+            };
+            let variant = read_len(&mut block)?;
+            let variant_schema = variants.get(variant).ok_or_else(|| {
+                ArrowError::ExternalFormat(format!("The union has no variant {}", variant))
+            })?;
+            let array = array
+                .as_mut_any()
+                .downcast_mut::<DynMutableUnionArray>()
+                .unwrap();
+            let values = array.try_push_type(variant)?;
+            block = deserialize_item(values, variant_schema, block)?;
         }
         DataType::Interval(IntervalUnit::MonthDayNano) => {
             // https://avro.apache.org/docs/current/spec.html#Duration
             // 12 bytes, months, days, millis in LE
-            let data = &block[..12];
-            block = &block[12..];
+            let data = read_bytes(&mut block, 12)?;
 
             let value = months_days_ns::new(
                 i32::from_le_bytes([data[0], data[1], data[2], data[3]]),
@@ -116,10 +323,20 @@ fn deserialize_item<'a>(
                 .unwrap();
             array.push(Some(value))
         }
+        DataType::Decimal(_, _) => {
+            let (value, remaining) = read_decimal(block, avro_schema)?;
+            block = remaining;
+
+            let array = array
+                .as_mut_any()
+                .downcast_mut::<MutablePrimitiveArray<i128>>()
+                .unwrap();
+            array.push(Some(value))
+        }
         _ => match data_type.to_physical_type() {
+            PhysicalType::Null => array.push_null(),
             PhysicalType::Boolean => {
-                let is_valid = block[0] == 1;
-                block = &block[1..];
+                let is_valid = read_bytes(&mut block, 1)?[0] == 1;
                 let array = array
                     .as_mut_any()
                     .downcast_mut::<MutableBooleanArray>()
@@ -144,9 +361,15 @@ fn deserialize_item<'a>(
                     array.push(Some(value))
                 }
                 PrimitiveType::Float32 => {
-                    let value =
-                        f32::from_le_bytes(block[..std::mem::size_of::<f32>()].try_into().unwrap());
-                    block = &block[std::mem::size_of::<f32>()..];
+                    let value = match avro_schema {
+                        // promoted from the writer's `int` or `long`
+                        AvroSchema::Int | AvroSchema::Long => util::zigzag_i64(&mut block)? as f32,
+                        _ => f32::from_le_bytes(
+                            read_bytes(&mut block, std::mem::size_of::<f32>())?
+                                .try_into()
+                                .unwrap(),
+                        ),
+                    };
                     let array = array
                         .as_mut_any()
                         .downcast_mut::<MutablePrimitiveArray<f32>>()
@@ -154,9 +377,20 @@ fn deserialize_item<'a>(
                     array.push(Some(value))
                 }
                 PrimitiveType::Float64 => {
-                    let value =
-                        f64::from_le_bytes(block[..std::mem::size_of::<f64>()].try_into().unwrap());
-                    block = &block[std::mem::size_of::<f64>()..];
+                    let value = match avro_schema {
+                        // promoted from the writer's `int`, `long` or `float`
+                        AvroSchema::Int | AvroSchema::Long => util::zigzag_i64(&mut block)? as f64,
+                        AvroSchema::Float => f32::from_le_bytes(
+                            read_bytes(&mut block, std::mem::size_of::<f32>())?
+                                .try_into()
+                                .unwrap(),
+                        ) as f64,
+                        _ => f64::from_le_bytes(
+                            read_bytes(&mut block, std::mem::size_of::<f64>())?
+                                .try_into()
+                                .unwrap(),
+                        ),
+                    };
                     let array = array
                         .as_mut_any()
                         .downcast_mut::<MutablePrimitiveArray<f64>>()
@@ -166,13 +400,8 @@ fn deserialize_item<'a>(
                 _ => unreachable!(),
             },
             PhysicalType::Utf8 => {
-                let len: usize = util::zigzag_i64(&mut block)?.try_into().map_err(|_| {
-                    ArrowError::ExternalFormat(
-                        "Avro format contains a non-usize number of bytes".to_string(),
-                    )
-                })?;
-                let data = simdutf8::basic::from_utf8(&block[..len])?;
-                block = &block[len..];
+                let len = read_len(&mut block)?;
+                let data = simdutf8::basic::from_utf8(read_bytes(&mut block, len)?)?;
 
                 let array = array
                     .as_mut_any()
@@ -181,13 +410,8 @@ fn deserialize_item<'a>(
                 array.push(Some(data))
             }
             PhysicalType::Binary => {
-                let len: usize = util::zigzag_i64(&mut block)?.try_into().map_err(|_| {
-                    ArrowError::ExternalFormat(
-                        "Avro format contains a non-usize number of bytes".to_string(),
-                    )
-                })?;
-                let data = &block[..len];
-                block = &block[len..];
+                let len = read_len(&mut block)?;
+                let data = read_bytes(&mut block, len)?;
 
                 let array = array
                     .as_mut_any()
@@ -200,9 +424,7 @@ fn deserialize_item<'a>(
                     .as_mut_any()
                     .downcast_mut::<MutableFixedSizeBinaryArray>()
                     .unwrap();
-                let len = array.size();
-                let data = &block[..len];
-                block = &block[len..];
+                let data = read_bytes(&mut block, array.size())?;
                 array.push(Some(data));
             }
             PhysicalType::Dictionary(_) => {
@@ -213,33 +435,194 @@ fn deserialize_item<'a>(
                     .unwrap();
                 array.push_valid(index);
             }
-            _ => todo!(),
+            other => {
+                return Err(ArrowError::NotYetImplemented(format!(
+                    "Deserializing physical type {:?} is still not implemented",
+                    other
+                )))
+            }
         },
     };
     Ok(block)
 }
 
+/// Skips an item of `avro_schema` from `block`, returning the remaining of the block.
+fn skip_item<'a>(avro_schema: &AvroSchema, mut block: &'a [u8]) -> Result<&'a [u8]> {
+    match avro_schema {
+        AvroSchema::Null => {}
+        AvroSchema::Boolean => {
+            read_bytes(&mut block, 1)?;
+        }
+        AvroSchema::Int
+        | AvroSchema::Long
+        | AvroSchema::Enum { .. }
+        | AvroSchema::Date
+        | AvroSchema::TimeMillis
+        | AvroSchema::TimeMicros
+        | AvroSchema::TimestampMillis
+        | AvroSchema::TimestampMicros => {
+            let _ = util::zigzag_i64(&mut block)?;
+        }
+        AvroSchema::Float => {
+            read_bytes(&mut block, std::mem::size_of::<f32>())?;
+        }
+        AvroSchema::Double => {
+            read_bytes(&mut block, std::mem::size_of::<f64>())?;
+        }
+        AvroSchema::Bytes | AvroSchema::String | AvroSchema::Uuid => {
+            let len = read_len(&mut block)?;
+            read_bytes(&mut block, len)?;
+        }
+        AvroSchema::Fixed { size, .. } => {
+            read_bytes(&mut block, *size)?;
+        }
+        AvroSchema::Duration => {
+            read_bytes(&mut block, 12)?;
+        }
+        AvroSchema::Decimal { inner, .. } => block = skip_item(inner, block)?,
+        AvroSchema::Union(union) => {
+            let variant = read_len(&mut block)?;
+            let variant = union.variants().get(variant).ok_or_else(|| {
+                ArrowError::ExternalFormat(format!("The union has no variant {}", variant))
+            })?;
+            block = skip_item(variant, block)?
+        }
+        AvroSchema::Array(_) | AvroSchema::Map(_) => loop {
+            let len = util::zigzag_i64(&mut block)?;
+            if len == 0 {
+                break;
+            }
+            if len < 0 {
+                // the block's size in bytes is available: skip it in one go
+                let bytes = read_len(&mut block)?;
+                read_bytes(&mut block, bytes)?;
+                continue;
+            }
+            for _ in 0..len {
+                block = match avro_schema {
+                    AvroSchema::Array(items) => skip_item(items, block)?,
+                    AvroSchema::Map(values) => {
+                        let block = skip_item(&AvroSchema::String, block)?;
+                        skip_item(values, block)?
+                    }
+                    _ => unreachable!(),
+                }
+            }
+        },
+        AvroSchema::Record { fields, .. } => {
+            for field in fields {
+                block = skip_item(&field.schema, block)?
+            }
+        }
+    }
+    Ok(block)
+}
+
+/// Deserializes a (decompressed) block of `rows` Avro records into a [`RecordBatch`].
+///
+/// `avro_schemas` are the schemas of all fields of the records and `projection` declares
+/// which of them are deserialized (in order) into the fields of `schema`. The encoding of
+/// the remaining fields is skipped without deserializing it.
+/// # Panics
+/// Panics iff `projection`'s length differs from `avro_schemas`'s, or the number of
+/// projected fields differs from the number of fields in `schema`.
 pub fn deserialize(
     mut block: &[u8],
     rows: usize,
     schema: Arc<Schema>,
     avro_schemas: &[AvroSchema],
+    projection: &[bool],
 ) -> Result<RecordBatch> {
-    // create mutables, one per field
+    assert_eq!(projection.len(), avro_schemas.len());
+    assert_eq!(
+        projection.iter().filter(|x| **x).count(),
+        schema.fields().len()
+    );
+
+    // create mutables, one per projected field
     let mut arrays: Vec<Box<dyn MutableArray>> = schema
         .fields()
         .iter()
-        .zip(avro_schemas.iter())
+        .zip(
+            avro_schemas
+                .iter()
+                .zip(projection.iter())
+                .filter_map(|(avro_schema, is_projected)| is_projected.then(|| avro_schema)),
+        )
         .map(|(field, avro_schema)| {
             let data_type = field.data_type().to_logical_type();
-            make_mutable(data_type, Some(avro_schema), rows)
+            make_mutable(data_type, avro_schema, rows)
         })
         .collect::<Result<_>>()?;
 
     // this is _the_ expensive transpose (rows -> columns)
     for _ in 0..rows {
-        for (array, field) in arrays.iter_mut().zip(schema.fields().iter()) {
-            block = deserialize_item(array.as_mut(), field.is_nullable(), block)?
+        let mut arrays = arrays.iter_mut();
+        for (avro_schema, is_projected) in avro_schemas.iter().zip(projection.iter()) {
+            if *is_projected {
+                let array = arrays.next().unwrap();
+                block = deserialize_item(array.as_mut(), avro_schema, block)?
+            } else {
+                block = skip_item(avro_schema, block)?
+            }
+        }
+    }
+    let columns = arrays.iter_mut().map(|array| array.as_arc()).collect();
+
+    RecordBatch::try_new(schema, columns)
+}
+
+/// Deserializes a (decompressed) block of `rows` Avro records written with the fields'
+/// schemas `avro_schemas` into a [`RecordBatch`] in the shape of the reader schema of
+/// `resolution`.
+///
+/// Fields of the writer that are not part of the reader schema are skipped and fields of the
+/// reader schema that are not part of the writer are filled with their default value.
+/// # Panics
+/// Panics iff `avro_schemas` is not the writer schema `resolution` was created from.
+pub fn deserialize_resolved(
+    mut block: &[u8],
+    rows: usize,
+    avro_schemas: &[AvroSchema],
+    resolution: &Resolution,
+) -> Result<RecordBatch> {
+    assert_eq!(avro_schemas.len(), resolution.mapping.len());
+    let schema = resolution.schema.clone();
+
+    // the arrays are read with the writer's schema or, when they are filled with their
+    // default, with the reader's schema
+    let mut field_schemas = resolution.avro_schemas.iter().collect::<Vec<_>>();
+    for (avro_schema, index) in avro_schemas.iter().zip(resolution.mapping.iter()) {
+        if let Some(index) = index {
+            field_schemas[*index] = avro_schema;
+        }
+    }
+    let mut arrays = schema
+        .fields()
+        .iter()
+        .zip(field_schemas.iter())
+        .map(|(field, avro_schema)| {
+            let data_type = field.data_type().to_logical_type();
+            make_mutable(data_type, avro_schema, rows)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    for _ in 0..rows {
+        for (avro_schema, index) in avro_schemas.iter().zip(resolution.mapping.iter()) {
+            block = if let Some(index) = index {
+                deserialize_item(arrays[*index].as_mut(), avro_schema, block)?
+            } else {
+                skip_item(avro_schema, block)?
+            }
+        }
+        for ((array, default), avro_schema) in arrays
+            .iter_mut()
+            .zip(resolution.defaults.iter())
+            .zip(resolution.avro_schemas.iter())
+        {
+            if let Some(default) = default {
+                deserialize_item(array.as_mut(), avro_schema, default)?;
+            }
         }
     }
     let columns = arrays.iter_mut().map(|array| array.as_arc()).collect();
@@ -3,24 +3,44 @@
 use std::io::Read;
 use std::sync::Arc;
 
-use avro_rs::{Codec, Schema as AvroSchema};
+use avro_rs::Schema as AvroSchema;
 use fallible_streaming_iterator::FallibleStreamingIterator;
 use libflate::deflate::Decoder;
 
 mod deserialize;
 mod nested;
+mod parallel;
+mod resolve;
 mod schema;
 mod util;
 
+pub use deserialize::{deserialize, deserialize_resolved};
+pub use parallel::{ParallelReader, Task};
+pub use resolve::{resolve, Resolution};
+pub(super) use util::deserialize_header;
+
 use crate::datatypes::Schema;
 use crate::error::{ArrowError, Result};
 use crate::record_batch::RecordBatch;
 
-/// Reads the avro metadata from `reader` into a [`Schema`], [`Codec`] and magic marker.
-pub fn read_metadata<R: std::io::Read>(
-    reader: &mut R,
-) -> Result<(Vec<AvroSchema>, Schema, Codec, [u8; 16])> {
-    let (avro_schema, codec, marker) = util::read_schema(reader)?;
+use super::Compression;
+
+/// The metadata of an Avro file: the schemas of its fields, its [`Schema`], its
+/// [`Compression`] and its magic marker.
+pub type Metadata = (Vec<AvroSchema>, Schema, Option<Compression>, [u8; 16]);
+
+/// Reads the avro metadata from `reader` into a [`Schema`], [`Compression`] and magic marker.
+/// # Errors
+/// This function errors iff the header is not a valid avro file header or declares
+/// a codec that is not part of Avro's specification.
+pub fn read_metadata<R: std::io::Read>(reader: &mut R) -> Result<Metadata> {
+    let (avro_schema, compression, marker) = util::read_schema(reader)?;
+    let (avro_schema, schema) = convert_metadata(avro_schema)?;
+    Ok((avro_schema, schema, compression, marker))
+}
+
+/// Converts the record schema of a file into the schemas of its fields and a [`Schema`].
+pub(super) fn convert_metadata(avro_schema: AvroSchema) -> Result<(Vec<AvroSchema>, Schema)> {
     let schema = schema::convert_schema(&avro_schema)?;
 
     let avro_schema = if let AvroSchema::Record { fields, .. } = avro_schema {
@@ -28,8 +48,7 @@ pub fn read_metadata<R: std::io::Read>(
     } else {
         panic!()
     };
-
-    Ok((avro_schema, schema, codec, marker))
+    Ok((avro_schema, schema))
 }
 
 fn read_size<R: Read>(reader: &mut R) -> Result<(usize, usize)> {
@@ -50,8 +69,8 @@ fn read_size<R: Read>(reader: &mut R) -> Result<(usize, usize)> {
 }
 
 /// Reads a block from the file into `buf`.
-/// # Panic
-/// Panics iff the block marker does not equal to the file's marker
+/// # Errors
+/// Errors iff the block marker does not equal to the file's marker
 fn read_block<R: Read>(reader: &mut R, buf: &mut Vec<u8>, file_marker: [u8; 16]) -> Result<usize> {
     let (rows, bytes) = read_size(reader)?;
     if rows == 0 {
@@ -64,24 +83,90 @@ fn read_block<R: Read>(reader: &mut R, buf: &mut Vec<u8>, file_marker: [u8; 16])
     let mut marker = [0u8; 16];
     reader.read_exact(&mut marker)?;
 
-    assert!(!(marker != file_marker));
+    if marker != file_marker {
+        return Err(ArrowError::ExternalFormat(
+            "Avro: the sync marker in the block does not correspond to the file marker".to_string(),
+        ));
+    }
     Ok(rows)
 }
 
-/// Decompresses an avro block.
+/// Decompresses an avro block into `decompressed`.
 /// Returns whether the buffers where swapped.
-fn decompress_block(block: &mut Vec<u8>, decompress: &mut Vec<u8>, codec: Codec) -> Result<bool> {
-    match codec {
-        Codec::Null => {
-            std::mem::swap(block, decompress);
+///
+/// Blocks are independent of each other: together with [`BlockStreamIterator`] and
+/// [`deserialize`], this can be used to decompress and deserialize blocks in parallel.
+/// # Errors
+/// Errors iff the block is not valid for its compression, or the compression requires
+/// a feature that is not active.
+pub fn decompress_block(
+    block: &mut Vec<u8>,
+    decompressed: &mut Vec<u8>,
+    compression: Option<Compression>,
+) -> Result<bool> {
+    match compression {
+        None => {
+            std::mem::swap(block, decompressed);
             Ok(true)
         }
-        Codec::Deflate => {
-            decompress.clear();
+        Some(Compression::Deflate) => {
+            decompressed.clear();
             let mut decoder = Decoder::new(&block[..]);
-            decoder.read_to_end(decompress)?;
+            decoder.read_to_end(decompressed)?;
+            Ok(false)
+        }
+        #[cfg(feature = "io_avro_compression")]
+        Some(Compression::Snappy) => {
+            // https://avro.apache.org/docs/current/spec.html#snappy
+            // the compressed data is followed by the big-endian CRC32 of the uncompressed data
+            if block.len() < 4 {
+                return Err(ArrowError::ExternalFormat(
+                    "Avro: a snappy-compressed block must contain a CRC32 checksum".to_string(),
+                ));
+            }
+            let (data, crc) = block.split_at(block.len() - 4);
+            let len = snap::raw::decompress_len(data)
+                .map_err(|e| ArrowError::External("".to_string(), Box::new(e)))?;
+            decompressed.clear();
+            decompressed.resize(len, 0);
+            snap::raw::Decoder::new()
+                .decompress(data, decompressed)
+                .map_err(|e| ArrowError::External("".to_string(), Box::new(e)))?;
+
+            let expected = u32::from_be_bytes([crc[0], crc[1], crc[2], crc[3]]);
+            if crc32fast::hash(decompressed) != expected {
+                return Err(ArrowError::ExternalFormat(
+                    "Avro: the CRC32 checksum of a snappy-compressed block does not match its content"
+                        .to_string(),
+                ));
+            }
+            Ok(false)
+        }
+        #[cfg(feature = "io_avro_compression")]
+        Some(Compression::Zstandard) => {
+            decompressed.clear();
+            zstd::stream::copy_decode(&block[..], decompressed)?;
+            Ok(false)
+        }
+        #[cfg(feature = "io_avro_compression")]
+        Some(Compression::Bzip2) => {
+            decompressed.clear();
+            let mut decoder = bzip2::read::BzDecoder::new(&block[..]);
+            decoder.read_to_end(decompressed)?;
+            Ok(false)
+        }
+        #[cfg(feature = "io_avro_compression")]
+        Some(Compression::Xz) => {
+            decompressed.clear();
+            let mut decoder = xz2::read::XzDecoder::new(&block[..]);
+            decoder.read_to_end(decompressed)?;
             Ok(false)
         }
+        #[cfg(not(feature = "io_avro_compression"))]
+        Some(compression) => Err(ArrowError::InvalidArgumentError(format!(
+            "The avro file is compressed with {:?} but the feature 'io_avro_compression' is not active.",
+            compression
+        ))),
     }
 }
 
@@ -130,17 +215,17 @@ impl<'a, R: Read> FallibleStreamingIterator for BlockStreamIterator<'a, R> {
 /// [`StreamingIterator`] of blocks of decompressed avro data
 pub struct Decompressor<'a, R: Read> {
     blocks: BlockStreamIterator<'a, R>,
-    codec: Codec,
+    compression: Option<Compression>,
     buf: (Vec<u8>, usize),
     was_swapped: bool,
 }
 
 impl<'a, R: Read> Decompressor<'a, R> {
     /// Creates a new [`Decompressor`].
-    pub fn new(blocks: BlockStreamIterator<'a, R>, codec: Codec) -> Self {
+    pub fn new(blocks: BlockStreamIterator<'a, R>, compression: Option<Compression>) -> Self {
         Self {
             blocks,
-            codec,
+            compression,
             buf: (vec![], 0),
             was_swapped: false,
         }
@@ -156,7 +241,8 @@ impl<'a, R: Read> FallibleStreamingIterator for Decompressor<'a, R> {
             std::mem::swap(self.blocks.buffer(), &mut self.buf.0);
         }
         self.blocks.advance()?;
-        self.was_swapped = decompress_block(self.blocks.buffer(), &mut self.buf.0, self.codec)?;
+        self.was_swapped =
+            decompress_block(self.blocks.buffer(), &mut self.buf.0, self.compression)?;
         self.buf.1 = self.blocks.get().map(|(_, rows)| *rows).unwrap_or_default();
         Ok(())
     }
@@ -175,21 +261,81 @@ pub struct Reader<'a, R: Read> {
     iter: Decompressor<'a, R>,
     schema: Arc<Schema>,
     avro_schemas: Vec<AvroSchema>,
+    projection: Vec<bool>,
+    resolution: Option<Resolution>,
 }
 
 impl<'a, R: Read> Reader<'a, R> {
-    /// Creates a new [`Reader`].
+    /// Creates a new [`Reader`]. Use `projection` to only read certain columns, whose
+    /// encoding is skipped without being deserialized.
+    /// # Panics
+    /// Panics iff the projection is not in increasing order (e.g. `[1, 0]` nor `[0, 1, 1]` are valid)
+    /// or contains an index larger than the number of fields.
     pub fn new(
         iter: Decompressor<'a, R>,
         avro_schemas: Vec<AvroSchema>,
         schema: Arc<Schema>,
+        projection: Option<Vec<usize>>,
     ) -> Self {
+        let (schema, projection) = prepare_projection(&schema, projection);
         Self {
             iter,
             avro_schemas,
             schema,
+            projection,
+            resolution: None,
         }
     }
+
+    /// Creates a new [`Reader`] of data written with the fields' schemas `avro_schemas`
+    /// whose [`RecordBatch`]es are in the shape of the reader schema of `resolution`.
+    /// See [`resolve`] for how to resolve a writer schema against a reader schema.
+    pub fn with_resolution(
+        iter: Decompressor<'a, R>,
+        avro_schemas: Vec<AvroSchema>,
+        resolution: Resolution,
+    ) -> Self {
+        let projection = vec![true; avro_schemas.len()];
+        Self {
+            iter,
+            avro_schemas,
+            schema: resolution.schema().clone(),
+            projection,
+            resolution: Some(resolution),
+        }
+    }
+
+    /// The (projected) schema of the [`RecordBatch`]es of this [`Reader`].
+    pub fn schema(&self) -> &Arc<Schema> {
+        &self.schema
+    }
+}
+
+/// Returns the projected schema of `schema` and the mask of the projected fields,
+/// as expected by [`deserialize`].
+/// # Panics
+/// Panics iff the projection is not in increasing order (e.g. `[1, 0]` nor `[0, 1, 1]` are valid)
+/// or contains an index larger than the number of fields.
+pub fn prepare_projection(
+    schema: &Arc<Schema>,
+    projection: Option<Vec<usize>>,
+) -> (Arc<Schema>, Vec<bool>) {
+    let fields = schema.fields();
+    if let Some(projection) = projection {
+        assert!(
+            projection.windows(2).all(|x| x[0] < x[1]),
+            "The projection on Avro files must be ordered and non-overlapping"
+        );
+        let mut mask = vec![false; fields.len()];
+        projection.iter().for_each(|x| mask[*x] = true);
+        let fields = projection.iter().map(|x| fields[*x].clone()).collect();
+        (
+            Arc::new(Schema::new_from(fields, schema.metadata().clone())),
+            mask,
+        )
+    } else {
+        (schema.clone(), vec![true; fields.len()])
+    }
 }
 
 impl<'a, R: Read> Iterator for Reader<'a, R> {
@@ -198,10 +344,16 @@ impl<'a, R: Read> Iterator for Reader<'a, R> {
     fn next(&mut self) -> Option<Self::Item> {
         let schema = self.schema.clone();
         let avro_schemas = &self.avro_schemas;
+        let projection = &self.projection;
+        let resolution = &self.resolution;
 
         self.iter.next().transpose().map(|x| {
             let (data, rows) = x?;
-            deserialize::deserialize(data, *rows, schema, avro_schemas)
+            if let Some(resolution) = resolution {
+                deserialize_resolved(data, *rows, avro_schemas, resolution)
+            } else {
+                deserialize(data, *rows, schema, avro_schemas, projection)
+            }
         })
     }
 }
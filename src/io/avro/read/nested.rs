@@ -5,6 +5,7 @@ use crate::bitmap::*;
 use crate::buffer::*;
 use crate::datatypes::*;
 use crate::error::*;
+use crate::types::Index;
 
 /// Auxiliary struct
 #[derive(Debug)]
@@ -29,12 +30,6 @@ impl<O: Offset> DynMutableListArray<O> {
         }
     }
 
-    /// Creates a new [`MutableListArray`] from a [`MutableArray`] and capacity.
-    pub fn new_with_capacity(values: Box<dyn MutableArray>, capacity: usize) -> Self {
-        let data_type = ListArray::<O>::default_datatype(values.data_type().clone());
-        Self::new_from(values, data_type, capacity)
-    }
-
     /// The values
     pub fn mut_values(&mut self) -> &mut dyn MutableArray {
         self.values.as_mut()
@@ -122,7 +117,11 @@ impl<O: Offset> MutableArray for DynMutableListArray<O> {
     }
 
     fn shrink_to_fit(&mut self) {
-        todo!();
+        self.values.shrink_to_fit();
+        self.offsets.shrink_to_fit();
+        if let Some(validity) = &mut self.validity {
+            validity.shrink_to_fit()
+        }
     }
 }
 
@@ -196,6 +195,381 @@ impl MutableArray for FixedItemsUtf8Dictionary {
     }
 
     fn shrink_to_fit(&mut self) {
-        todo!();
+        self.keys.shrink_to_fit()
+    }
+}
+
+/// Auxiliary struct
+#[derive(Debug)]
+pub struct DynMutableStructArray {
+    data_type: DataType,
+    length: usize,
+    values: Vec<Box<dyn MutableArray>>,
+    validity: Option<MutableBitmap>,
+}
+
+impl DynMutableStructArray {
+    pub fn new(values: Vec<Box<dyn MutableArray>>, data_type: DataType) -> Self {
+        assert_eq!(values.len(), StructArray::get_fields(&data_type).len());
+        Self {
+            data_type,
+            length: 0,
+            values,
+            validity: None,
+        }
     }
+
+    /// The values
+    pub fn mut_values(&mut self) -> &mut [Box<dyn MutableArray>] {
+        &mut self.values
+    }
+
+    #[inline]
+    pub fn try_push_valid(&mut self) -> Result<()> {
+        self.length += 1;
+        if let Some(validity) = &mut self.validity {
+            validity.push(true)
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn push_null(&mut self) {
+        self.values.iter_mut().for_each(|x| x.push_null());
+        self.length += 1;
+        match &mut self.validity {
+            Some(validity) => validity.push(false),
+            None => self.init_validity(),
+        }
+    }
+
+    fn init_validity(&mut self) {
+        let len = self.len();
+
+        let mut validity = MutableBitmap::new();
+        validity.extend_constant(len, true);
+        validity.set(len - 1, false);
+        self.validity = Some(validity)
+    }
+
+    fn as_struct(&mut self) -> StructArray {
+        self.length = 0;
+        StructArray::from_data(
+            self.data_type.clone(),
+            self.values.iter_mut().map(|x| x.as_arc()).collect(),
+            std::mem::take(&mut self.validity).map(|x| x.into()),
+        )
+    }
+}
+
+impl MutableArray for DynMutableStructArray {
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn validity(&self) -> Option<&MutableBitmap> {
+        self.validity.as_ref()
+    }
+
+    fn as_box(&mut self) -> Box<dyn Array> {
+        Box::new(self.as_struct())
+    }
+
+    fn as_arc(&mut self) -> Arc<dyn Array> {
+        Arc::new(self.as_struct())
+    }
+
+    fn data_type(&self) -> &DataType {
+        &self.data_type
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    #[inline]
+    fn push_null(&mut self) {
+        self.push_null()
+    }
+
+    fn shrink_to_fit(&mut self) {
+        self.values.iter_mut().for_each(|x| x.shrink_to_fit());
+        if let Some(validity) = &mut self.validity {
+            validity.shrink_to_fit()
+        }
+    }
+}
+
+/// Auxiliary struct
+#[derive(Debug)]
+pub struct DynMutableMapArray {
+    data_type: DataType,
+    offsets: MutableBuffer<i32>,
+    values: DynMutableStructArray,
+    validity: Option<MutableBitmap>,
+}
+
+impl DynMutableMapArray {
+    pub fn new(values: DynMutableStructArray, data_type: DataType, capacity: usize) -> Self {
+        let mut offsets = MutableBuffer::<i32>::with_capacity(capacity + 1);
+        offsets.push(0);
+        assert_eq!(values.len(), 0);
+        assert_eq!(
+            MapArray::get_field(&data_type).data_type(),
+            values.data_type()
+        );
+        Self {
+            data_type,
+            offsets,
+            values,
+            validity: None,
+        }
+    }
+
+    /// The entries, a struct of keys and values
+    pub fn mut_values(&mut self) -> &mut DynMutableStructArray {
+        &mut self.values
+    }
+
+    #[inline]
+    pub fn try_push_valid(&mut self) -> Result<()> {
+        let size = self.values.len();
+        let size = i32::from_usize(size).ok_or(ArrowError::KeyOverflowError)?;
+        assert!(size >= *self.offsets.last().unwrap());
+
+        self.offsets.push(size);
+        if let Some(validity) = &mut self.validity {
+            validity.push(true)
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn push_null(&mut self) {
+        self.offsets.push(*self.offsets.last().unwrap());
+        match &mut self.validity {
+            Some(validity) => validity.push(false),
+            None => self.init_validity(),
+        }
+    }
+
+    fn init_validity(&mut self) {
+        let len = self.offsets.len() - 1;
+
+        let mut validity = MutableBitmap::new();
+        validity.extend_constant(len, true);
+        validity.set(len - 1, false);
+        self.validity = Some(validity)
+    }
+
+    fn as_map(&mut self) -> MapArray {
+        MapArray::from_data(
+            self.data_type.clone(),
+            std::mem::take(&mut self.offsets).into(),
+            self.values.as_arc(),
+            std::mem::take(&mut self.validity).map(|x| x.into()),
+        )
+    }
+}
+
+impl MutableArray for DynMutableMapArray {
+    fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    fn validity(&self) -> Option<&MutableBitmap> {
+        self.validity.as_ref()
+    }
+
+    fn as_box(&mut self) -> Box<dyn Array> {
+        Box::new(self.as_map())
+    }
+
+    fn as_arc(&mut self) -> Arc<dyn Array> {
+        Arc::new(self.as_map())
+    }
+
+    fn data_type(&self) -> &DataType {
+        &self.data_type
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    #[inline]
+    fn push_null(&mut self) {
+        self.push_null()
+    }
+
+    fn shrink_to_fit(&mut self) {
+        self.values.shrink_to_fit();
+        self.offsets.shrink_to_fit();
+        if let Some(validity) = &mut self.validity {
+            validity.shrink_to_fit()
+        }
+    }
+}
+
+/// Auxiliary struct of a dense [`UnionArray`]
+#[derive(Debug)]
+pub struct DynMutableUnionArray {
+    data_type: DataType,
+    types: MutableBuffer<i8>,
+    offsets: MutableBuffer<i32>,
+    fields: Vec<Box<dyn MutableArray>>,
+}
+
+impl DynMutableUnionArray {
+    pub fn new(fields: Vec<Box<dyn MutableArray>>, data_type: DataType, capacity: usize) -> Self {
+        assert_eq!(fields.len(), UnionArray::get_fields(&data_type).len());
+        Self {
+            data_type,
+            types: MutableBuffer::with_capacity(capacity),
+            offsets: MutableBuffer::with_capacity(capacity),
+            fields,
+        }
+    }
+
+    /// Declares that the next slot is of variant `type_`, returning the variant's values,
+    /// to where the slot's value must be pushed.
+    #[inline]
+    pub fn try_push_type(&mut self, type_: usize) -> Result<&mut dyn MutableArray> {
+        let field = self.fields.get_mut(type_).ok_or_else(|| {
+            ArrowError::ExternalFormat(format!("The union has no variant {}", type_))
+        })?;
+        self.types.push(type_ as i8);
+        self.offsets.push(field.len() as i32);
+        Ok(field.as_mut())
+    }
+
+    #[inline]
+    fn push_null(&mut self) {
+        // unions have no validity: the null is pushed to the first variant that is null, if any
+        let type_ = self
+            .fields
+            .iter()
+            .position(|x| x.data_type() == &DataType::Null)
+            .unwrap_or(0);
+        self.types.push(type_ as i8);
+        self.offsets.push(self.fields[type_].len() as i32);
+        self.fields[type_].push_null()
+    }
+
+    fn as_union(&mut self) -> UnionArray {
+        UnionArray::from_data(
+            self.data_type.clone(),
+            std::mem::take(&mut self.types).into(),
+            self.fields.iter_mut().map(|x| x.as_arc()).collect(),
+            Some(std::mem::take(&mut self.offsets).into()),
+        )
+    }
+}
+
+impl MutableArray for DynMutableUnionArray {
+    fn len(&self) -> usize {
+        self.types.len()
+    }
+
+    fn validity(&self) -> Option<&MutableBitmap> {
+        None
+    }
+
+    fn as_box(&mut self) -> Box<dyn Array> {
+        Box::new(self.as_union())
+    }
+
+    fn as_arc(&mut self) -> Arc<dyn Array> {
+        Arc::new(self.as_union())
+    }
+
+    fn data_type(&self) -> &DataType {
+        &self.data_type
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    #[inline]
+    fn push_null(&mut self) {
+        self.push_null()
+    }
+
+    fn shrink_to_fit(&mut self) {
+        self.types.shrink_to_fit();
+        self.offsets.shrink_to_fit();
+        self.fields.iter_mut().for_each(|x| x.shrink_to_fit());
+    }
+}
+
+/// Auxiliary struct
+#[derive(Debug)]
+pub struct MutableNullArray {
+    data_type: DataType,
+    length: usize,
+}
+
+impl MutableNullArray {
+    pub fn new(data_type: DataType) -> Self {
+        Self {
+            data_type,
+            length: 0,
+        }
+    }
+}
+
+impl MutableArray for MutableNullArray {
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn validity(&self) -> Option<&MutableBitmap> {
+        None
+    }
+
+    fn as_box(&mut self) -> Box<dyn Array> {
+        Box::new(NullArray::from_data(
+            self.data_type.clone(),
+            std::mem::take(&mut self.length),
+        ))
+    }
+
+    fn as_arc(&mut self) -> Arc<dyn Array> {
+        Arc::new(NullArray::from_data(
+            self.data_type.clone(),
+            std::mem::take(&mut self.length),
+        ))
+    }
+
+    fn data_type(&self) -> &DataType {
+        &self.data_type
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    #[inline]
+    fn push_null(&mut self) {
+        self.length += 1;
+    }
+
+    fn shrink_to_fit(&mut self) {}
 }
@@ -0,0 +1,123 @@
+use std::collections::VecDeque;
+use std::io::Read;
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::Arc;
+
+use avro_rs::Schema as AvroSchema;
+use fallible_streaming_iterator::FallibleStreamingIterator;
+
+use crate::datatypes::Schema;
+use crate::error::{ArrowError, Result};
+use crate::record_batch::RecordBatch;
+
+use super::super::Compression;
+use super::{decompress_block, deserialize, prepare_projection, BlockStreamIterator};
+
+/// A task that decompresses and deserializes a block, to be run by a thread pool.
+pub type Task = Box<dyn FnOnce() + Send>;
+
+/// Multi-threaded reader of Avro files; [`Iterator`] of [`RecordBatch`]es.
+///
+/// Blocks are read from the file on the calling thread and each of them is decompressed and
+/// deserialized by a [`Task`] handed to `spawn`, which is expected to run it on a thread
+/// pool (e.g. `|task| pool.spawn(task)`). The [`RecordBatch`]es are yielded in the order of
+/// the blocks in the file.
+pub struct ParallelReader<'a, R: Read, S: FnMut(Task)> {
+    blocks: BlockStreamIterator<'a, R>,
+    compression: Option<Compression>,
+    avro_schemas: Arc<Vec<AvroSchema>>,
+    schema: Arc<Schema>,
+    projection: Arc<Vec<bool>>,
+    spawn: S,
+    // the results of the spawned tasks, in the order of their blocks
+    tasks: VecDeque<Receiver<Result<RecordBatch>>>,
+    max_tasks: usize,
+    is_finished: bool,
+}
+
+impl<'a, R: Read, S: FnMut(Task)> ParallelReader<'a, R, S> {
+    /// Creates a new [`ParallelReader`] that has at most `max_tasks` blocks being
+    /// decompressed and deserialized at any given time.
+    /// Use `projection` to only read certain columns, as in [`Reader::new`](super::Reader::new).
+    /// # Panics
+    /// Panics iff `max_tasks` is zero, or the projection is not in increasing order or
+    /// contains an index larger than the number of fields.
+    pub fn new(
+        blocks: BlockStreamIterator<'a, R>,
+        compression: Option<Compression>,
+        avro_schemas: Vec<AvroSchema>,
+        schema: Arc<Schema>,
+        projection: Option<Vec<usize>>,
+        spawn: S,
+        max_tasks: usize,
+    ) -> Self {
+        assert!(max_tasks > 0, "A ParallelReader requires at least one task");
+        let (schema, projection) = prepare_projection(&schema, projection);
+        Self {
+            blocks,
+            compression,
+            avro_schemas: Arc::new(avro_schemas),
+            schema,
+            projection: Arc::new(projection),
+            spawn,
+            tasks: VecDeque::with_capacity(max_tasks),
+            max_tasks,
+            is_finished: false,
+        }
+    }
+
+    /// The (projected) schema of the [`RecordBatch`]es of this [`ParallelReader`].
+    pub fn schema(&self) -> &Arc<Schema> {
+        &self.schema
+    }
+
+    /// Reads the next block from the file and spawns a task to decode it.
+    fn spawn_next(&mut self) -> Result<()> {
+        self.blocks.advance()?;
+        let rows = if let Some((_, rows)) = self.blocks.get() {
+            *rows
+        } else {
+            self.is_finished = true;
+            return Ok(());
+        };
+        let mut block = std::mem::take(self.blocks.buffer());
+
+        let compression = self.compression;
+        let schema = self.schema.clone();
+        let avro_schemas = self.avro_schemas.clone();
+        let projection = self.projection.clone();
+        let (sender, receiver) = channel();
+        (self.spawn)(Box::new(move || {
+            let mut decompressed = vec![];
+            let result = decompress_block(&mut block, &mut decompressed, compression)
+                .and_then(|_| deserialize(&decompressed, rows, schema, &avro_schemas, &projection));
+            // the reader may have been dropped, in which case the result is no longer needed
+            let _ = sender.send(result);
+        }));
+        self.tasks.push_back(receiver);
+        Ok(())
+    }
+}
+
+impl<'a, R: Read, S: FnMut(Task)> Iterator for ParallelReader<'a, R, S> {
+    type Item = Result<RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.is_finished && self.tasks.len() < self.max_tasks {
+            if let Err(e) = self.spawn_next() {
+                // the error is yielded after the blocks read before it
+                let (sender, receiver) = channel();
+                let _ = sender.send(Err(e));
+                self.tasks.push_back(receiver);
+                self.is_finished = true;
+            }
+        }
+        self.tasks.pop_front().map(|task| {
+            task.recv().unwrap_or_else(|_| {
+                Err(ArrowError::Other(
+                    "The task deserializing an Avro block ended without a result".to_string(),
+                ))
+            })
+        })
+    }
+}
@@ -0,0 +1,190 @@
+//! Resolution of the schema data was written with (the writer schema) against the schema it
+//! is read with (the reader schema).
+//! See <https://avro.apache.org/docs/current/spec.html#Schema+Resolution>
+use std::sync::Arc;
+
+use avro_rs::types::Value;
+use avro_rs::Schema as AvroSchema;
+
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+
+use super::schema::convert_schema;
+
+/// The resolution of a writer schema against a reader schema, used to read Avro data
+/// into [`RecordBatch`](crate::record_batch::RecordBatch)es in the shape of the reader schema.
+#[derive(Debug, Clone)]
+pub struct Resolution {
+    /// the (arrow) reader schema
+    pub(super) schema: Arc<Schema>,
+    /// the schemas of the fields of the reader schema
+    pub(super) avro_schemas: Vec<AvroSchema>,
+    /// for each field of the writer, the field of the reader it is read into, if any
+    pub(super) mapping: Vec<Option<usize>>,
+    /// for each field of the reader that the writer does not have, its Avro-encoded default
+    pub(super) defaults: Vec<Option<Vec<u8>>>,
+}
+
+impl Resolution {
+    /// The schema of the [`RecordBatch`](crate::record_batch::RecordBatch)es read with this
+    /// [`Resolution`].
+    pub fn schema(&self) -> &Arc<Schema> {
+        &self.schema
+    }
+}
+
+/// Returns the aliases of each field of `reader_schema`, a record.
+/// `avro_rs` does not keep the aliases of fields when parsing a schema, so they are read from
+/// its JSON.
+fn field_aliases(reader_schema: &serde_json::Value) -> Vec<Vec<&str>> {
+    reader_schema
+        .get("fields")
+        .and_then(|fields| fields.as_array())
+        .map(|fields| {
+            fields
+                .iter()
+                .map(|field| {
+                    field
+                        .get("aliases")
+                        .and_then(|aliases| aliases.as_array())
+                        .map(|aliases| aliases.iter().filter_map(|x| x.as_str()).collect())
+                        .unwrap_or_default()
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The name of a field of a record, which is qualified by the record's name (`"{record}.{field}"`)
+fn unqualified(name: &str) -> &str {
+    name.rsplit('.').next().unwrap_or(name)
+}
+
+/// Whether data of the `writer` field can be read as the `reader` field.
+/// Nullable fields can't be read as non-nullable fields.
+fn is_promotable_field(writer: &Field, reader: &Field) -> bool {
+    (reader.is_nullable() || !writer.is_nullable())
+        && is_promotable(writer.data_type(), reader.data_type())
+}
+
+/// Whether data of `writer` can be read as `reader`
+fn is_promotable(writer: &DataType, reader: &DataType) -> bool {
+    use DataType::*;
+    match (writer, reader) {
+        (List(writer), List(reader)) => is_promotable_field(writer, reader),
+        (Map(writer, _), Map(reader, _)) => is_promotable_field(writer, reader),
+        // records are matched irrespectively of their names
+        (Struct(writer), Struct(reader)) => {
+            writer.len() == reader.len()
+                && writer.iter().zip(reader.iter()).all(|(writer, reader)| {
+                    unqualified(writer.name()) == unqualified(reader.name())
+                        && is_promotable_field(writer, reader)
+                })
+        }
+        _ => {
+            matches!(
+                (writer, reader),
+                (Int32, Int64)
+                    | (Int32, Float32)
+                    | (Int32, Float64)
+                    | (Int64, Float32)
+                    | (Int64, Float64)
+                    | (Float32, Float64)
+                    | (Utf8, Binary)
+                    | (Binary, Utf8)
+            ) || writer == reader
+        }
+    }
+}
+
+/// Resolves the schema data was written with, `writer_schema` (as returned by
+/// [`read_metadata`](super::read_metadata)), against `reader_schema`, the JSON of an Avro
+/// schema, following Avro's resolution rules:
+/// * fields are matched by name or by the aliases of the reader's field, irrespectively of
+///   their order
+/// * fields of the writer that the reader does not have are ignored
+/// * fields of the reader that the writer does not have are filled with their default
+/// * `int` is promoted to `long`, `float` and `double`, `long` to `float` and `double`,
+///   `float` to `double`, and `string` and `bytes` are interchangeable, including in the
+///   items of arrays and maps and in the fields of nested records
+///
+/// Nested records must have the same fields, in the same order, on both schemas, and their
+/// fields are not matched by aliases. Other types must be equal on both schemas.
+/// # Errors
+/// This function errors iff `reader_schema` is not a valid Avro record, a field of the reader
+/// has no default and is not part of the writer, a nullable field of the writer is read as a
+/// non-nullable field, or the types of matching fields can't be resolved.
+pub fn resolve(writer_schema: &Schema, reader_schema: &str) -> Result<Resolution> {
+    let json = serde_json::from_str(reader_schema).map_err(|e| {
+        ArrowError::ExternalFormat(format!("The Avro reader schema is not valid JSON: {}", e))
+    })?;
+    let reader_schema = AvroSchema::parse(&json)?;
+    let reader_fields = if let AvroSchema::Record { fields, .. } = &reader_schema {
+        fields
+    } else {
+        return Err(ArrowError::Schema(
+            "The Avro reader schema must be a record".to_string(),
+        ));
+    };
+    let aliases = field_aliases(&json);
+    let schema = convert_schema(&reader_schema)?;
+
+    let mapping = writer_schema
+        .fields()
+        .iter()
+        .map(|writer| {
+            let index = schema.fields().iter().enumerate().position(|(i, reader)| {
+                reader.name() == writer.name()
+                    || aliases
+                        .get(i)
+                        .map(|aliases| aliases.contains(&writer.name().as_str()))
+                        .unwrap_or(false)
+            });
+            if let Some(index) = index {
+                let reader = &schema.fields()[index];
+                if writer.is_nullable() && !reader.is_nullable() {
+                    return Err(ArrowError::Schema(format!(
+                        "The nullable field \"{}\" can't be read as the non-nullable field \"{}\"",
+                        writer.name(),
+                        reader.name(),
+                    )));
+                }
+                if !is_promotable(writer.data_type(), reader.data_type()) {
+                    return Err(ArrowError::Schema(format!(
+                        "The field \"{}\" of type {:?} can't be read as the field \"{}\" of type {:?}",
+                        writer.name(),
+                        writer.data_type(),
+                        reader.name(),
+                        reader.data_type()
+                    )));
+                }
+            }
+            Ok(index)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let defaults = reader_fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            if mapping.contains(&Some(index)) {
+                return Ok(None);
+            }
+            let default = field.default.clone().ok_or_else(|| {
+                ArrowError::Schema(format!(
+                    "The field \"{}\" of the reader schema has no default and is not part of the writer schema",
+                    field.name
+                ))
+            })?;
+            let default = Value::from(default).resolve(&field.schema)?;
+            Ok(Some(avro_rs::to_avro_datum(&field.schema, default)?))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Resolution {
+        schema: Arc::new(schema),
+        avro_schemas: reader_fields.iter().map(|x| x.schema.clone()).collect(),
+        mapping,
+        defaults,
+    })
+}
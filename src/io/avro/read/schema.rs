@@ -117,7 +117,17 @@ fn schema_to_field(
             false,
             None,
         )?)),
-        AvroSchema::Map(_) => todo!("Avro maps are mapped to MapArrays"),
+        AvroSchema::Map(values_schema) => {
+            // Avro maps' keys are always strings
+            let fields = vec![
+                Field::new("key", DataType::Utf8, false),
+                schema_to_field(values_schema, Some("value"), false, None)?,
+            ];
+            DataType::Map(
+                Box::new(Field::new("entries", DataType::Struct(fields), false)),
+                false,
+            )
+        }
         AvroSchema::Union(us) => {
             // If there are only two variants and one of them is null, set the other type as the field data type
             let has_nullable = us.find_schema(&Value::Null).is_some();
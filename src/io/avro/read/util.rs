@@ -1,11 +1,13 @@
+use std::collections::HashMap;
 use std::io::Read;
-use std::str::FromStr;
 
-use crate::error::Result;
+use crate::error::{ArrowError, Result};
 
-use avro_rs::{from_avro_datum, types::Value, AvroResult, Codec, Error, Schema};
+use avro_rs::{from_avro_datum, types::Value, AvroResult, Error, Schema};
 use serde_json::from_slice;
 
+use super::super::Compression;
+
 pub fn zigzag_i64<R: Read>(reader: &mut R) -> Result<i64> {
     let z = decode_variable(reader)?;
     Ok(if z & 0x1 == 0 {
@@ -43,52 +45,70 @@ fn read_file_marker<R: std::io::Read>(reader: &mut R) -> AvroResult<[u8; 16]> {
     Ok(marker)
 }
 
-/// Reads the schema from `reader`, returning the file's [`Schema`] and [`Codec`].
+/// Reads the schema from `reader`, returning the file's [`Schema`], [`Compression`] and marker.
 /// # Error
 /// This function errors iff the header is not a valid avro file header.
-pub fn read_schema<R: Read>(reader: &mut R) -> AvroResult<(Schema, Codec, [u8; 16])> {
+pub fn read_schema<R: Read>(reader: &mut R) -> Result<(Schema, Option<Compression>, [u8; 16])> {
     let meta_schema = Schema::Map(Box::new(Schema::Bytes));
 
     let mut buf = [0u8; 4];
     reader.read_exact(&mut buf).map_err(Error::ReadHeader)?;
 
     if buf != [b'O', b'b', b'j', 1u8] {
-        return Err(Error::HeaderMagic);
+        return Err(Error::HeaderMagic.into());
     }
 
     if let Value::Map(meta) = from_avro_datum(&meta_schema, reader, None)? {
-        // TODO: surface original parse schema errors instead of coalescing them here
-        let json = meta
-            .get("avro.schema")
-            .and_then(|bytes| {
-                if let Value::Bytes(ref bytes) = *bytes {
-                    from_slice(bytes.as_ref()).ok()
-                } else {
-                    None
-                }
-            })
-            .ok_or(Error::GetAvroSchemaFromMap)?;
-        let schema = Schema::parse(&json)?;
-
-        let codec = if let Some(codec) = meta
-            .get("avro.codec")
-            .and_then(|codec| {
-                if let Value::Bytes(ref bytes) = *codec {
-                    simdutf8::basic::from_utf8(bytes.as_ref()).ok()
+        let header = meta
+            .into_iter()
+            .filter_map(|(key, value)| {
+                if let Value::Bytes(bytes) = value {
+                    Some((key, bytes))
                 } else {
                     None
                 }
             })
-            .and_then(|codec| Codec::from_str(codec).ok())
-        {
-            codec
-        } else {
-            Codec::Null
-        };
+            .collect();
+        let (schema, compression) = deserialize_header(header)?;
         let marker = read_file_marker(reader)?;
 
-        Ok((schema, codec, marker))
+        Ok((schema, compression, marker))
     } else {
-        Err(Error::GetHeaderMetadata)
+        Err(Error::GetHeaderMetadata.into())
     }
 }
+
+/// Deserializes the schema and compression from the (already read) metadata of a file's header.
+/// # Error
+/// This function errors iff the metadata does not contain a valid Avro schema or
+/// the file's codec is not part of Avro's specification.
+pub fn deserialize_header(
+    header: HashMap<String, Vec<u8>>,
+) -> Result<(Schema, Option<Compression>)> {
+    let json = header
+        .get("avro.schema")
+        .and_then(|bytes| from_slice(bytes.as_ref()).ok())
+        .ok_or(Error::GetAvroSchemaFromMap)?;
+    let schema = Schema::parse(&json)?;
+
+    let compression = header.get("avro.codec").map(|bytes| {
+        let codec = simdutf8::basic::from_utf8(bytes.as_ref())?;
+        Ok(match codec {
+            "null" => None,
+            "deflate" => Some(Compression::Deflate),
+            "snappy" => Some(Compression::Snappy),
+            "zstandard" => Some(Compression::Zstandard),
+            "bzip2" => Some(Compression::Bzip2),
+            "xz" => Some(Compression::Xz),
+            other => {
+                return Err(ArrowError::ExternalFormat(format!(
+                    "The avro file has an unknown codec \"{}\"",
+                    other
+                )))
+            }
+        })
+    });
+    // a file without codec is not compressed
+    let compression = compression.transpose()?.flatten();
+    Ok((schema, compression))
+}
@@ -0,0 +1,75 @@
+//! Async reading of Avro blocks
+use futures::stream::{self, Stream};
+use futures::AsyncRead;
+use futures::AsyncReadExt;
+
+use crate::error::{ArrowError, Result};
+
+use super::super::read::decompress_block;
+use super::super::Compression;
+use super::utils::zigzag_i64;
+
+async fn read_size<R: AsyncRead + Unpin + Send>(reader: &mut R) -> Result<(usize, usize)> {
+    let rows = match zigzag_i64(reader).await {
+        Ok(a) => a,
+        Err(ArrowError::Io(io_err)) => {
+            if let std::io::ErrorKind::UnexpectedEof = io_err.kind() {
+                // end
+                return Ok((0, 0));
+            } else {
+                return Err(ArrowError::Io(io_err));
+            }
+        }
+        Err(other) => return Err(other),
+    };
+    let bytes = zigzag_i64(reader).await?;
+    Ok((rows as usize, bytes as usize))
+}
+
+/// Reads a block from the file into `buf`, returning its number of rows.
+/// # Errors
+/// Errors iff the block marker does not equal to the file's marker
+async fn read_block<R: AsyncRead + Unpin + Send>(
+    reader: &mut R,
+    buf: &mut Vec<u8>,
+    file_marker: [u8; 16],
+) -> Result<usize> {
+    let (rows, bytes) = read_size(reader).await?;
+    if rows == 0 {
+        return Ok(0);
+    };
+
+    buf.resize(bytes, 0);
+    reader.read_exact(buf).await?;
+
+    let mut marker = [0u8; 16];
+    reader.read_exact(&mut marker).await?;
+
+    if marker != file_marker {
+        return Err(ArrowError::ExternalFormat(
+            "Avro: the sync marker in the block does not correspond to the file marker".to_string(),
+        ));
+    }
+    Ok(rows)
+}
+
+/// Returns a [`Stream`] of decompressed Avro blocks read from `reader`, each with its
+/// number of rows. Each block can be deserialized with
+/// [`deserialize`](super::super::read::deserialize).
+pub fn block_stream<R: AsyncRead + Unpin + Send>(
+    reader: &mut R,
+    file_marker: [u8; 16],
+    compression: Option<Compression>,
+) -> impl Stream<Item = Result<(Vec<u8>, usize)>> + '_ {
+    stream::try_unfold(reader, move |reader| async move {
+        let mut block = vec![];
+        let rows = read_block(reader, &mut block, file_marker).await?;
+        if rows == 0 {
+            return Ok(None);
+        }
+        let mut decompressed = vec![];
+        // the decompressed data is always in `decompressed`: either swapped or decompressed into
+        let _ = decompress_block(&mut block, &mut decompressed, compression)?;
+        Ok(Some(((decompressed, rows), reader)))
+    })
+}
@@ -0,0 +1,74 @@
+//! Async reading of the header of Avro files
+use std::collections::HashMap;
+
+use futures::AsyncRead;
+use futures::AsyncReadExt;
+
+use crate::error::{ArrowError, Result};
+
+use super::super::read::{convert_metadata, deserialize_header, Metadata};
+use super::utils::zigzag_i64;
+
+/// Reads Avro's metadata from `reader` into a [`Schema`](crate::datatypes::Schema),
+/// [`Compression`](super::super::Compression) and magic marker.
+/// # Errors
+/// This function errors iff the header is not a valid avro file header.
+pub async fn read_metadata<R: AsyncRead + Unpin + Send>(reader: &mut R) -> Result<Metadata> {
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic).await?;
+
+    if magic != [b'O', b'b', b'j', 1u8] {
+        return Err(ArrowError::ExternalFormat(
+            "Avro header does not contain a valid magic number".to_string(),
+        ));
+    }
+
+    let header = read_header(reader).await?;
+
+    let (avro_schema, compression) = deserialize_header(header)?;
+
+    let mut marker = [0u8; 16];
+    reader.read_exact(&mut marker).await?;
+
+    let (avro_schema, schema) = convert_metadata(avro_schema)?;
+    Ok((avro_schema, schema, compression, marker))
+}
+
+async fn read_bytes<R: AsyncRead + Unpin + Send>(reader: &mut R) -> Result<Vec<u8>> {
+    let len = zigzag_i64(reader).await?;
+    if len < 0 {
+        return Err(ArrowError::ExternalFormat(
+            "Avro header contains a negative number of bytes".to_string(),
+        ));
+    }
+    let mut data = vec![0; len as usize];
+    reader.read_exact(&mut data).await?;
+    Ok(data)
+}
+
+/// Reads the file's metadata, an Avro `map<bytes>`.
+async fn read_header<R: AsyncRead + Unpin + Send>(
+    reader: &mut R,
+) -> Result<HashMap<String, Vec<u8>>> {
+    let mut items = HashMap::new();
+
+    loop {
+        let len = zigzag_i64(reader).await?;
+        if len == 0 {
+            break Ok(items);
+        }
+        if len < 0 {
+            // the block's size in bytes, which we do not need
+            let _ = zigzag_i64(reader).await?;
+        }
+
+        items.reserve(len.abs() as usize);
+        for _ in 0..len.abs() {
+            let key = read_bytes(reader).await?;
+            let key = String::from_utf8(key)
+                .map_err(|_| ArrowError::ExternalFormat("Invalid Avro header".to_string()))?;
+            let value = read_bytes(reader).await?;
+            items.insert(key, value);
+        }
+    }
+}
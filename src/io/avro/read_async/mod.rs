@@ -0,0 +1,7 @@
+//! Async Avro
+mod block;
+mod metadata;
+mod utils;
+
+pub use block::block_stream;
+pub use metadata::read_metadata;
@@ -0,0 +1,235 @@
+#![deny(missing_docs)]
+//! APIs to write to Avro format.
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::io::Write;
+
+use libflate::deflate::Encoder;
+
+mod schema;
+mod serialize;
+mod util;
+
+pub use schema::to_avro_schema;
+pub use serialize::{new_serializer, BoxSerializer};
+
+use crate::datatypes::Schema;
+use crate::error::{ArrowError, Result};
+use crate::record_batch::RecordBatch;
+
+use super::Compression;
+
+/// Generates a random marker to separate blocks of a file.
+fn new_marker() -> [u8; 16] {
+    let mut marker = [0u8; 16];
+    marker.chunks_exact_mut(8).for_each(|chunk| {
+        // every `RandomState` is seeded with different random keys
+        let value = RandomState::new().build_hasher().finish();
+        chunk.copy_from_slice(&value.to_le_bytes())
+    });
+    marker
+}
+
+/// Writes Avro's metadata (magic, schema and codec) to `writer`, returning the
+/// marker of the file.
+/// # Errors
+/// This function errors iff `schema` can't be represented in Avro or `writer` errors.
+pub fn write_metadata<W: Write>(
+    writer: &mut W,
+    schema: &Schema,
+    compression: Option<Compression>,
+) -> Result<[u8; 16]> {
+    let schema = serde_json::to_vec(&schema::to_json(schema)?)
+        .map_err(|e| ArrowError::External("".to_string(), Box::new(e)))?;
+    let codec: &[u8] = match compression {
+        None => b"null",
+        Some(Compression::Deflate) => b"deflate",
+        Some(Compression::Snappy) => b"snappy",
+        Some(Compression::Zstandard) => b"zstandard",
+        Some(Compression::Bzip2) => b"bzip2",
+        Some(Compression::Xz) => b"xz",
+    };
+
+    writer.write_all(&[b'O', b'b', b'j', 1u8])?;
+
+    // the metadata is an Avro map<bytes> with two entries
+    let mut header = vec![];
+    util::zigzag_encode(2, &mut header);
+    util::encode_bytes(b"avro.schema", &mut header);
+    util::encode_bytes(&schema, &mut header);
+    util::encode_bytes(b"avro.codec", &mut header);
+    util::encode_bytes(codec, &mut header);
+    util::zigzag_encode(0, &mut header);
+    writer.write_all(&header)?;
+
+    let marker = new_marker();
+    writer.write_all(&marker)?;
+    Ok(marker)
+}
+
+/// Serializes all rows of `batch` into `block`, in Avro's (uncompressed) binary encoding.
+/// # Errors
+/// This function errors iff a column can't be written to Avro.
+pub fn serialize(batch: &RecordBatch, block: &mut Vec<u8>) -> Result<()> {
+    let mut serializers = batch
+        .schema()
+        .fields()
+        .iter()
+        .zip(batch.columns())
+        .map(|(field, array)| new_serializer(array.as_ref(), field.is_nullable()))
+        .collect::<Result<Vec<_>>>()?;
+
+    // _the_ transpose (columns -> rows)
+    for _ in 0..batch.num_rows() {
+        for serializer in serializers.iter_mut() {
+            let item = serializer.next().unwrap();
+            block.extend_from_slice(item);
+        }
+    }
+    Ok(())
+}
+
+/// Compresses `block` with `compression` into `compressed`.
+/// Returns whether the buffers should be swapped, i.e. when there is no compression.
+/// # Errors
+/// Errors iff the compression requires a feature that is not active.
+pub fn compress(
+    block: &mut Vec<u8>,
+    compressed: &mut Vec<u8>,
+    compression: Option<Compression>,
+) -> Result<bool> {
+    match compression {
+        None => {
+            std::mem::swap(block, compressed);
+            Ok(true)
+        }
+        Some(Compression::Deflate) => {
+            compressed.clear();
+            let mut encoder = Encoder::new(compressed);
+            encoder.write_all(block)?;
+            encoder.finish().into_result()?;
+            Ok(false)
+        }
+        #[cfg(feature = "io_avro_compression")]
+        Some(Compression::Snappy) => {
+            compressed.clear();
+            compressed.resize(snap::raw::max_compress_len(block.len()), 0);
+            let len = snap::raw::Encoder::new()
+                .compress(block, compressed)
+                .map_err(|e| ArrowError::External("".to_string(), Box::new(e)))?;
+            compressed.truncate(len);
+            // https://avro.apache.org/docs/current/spec.html#snappy
+            compressed.extend_from_slice(&crc32fast::hash(block).to_be_bytes());
+            Ok(false)
+        }
+        #[cfg(feature = "io_avro_compression")]
+        Some(Compression::Zstandard) => {
+            compressed.clear();
+            zstd::stream::copy_encode(&block[..], compressed, 0)?;
+            Ok(false)
+        }
+        #[cfg(feature = "io_avro_compression")]
+        Some(Compression::Bzip2) => {
+            compressed.clear();
+            let mut encoder =
+                bzip2::write::BzEncoder::new(compressed, bzip2::Compression::default());
+            encoder.write_all(block)?;
+            encoder.finish()?;
+            Ok(false)
+        }
+        #[cfg(feature = "io_avro_compression")]
+        Some(Compression::Xz) => {
+            compressed.clear();
+            let mut encoder = xz2::write::XzEncoder::new(compressed, 6);
+            encoder.write_all(block)?;
+            encoder.finish()?;
+            Ok(false)
+        }
+        #[cfg(not(feature = "io_avro_compression"))]
+        Some(compression) => Err(ArrowError::InvalidArgumentError(format!(
+            "Compressing Avro with {:?} requires the feature 'io_avro_compression'.",
+            compression
+        ))),
+    }
+}
+
+/// Writes a block of `rows` already (compressed) serialized rows to `writer`.
+pub fn write_block<W: Write>(
+    writer: &mut W,
+    block: &[u8],
+    rows: usize,
+    marker: [u8; 16],
+) -> Result<()> {
+    let mut header = vec![];
+    util::zigzag_encode(rows as i64, &mut header);
+    util::zigzag_encode(block.len() as i64, &mut header);
+    writer.write_all(&header)?;
+    writer.write_all(block)?;
+    writer.write_all(&marker)?;
+    Ok(())
+}
+
+/// Single threaded, blocking writer of Avro files. Each [`RecordBatch`] is written as a block.
+pub struct Writer<W: Write> {
+    writer: W,
+    schema: Schema,
+    compression: Option<Compression>,
+    marker: [u8; 16],
+    block: Vec<u8>,
+    compressed: Vec<u8>,
+}
+
+impl<W: Write> Writer<W> {
+    /// Creates a new [`Writer`], writing the file's metadata to `writer`.
+    /// # Errors
+    /// This function errors iff `schema` can't be represented in Avro or `writer` errors.
+    pub fn try_new(
+        mut writer: W,
+        schema: &Schema,
+        compression: Option<Compression>,
+    ) -> Result<Self> {
+        let marker = write_metadata(&mut writer, schema, compression)?;
+        Ok(Self {
+            writer,
+            schema: schema.clone(),
+            compression,
+            marker,
+            block: vec![],
+            compressed: vec![],
+        })
+    }
+
+    /// Writes `batch` as a block of the file.
+    /// # Errors
+    /// This function errors iff the schema of `batch` differs from the one of the file,
+    /// a column can't be written to Avro, or the writer errors.
+    pub fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        if batch.schema().fields() != self.schema.fields() {
+            return Err(ArrowError::InvalidArgumentError(
+                "The batch's schema differs from the file's schema".to_string(),
+            ));
+        }
+        if batch.num_rows() == 0 {
+            // Avro readers interpret a block without rows as the end of the file
+            return Ok(());
+        }
+        self.block.clear();
+        serialize(batch, &mut self.block)?;
+        let was_swapped = compress(&mut self.block, &mut self.compressed, self.compression)?;
+        write_block(
+            &mut self.writer,
+            &self.compressed,
+            batch.num_rows(),
+            self.marker,
+        )?;
+        if was_swapped {
+            std::mem::swap(&mut self.block, &mut self.compressed);
+        }
+        Ok(())
+    }
+
+    /// Consumes this [`Writer`], returning its underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
@@ -0,0 +1,117 @@
+use avro_rs::Schema as AvroSchema;
+use serde_json::{json, Value};
+
+use crate::array::StructArray;
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+
+/// Converts a [`Schema`] into an Avro record schema, the inverse of
+/// [`read_metadata`](super::super::read::read_metadata)'s conversion.
+/// # Errors
+/// This function errors iff a field's [`DataType`] has no Avro representation.
+pub fn to_avro_schema(schema: &Schema) -> Result<AvroSchema> {
+    let schema = to_json(schema)?;
+    Ok(AvroSchema::parse(&schema)?)
+}
+
+/// Returns the JSON representation of the Avro record schema of `schema`.
+pub(super) fn to_json(schema: &Schema) -> Result<Value> {
+    let fields = schema
+        .fields()
+        .iter()
+        .map(field_to_json)
+        .collect::<Result<Vec<_>>>()?;
+    Ok(json!({
+        "type": "record",
+        "name": "record",
+        "fields": fields,
+    }))
+}
+
+fn field_to_json(field: &Field) -> Result<Value> {
+    let mut value = json!({
+        "name": field.name(),
+        "type": type_to_json(field.data_type(), field.name(), field.is_nullable())?,
+    });
+    if let Some(doc) = field.metadata().as_ref().and_then(|x| x.get("avro::doc")) {
+        value["doc"] = doc.clone().into();
+    }
+    Ok(value)
+}
+
+/// The Avro schema of `data_type`: a union with `"null"` when `is_nullable`.
+/// `name` is used to name Avro's named types (records and fixed).
+fn type_to_json(data_type: &DataType, name: &str, is_nullable: bool) -> Result<Value> {
+    let schema = match data_type.to_logical_type() {
+        // a null is always null: it does not need a union
+        DataType::Null => return Ok(json!("null")),
+        DataType::Boolean => json!("boolean"),
+        DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::UInt8 | DataType::UInt16 => {
+            json!("int")
+        }
+        DataType::Int64 | DataType::UInt32 => json!("long"),
+        DataType::Float32 => json!("float"),
+        DataType::Float64 => json!("double"),
+        DataType::Utf8 | DataType::LargeUtf8 => json!("string"),
+        DataType::Binary | DataType::LargeBinary => json!("bytes"),
+        DataType::FixedSizeBinary(size) => json!({"type": "fixed", "name": name, "size": size}),
+        DataType::Date32 => json!({"type": "int", "logicalType": "date"}),
+        DataType::Time32(TimeUnit::Millisecond) => {
+            json!({"type": "int", "logicalType": "time-millis"})
+        }
+        DataType::Time64(TimeUnit::Microsecond) => {
+            json!({"type": "long", "logicalType": "time-micros"})
+        }
+        // Avro timestamps are instants in UTC, which is also how arrow stores them
+        DataType::Timestamp(TimeUnit::Millisecond, _) => {
+            json!({"type": "long", "logicalType": "timestamp-millis"})
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, _) => {
+            json!({"type": "long", "logicalType": "timestamp-micros"})
+        }
+        DataType::Decimal(precision, scale) => json!({
+            "type": "bytes",
+            "logicalType": "decimal",
+            "precision": precision,
+            "scale": scale,
+        }),
+        DataType::Interval(IntervalUnit::MonthDayNano) => json!({
+            "type": {"type": "fixed", "name": name, "size": 12},
+            "logicalType": "duration",
+        }),
+        DataType::List(field) | DataType::LargeList(field) => json!({
+            "type": "array",
+            "items": type_to_json(field.data_type(), field.name(), field.is_nullable())?,
+        }),
+        DataType::Struct(fields) => json!({
+            "type": "record",
+            "name": name,
+            "fields": fields.iter().map(field_to_json).collect::<Result<Vec<_>>>()?,
+        }),
+        DataType::Map(field, _) => {
+            let fields = StructArray::get_fields(field.data_type());
+            if fields[0].data_type().to_logical_type() != &DataType::Utf8 {
+                return Err(ArrowError::NotYetImplemented(
+                    "Avro maps only support utf8 keys".to_string(),
+                ));
+            }
+            json!({
+                "type": "map",
+                "values": type_to_json(fields[1].data_type(), fields[1].name(), fields[1].is_nullable())?,
+            })
+        }
+        DataType::Dictionary(_, values) => return type_to_json(values, name, is_nullable),
+        other => {
+            return Err(ArrowError::NotYetImplemented(format!(
+                "Writing {:?} to Avro is not supported",
+                other
+            )))
+        }
+    };
+    Ok(if is_nullable {
+        // the null must be the first variant, so that the default of the field can be null
+        json!(["null", schema])
+    } else {
+        schema
+    })
+}
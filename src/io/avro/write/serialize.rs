@@ -0,0 +1,299 @@
+use crate::array::*;
+use crate::datatypes::*;
+use crate::error::{ArrowError, Result};
+use crate::io::iterator::{BufStreamingIterator, StreamingIterator};
+use crate::types::{months_days_ns, NativeType};
+
+use super::util::{encode_bytes, zigzag_encode};
+
+/// A type alias for a boxed [`StreamingIterator`], used to write arrays into Avro.
+pub type BoxSerializer<'a> = Box<dyn StreamingIterator<Item = [u8]> + 'a>;
+
+/// Writes the variant of the union `["null", _]` of a nullable value
+#[inline]
+fn write_validity(is_nullable: bool, is_valid: bool, buf: &mut Vec<u8>) {
+    if is_nullable {
+        zigzag_encode(is_valid as i64, buf)
+    }
+}
+
+fn new_iterator<'a, I, T, F>(iter: I, is_nullable: bool, mut f: F) -> BoxSerializer<'a>
+where
+    I: Iterator<Item = Option<T>> + 'a,
+    T: 'a,
+    F: FnMut(T, &mut Vec<u8>) + 'a,
+{
+    Box::new(BufStreamingIterator::new(
+        iter,
+        move |x, buf| {
+            write_validity(is_nullable, x.is_some(), buf);
+            if let Some(x) = x {
+                f(x, buf)
+            }
+        },
+        vec![],
+    ))
+}
+
+fn primitive_serializer<'a, T, F>(
+    array: &'a dyn Array,
+    is_nullable: bool,
+    f: F,
+) -> BoxSerializer<'a>
+where
+    T: NativeType,
+    F: Fn(T, &mut Vec<u8>) + 'a,
+{
+    let array = array.as_any().downcast_ref::<PrimitiveArray<T>>().unwrap();
+    new_iterator(array.iter(), is_nullable, move |x, buf| f(*x, buf))
+}
+
+fn integer_serializer<'a, T: NativeType + Into<i64>>(
+    array: &'a dyn Array,
+    is_nullable: bool,
+) -> BoxSerializer<'a> {
+    primitive_serializer(array, is_nullable, |x: T, buf| zigzag_encode(x.into(), buf))
+}
+
+fn utf8_serializer<'a, O: Offset>(array: &'a dyn Array, is_nullable: bool) -> BoxSerializer<'a> {
+    let array = array.as_any().downcast_ref::<Utf8Array<O>>().unwrap();
+    new_iterator(array.iter(), is_nullable, |x, buf| {
+        encode_bytes(x.as_bytes(), buf)
+    })
+}
+
+fn binary_serializer<'a, O: Offset>(array: &'a dyn Array, is_nullable: bool) -> BoxSerializer<'a> {
+    let array = array.as_any().downcast_ref::<BinaryArray<O>>().unwrap();
+    new_iterator(array.iter(), is_nullable, encode_bytes)
+}
+
+/// Writes `value` as the big-endian two's-complement of Avro's `decimal`, with the minimal
+/// number of bytes.
+fn encode_decimal(value: i128, buf: &mut Vec<u8>) {
+    let bytes = value.to_be_bytes();
+    // redundant leading bytes are the ones whose sign is also the sign of the next byte
+    let start = (0..15)
+        .find(|&i| {
+            let (byte, next) = (bytes[i], bytes[i + 1] as i8);
+            !((byte == 0 && next >= 0) || (byte == 0xFF && next < 0))
+        })
+        .unwrap_or(15);
+    encode_bytes(&bytes[start..], buf)
+}
+
+/// Writes `length` items of `values` as blocks of an Avro array
+#[inline]
+fn write_items(values: &mut BoxSerializer, length: usize, buf: &mut Vec<u8>) {
+    if length > 0 {
+        zigzag_encode(length as i64, buf);
+        (0..length).for_each(|_| {
+            values.advance();
+            buf.extend_from_slice(values.get().unwrap());
+        });
+    }
+    zigzag_encode(0, buf);
+}
+
+#[inline]
+fn skip(values: &mut BoxSerializer, length: usize) {
+    (0..length).for_each(|_| values.advance());
+}
+
+fn list_serializer<'a, O: Offset>(
+    array: &'a dyn Array,
+    is_nullable: bool,
+) -> Result<BoxSerializer<'a>> {
+    let array = array.as_any().downcast_ref::<ListArray<O>>().unwrap();
+    let field = ListArray::<O>::get_child_field(array.data_type());
+    let offsets = array.offsets();
+    let mut values = new_serializer(array.values().as_ref(), field.is_nullable())?;
+    // the values before the first offset do not belong to this array
+    skip(&mut values, offsets[0].to_usize());
+
+    Ok(Box::new(BufStreamingIterator::new(
+        offsets.windows(2).enumerate(),
+        move |(i, window), buf| {
+            let length = (window[1] - window[0]).to_usize();
+            let is_valid = array.is_valid(i);
+            write_validity(is_nullable, is_valid, buf);
+            if is_valid {
+                write_items(&mut values, length, buf)
+            } else {
+                skip(&mut values, length)
+            }
+        },
+        vec![],
+    )))
+}
+
+fn struct_serializer<'a>(array: &'a dyn Array, is_nullable: bool) -> Result<BoxSerializer<'a>> {
+    let array = array.as_any().downcast_ref::<StructArray>().unwrap();
+    let mut fields = array
+        .fields()
+        .iter()
+        .zip(array.values())
+        .map(|(field, values)| new_serializer(values.as_ref(), field.is_nullable()))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Box::new(BufStreamingIterator::new(
+        0..array.len(),
+        move |i, buf| {
+            let is_valid = array.is_valid(i);
+            write_validity(is_nullable, is_valid, buf);
+            fields.iter_mut().for_each(|values| {
+                values.advance();
+                if is_valid {
+                    buf.extend_from_slice(values.get().unwrap())
+                }
+            })
+        },
+        vec![],
+    )))
+}
+
+fn map_serializer<'a>(array: &'a dyn Array, is_nullable: bool) -> Result<BoxSerializer<'a>> {
+    let array = array.as_any().downcast_ref::<MapArray>().unwrap();
+    let entries = array
+        .field()
+        .as_any()
+        .downcast_ref::<StructArray>()
+        .unwrap();
+    let value_field = &entries.fields()[1];
+    let mut keys = new_serializer(entries.values()[0].as_ref(), false)?;
+    let mut values = new_serializer(entries.values()[1].as_ref(), value_field.is_nullable())?;
+    let offsets = array.offsets();
+    skip(&mut keys, offsets[0] as usize);
+    skip(&mut values, offsets[0] as usize);
+
+    Ok(Box::new(BufStreamingIterator::new(
+        offsets.windows(2).enumerate(),
+        move |(i, window), buf| {
+            let length = (window[1] - window[0]) as usize;
+            let is_valid = array.is_valid(i);
+            write_validity(is_nullable, is_valid, buf);
+            if !is_valid {
+                skip(&mut keys, length);
+                skip(&mut values, length);
+                return;
+            }
+            if length > 0 {
+                zigzag_encode(length as i64, buf);
+                (0..length).for_each(|_| {
+                    keys.advance();
+                    buf.extend_from_slice(keys.get().unwrap());
+                    values.advance();
+                    buf.extend_from_slice(values.get().unwrap());
+                });
+            }
+            zigzag_encode(0, buf);
+        },
+        vec![],
+    )))
+}
+
+fn dictionary_serializer<'a, K: DictionaryKey>(
+    array: &'a dyn Array,
+    is_nullable: bool,
+) -> Result<BoxSerializer<'a>> {
+    let array = array.as_any().downcast_ref::<DictionaryArray<K>>().unwrap();
+    let values = array.values();
+
+    // the values are serialized once and copied for every key that references them
+    let has_nulls = values.null_count() > 0;
+    let mut serializer = new_serializer(values.as_ref(), has_nulls)?;
+    let mut serialized = Vec::with_capacity(values.len());
+    while let Some(value) = serializer.next() {
+        // a valid value of a nullable array is prefixed by its (one byte) union variant
+        let value = if has_nulls { &value[1..] } else { value };
+        serialized.push(value.to_vec());
+    }
+
+    let key = move |key: Option<&K>| {
+        key.map(|key| key.to_usize().unwrap())
+            .filter(|key| values.is_valid(*key))
+    };
+    if !is_nullable && array.keys().iter().any(|x| key(x).is_none()) {
+        return Err(ArrowError::InvalidArgumentError(
+            "A non-nullable dictionary can not have null values".to_string(),
+        ));
+    }
+    Ok(new_iterator(
+        array.keys().iter().map(key),
+        is_nullable,
+        move |key, buf| buf.extend_from_slice(&serialized[key]),
+    ))
+}
+
+/// Creates a [`StreamingIterator`] that yields the Avro (binary) encoding of each slot of
+/// `array`, whose schema is the one returned by [`to_avro_schema`](super::to_avro_schema)
+/// for a field of `array`'s [`DataType`] and nullability `is_nullable`.
+/// # Errors
+/// This function errors iff the [`DataType`] is not supported, or when `array` has nulls
+/// and `is_nullable` is false.
+pub fn new_serializer(array: &dyn Array, is_nullable: bool) -> Result<BoxSerializer> {
+    if !is_nullable && array.null_count() > 0 && array.data_type() != &DataType::Null {
+        return Err(ArrowError::InvalidArgumentError(
+            "A non-nullable field can not have null values".to_string(),
+        ));
+    }
+    use DataType::*;
+    Ok(match array.data_type().to_logical_type() {
+        Null => Box::new(BufStreamingIterator::new(0..array.len(), |_, _| {}, vec![])),
+        Boolean => {
+            let array = array.as_any().downcast_ref::<BooleanArray>().unwrap();
+            new_iterator(array.iter(), is_nullable, |x, buf| buf.push(x as u8))
+        }
+        Int8 => integer_serializer::<i8>(array, is_nullable),
+        Int16 => integer_serializer::<i16>(array, is_nullable),
+        Int32 | Date32 | Time32(TimeUnit::Millisecond) => {
+            integer_serializer::<i32>(array, is_nullable)
+        }
+        Int64
+        | Time64(TimeUnit::Microsecond)
+        | Timestamp(TimeUnit::Millisecond, _)
+        | Timestamp(TimeUnit::Microsecond, _) => integer_serializer::<i64>(array, is_nullable),
+        UInt8 => integer_serializer::<u8>(array, is_nullable),
+        UInt16 => integer_serializer::<u16>(array, is_nullable),
+        UInt32 => integer_serializer::<u32>(array, is_nullable),
+        Float32 => primitive_serializer(array, is_nullable, |x: f32, buf| {
+            buf.extend_from_slice(&x.to_le_bytes())
+        }),
+        Float64 => primitive_serializer(array, is_nullable, |x: f64, buf| {
+            buf.extend_from_slice(&x.to_le_bytes())
+        }),
+        Utf8 => utf8_serializer::<i32>(array, is_nullable),
+        LargeUtf8 => utf8_serializer::<i64>(array, is_nullable),
+        Binary => binary_serializer::<i32>(array, is_nullable),
+        LargeBinary => binary_serializer::<i64>(array, is_nullable),
+        FixedSizeBinary(_) => {
+            let array = array
+                .as_any()
+                .downcast_ref::<FixedSizeBinaryArray>()
+                .unwrap();
+            new_iterator(array.iter(), is_nullable, |x, buf| buf.extend_from_slice(x))
+        }
+        Decimal(_, _) => primitive_serializer(array, is_nullable, encode_decimal),
+        Interval(IntervalUnit::MonthDayNano) => {
+            // https://avro.apache.org/docs/current/spec.html#Duration
+            // 12 bytes, months, days, millis in LE
+            primitive_serializer(array, is_nullable, |x: months_days_ns, buf| {
+                buf.extend_from_slice(&x.months().to_le_bytes());
+                buf.extend_from_slice(&x.days().to_le_bytes());
+                buf.extend_from_slice(&((x.ns() / 1_000_000) as i32).to_le_bytes());
+            })
+        }
+        List(_) => list_serializer::<i32>(array, is_nullable)?,
+        LargeList(_) => list_serializer::<i64>(array, is_nullable)?,
+        Struct(_) => struct_serializer(array, is_nullable)?,
+        Map(_, _) => map_serializer(array, is_nullable)?,
+        Dictionary(key_type, _) => match_integer_type!(key_type, |$T| {
+            dictionary_serializer::<$T>(array, is_nullable)?
+        }),
+        other => {
+            return Err(ArrowError::NotYetImplemented(format!(
+                "Writing {:?} to Avro is not supported",
+                other
+            )))
+        }
+    })
+}
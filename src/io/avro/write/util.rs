@@ -0,0 +1,25 @@
+/// Encodes `value` in Avro's variable-length zig-zag encoding into `buf`
+#[inline]
+pub fn zigzag_encode(value: i64, buf: &mut Vec<u8>) {
+    encode_variable(((value << 1) ^ (value >> 63)) as u64, buf)
+}
+
+#[inline]
+fn encode_variable(mut z: u64, buf: &mut Vec<u8>) {
+    loop {
+        if z <= 0x7F {
+            buf.push((z & 0x7F) as u8);
+            break;
+        } else {
+            buf.push((0x80 | (z & 0x7F)) as u8);
+            z >>= 7;
+        }
+    }
+}
+
+/// Encodes `bytes` as Avro's `bytes` (and `string`): its length followed by its content
+#[inline]
+pub fn encode_bytes(bytes: &[u8], buf: &mut Vec<u8>) {
+    zigzag_encode(bytes.len() as i64, buf);
+    buf.extend_from_slice(bytes);
+}
@@ -0,0 +1,714 @@
+use std::collections::VecDeque;
+use std::io::{Cursor, Read};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+use crate::array::Array;
+use crate::datatypes::{DataType, Field, Schema};
+use crate::error::{ArrowError, Result};
+use crate::record_batch::{RecordBatch, RecordBatchReader};
+
+use super::super::read_utils::deserialize_batch_with;
+use super::{
+    deserialize_column, infer, infer_schema, infer_with_options, read_rows, ByteRecord,
+    DeserializeOptions, InferSchemaOptions, Reader, ReaderBuilder,
+};
+
+/// A function that deserializes a column of [`ByteRecord`]s into an [`Array`],
+/// e.g. [`deserialize_column`] with some [`DeserializeOptions`].
+/// Its arguments are the rows, the index of the column, its [`DataType`] and the row number
+/// of the first row.
+pub type ColumnDeserializer =
+    Arc<dyn Fn(&[ByteRecord], usize, DataType, usize) -> Result<Arc<dyn Array>> + Send + Sync>;
+
+/// A function that infers the [`DataType`] of a value, such as [`infer`].
+type Infer = Arc<dyn Fn(&[u8]) -> DataType + Send + Sync>;
+
+fn deserialize_column_lenient(
+    rows: &[ByteRecord],
+    column: usize,
+    data_type: DataType,
+    line_number: usize,
+) -> Result<Arc<dyn Array>> {
+    deserialize_column(
+        rows,
+        column,
+        data_type,
+        line_number,
+        &DeserializeOptions::default(),
+    )
+    .map(|x| x.0)
+}
+
+/// Builder of [`BatchReader`] and [`ParallelBatchReader`], readers of [`RecordBatch`]es from CSV.
+/// # Example
+/// ```
+/// use arrow2::io::csv::read::BatchReaderBuilder;
+/// use arrow2::record_batch::RecordBatchReader;
+///
+/// let data = "a,b\n1,x\n2,y\n";
+/// let reader = BatchReaderBuilder::new()
+///     .with_batch_size(1)
+///     .build(data.as_bytes())
+///     .unwrap();
+/// assert_eq!(reader.schema().fields().len(), 2);
+/// assert_eq!(reader.count(), 2);
+/// ```
+#[derive(Clone)]
+pub struct BatchReaderBuilder {
+    schema: Option<Arc<Schema>>,
+    infer: Infer,
+    max_infer_rows: Option<usize>,
+    has_header: bool,
+    delimiter: u8,
+    quote: u8,
+    batch_size: usize,
+    projection: Option<Vec<usize>>,
+    deserialize_column: ColumnDeserializer,
+    chunk_size: usize,
+}
+
+impl Default for BatchReaderBuilder {
+    fn default() -> Self {
+        Self {
+            schema: None,
+            infer: Arc::new(infer),
+            max_infer_rows: Some(1000),
+            has_header: true,
+            delimiter: b',',
+            quote: b'"',
+            batch_size: 1024,
+            projection: None,
+            deserialize_column: Arc::new(deserialize_column_lenient),
+            chunk_size: 4 * 1024 * 1024,
+        }
+    }
+}
+
+impl BatchReaderBuilder {
+    /// Returns a new [`BatchReaderBuilder`] with default options: the schema is inferred
+    /// from the first 1000 rows, the first row is a header, fields are delimited by `,`
+    /// and quoted by `"`, and batches have 1024 rows.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the [`Schema`] of the file, disabling schema inference.
+    pub fn with_schema(mut self, schema: Arc<Schema>) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    /// Sets the function used to infer the [`DataType`] of each value (defaults to [`infer`])
+    /// and the maximum number of rows used to infer the schema (`None` reads all rows).
+    pub fn with_inference<F>(mut self, infer: F, max_rows: Option<usize>) -> Self
+    where
+        F: Fn(&[u8]) -> DataType + Send + Sync + 'static,
+    {
+        self.infer = Arc::new(infer);
+        self.max_infer_rows = max_rows;
+        self
+    }
+
+    /// Sets whether the first row is a header (defaults to `true`).
+    pub fn has_header(mut self, has_header: bool) -> Self {
+        self.has_header = has_header;
+        self
+    }
+
+    /// Sets the field delimiter (defaults to `b','`).
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Sets the quote character (defaults to `b'"'`).
+    pub fn with_quote(mut self, quote: u8) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Sets the maximum number of rows of each [`RecordBatch`] (defaults to 1024).
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Sets the indices of the columns to read.
+    pub fn with_projection(mut self, projection: Vec<usize>) -> Self {
+        self.projection = Some(projection);
+        self
+    }
+
+    /// Sets the function used to deserialize columns (defaults to [`deserialize_column`] with
+    /// the default [`DeserializeOptions`]).
+    pub fn with_deserializer<F>(mut self, deserialize_column: F) -> Self
+    where
+        F: Fn(&[ByteRecord], usize, DataType, usize) -> Result<Arc<dyn Array>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.deserialize_column = Arc::new(deserialize_column);
+        self
+    }
+
+    /// Infers and deserializes values according to `options`, replacing the functions set by
+    /// [`Self::with_inference`] and [`Self::with_deserializer`].
+    /// Cells rejected with [`ParseMode::Permissive`](super::ParseMode::Permissive) are
+    /// deserialized as nulls but not reported;
+    /// use [`deserialize_batch`](super::deserialize_batch) for that.
+    pub fn with_options(mut self, options: DeserializeOptions) -> Self {
+        let options = Arc::new(options);
+        let infer_options = options.clone();
+        self.infer = Arc::new(move |bytes| infer_with_options(bytes, &infer_options));
+        self.deserialize_column = Arc::new(move |rows, column, data_type, line_number| {
+            deserialize_column(rows, column, data_type, line_number, &options).map(|x| x.0)
+        });
+        self
+    }
+
+    /// Sets the approximate number of bytes that each thread of a [`ParallelBatchReader`]
+    /// deserializes at a time (defaults to 4MiB).
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    fn infer_options(&self) -> InferSchemaOptions {
+        InferSchemaOptions {
+            max_rows: self.max_infer_rows,
+            has_header: self.has_header,
+            max_cardinality: None,
+        }
+    }
+
+    fn csv_builder(&self, has_header: bool) -> ReaderBuilder {
+        let mut builder = ReaderBuilder::new();
+        builder
+            .has_headers(has_header)
+            .delimiter(self.delimiter)
+            .quote(self.quote);
+        builder
+    }
+
+    fn projected_schema(&self, fields: &[Field]) -> Result<Schema> {
+        Ok(Schema::new(match &self.projection {
+            Some(projection) => projection
+                .iter()
+                .map(|i| {
+                    fields.get(*i).cloned().ok_or_else(|| {
+                        ArrowError::InvalidArgumentError(format!(
+                            "The projection index {} is out of bounds of a schema with {} fields",
+                            i,
+                            fields.len()
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?,
+            None => fields.to_vec(),
+        }))
+    }
+
+    /// Returns a [`BatchReader`] that reads `reader` sequentially.
+    /// When the schema is inferred, the rows read during inference are buffered,
+    /// so `reader` does not need to implement [`std::io::Seek`].
+    /// # Errors
+    /// This function errors if the header or the rows used for inference cannot be read,
+    /// or if the projection is out of bounds.
+    pub fn build<R: Read>(&self, reader: R) -> Result<BatchReader<R>> {
+        let mut reader = self.csv_builder(self.has_header).from_reader(reader);
+
+        let (fields, buffered) = match &self.schema {
+            Some(schema) => (schema.fields().clone(), vec![]),
+            None => {
+                let (schema, buffered) =
+                    infer_schema(&mut reader, &self.infer_options(), &self.infer.as_ref())?;
+                (schema.fields().clone(), buffered)
+            }
+        };
+        let schema = self.projected_schema(&fields)?;
+
+        Ok(BatchReader {
+            reader,
+            fields,
+            schema,
+            projection: self.projection.clone(),
+            deserialize_column: self.deserialize_column.clone(),
+            buffered: buffered.into_iter(),
+            rows: vec![ByteRecord::default(); self.batch_size],
+            line_number: 0,
+            finished: false,
+        })
+    }
+
+    /// Returns a [`ParallelBatchReader`] that reads `reader` in chunks of bytes and
+    /// deserializes up to `num_threads` chunks concurrently.
+    /// When the schema is inferred, it is inferred from the rows of the first chunk.
+    /// # Errors
+    /// This function errors if the first chunk cannot be read, or if the projection is out of bounds.
+    pub fn build_parallel<R: Read>(
+        &self,
+        reader: R,
+        num_threads: usize,
+    ) -> Result<ParallelBatchReader<R>> {
+        let mut chunks = ChunkReader {
+            reader,
+            quote: self.quote,
+            chunk_size: self.chunk_size,
+            remainder: vec![],
+            scan: Scan::default(),
+            eof: false,
+        };
+        let mut first = chunks.next_chunk()?.unwrap_or_default();
+
+        let fields = match &self.schema {
+            Some(schema) => schema.fields().clone(),
+            None => {
+                let mut reader = self
+                    .csv_builder(self.has_header)
+                    .from_reader(&first.bytes[..]);
+                infer_schema(&mut reader, &self.infer_options(), &self.infer.as_ref())?
+                    .0
+                    .fields()
+                    .clone()
+            }
+        };
+        let schema = self.projected_schema(&fields)?;
+
+        let mut line_number = 0;
+        if self.has_header {
+            let mut reader = self.csv_builder(true).from_reader(&first.bytes[..]);
+            reader.headers()?;
+            let header_end = reader.position().byte() as usize;
+            line_number = count_lines(&first.bytes[..header_end]);
+            first.bytes.drain(..header_end);
+            first.lines -= line_number;
+            if header_end > 0 {
+                first.rows = first.rows.saturating_sub(1);
+            }
+        }
+
+        let task = ChunkTask {
+            fields: Arc::new(fields),
+            projection: self.projection.clone(),
+            delimiter: self.delimiter,
+            quote: self.quote,
+            batch_size: self.batch_size,
+            deserialize_column: self.deserialize_column.clone(),
+        };
+        let num_threads = num_threads.max(1);
+        let (sender, jobs) = channel();
+        let (results, receiver) = channel();
+        let jobs = Arc::new(Mutex::new(jobs));
+        let workers = (0..num_threads)
+            .map(|_| {
+                let task = task.clone();
+                let jobs = jobs.clone();
+                let results = results.clone();
+                std::thread::spawn(move || task.run(&jobs, &results))
+            })
+            .collect();
+
+        Ok(ParallelBatchReader {
+            chunks,
+            first: Some(first),
+            schema,
+            sender: Some(sender),
+            receiver,
+            workers,
+            num_threads,
+            line_number,
+            row_number: 0,
+            pending: VecDeque::new(),
+        })
+    }
+}
+
+/// A [`RecordBatchReader`] that reads and deserializes CSV rows sequentially.
+/// Use [`BatchReaderBuilder`] to create it.
+pub struct BatchReader<R: Read> {
+    reader: Reader<R>,
+    fields: Vec<Field>,
+    schema: Schema,
+    projection: Option<Vec<usize>>,
+    deserialize_column: ColumnDeserializer,
+    buffered: std::vec::IntoIter<ByteRecord>,
+    rows: Vec<ByteRecord>,
+    line_number: usize,
+    finished: bool,
+}
+
+impl<R: Read> BatchReader<R> {
+    /// Returns the inner [`Reader`]
+    pub fn into_inner(self) -> Reader<R> {
+        self.reader
+    }
+}
+
+impl<R: Read> Iterator for BatchReader<R> {
+    type Item = Result<RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        // rows read during inference come first
+        let mut rows_read = 0;
+        for (row, buffered) in self.rows.iter_mut().zip(&mut self.buffered) {
+            *row = buffered;
+            rows_read += 1;
+        }
+        if rows_read < self.rows.len() {
+            match read_rows(&mut self.reader, 0, &mut self.rows[rows_read..]) {
+                Ok(read) => rows_read += read,
+                Err(error) => {
+                    self.finished = true;
+                    return Some(Err(error));
+                }
+            }
+        }
+        if rows_read == 0 {
+            self.finished = true;
+            return None;
+        }
+
+        let batch = deserialize_batch_with(
+            &self.rows[..rows_read],
+            &self.fields,
+            self.projection.as_deref(),
+            self.line_number,
+            self.deserialize_column.as_ref(),
+        );
+        self.line_number += rows_read;
+        Some(batch)
+    }
+}
+
+impl<R: Read> RecordBatchReader for BatchReader<R> {
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+}
+
+/// A chunk of bytes that ends on a record boundary.
+#[derive(Default)]
+struct Chunk {
+    bytes: Vec<u8>,
+    /// The number of line breaks in `bytes`
+    lines: usize,
+    /// The number of records in `bytes`
+    rows: usize,
+}
+
+/// The state of the search for record boundaries in the bytes that were read but not yet
+/// returned by [`ChunkReader`], so that they are not scanned again.
+#[derive(Default, Clone, Copy)]
+struct Scan {
+    /// The number of bytes scanned
+    position: usize,
+    is_quoted: bool,
+    /// Whether the current record has any bytes
+    in_record: bool,
+    /// The number of line breaks within the scanned bytes
+    lines: usize,
+    /// The number of records within the scanned bytes
+    rows: usize,
+}
+
+/// Splits a [`Read`] into chunks of bytes that end on record boundaries.
+/// Record boundaries are line breaks that are not within quotes. This assumes that quotes
+/// within quoted fields are escaped by doubling them (the CSV default).
+struct ChunkReader<R: Read> {
+    reader: R,
+    quote: u8,
+    chunk_size: usize,
+    remainder: Vec<u8>,
+    scan: Scan,
+    eof: bool,
+}
+
+impl<R: Read> ChunkReader<R> {
+    /// Returns the next chunk of at least `chunk_size` bytes (unless the end of the reader
+    /// was reached) that ends on a record boundary, or `None` when there are no more bytes.
+    fn next_chunk(&mut self) -> Result<Option<Chunk>> {
+        let mut bytes = std::mem::take(&mut self.remainder);
+        let mut scan = std::mem::take(&mut self.scan);
+        // the `Scan` at the last record boundary
+        let mut end = None;
+        loop {
+            if !self.eof {
+                let read = (&mut self.reader)
+                    .take(self.chunk_size as u64)
+                    .read_to_end(&mut bytes)?;
+                self.eof = read < self.chunk_size;
+            }
+
+            for byte in &bytes[scan.position..] {
+                scan.position += 1;
+                if *byte == self.quote {
+                    scan.is_quoted = !scan.is_quoted;
+                    scan.in_record = true;
+                } else if *byte == b'\n' {
+                    scan.lines += 1;
+                    if !scan.is_quoted {
+                        if scan.in_record {
+                            scan.rows += 1;
+                            scan.in_record = false;
+                        }
+                        end = Some(scan);
+                    }
+                } else if *byte != b'\r' {
+                    scan.in_record = true;
+                }
+            }
+
+            if self.eof {
+                if bytes.is_empty() {
+                    return Ok(None);
+                }
+                return Ok(Some(Chunk {
+                    bytes,
+                    lines: scan.lines,
+                    rows: scan.rows + scan.in_record as usize,
+                }));
+            }
+            if let Some(end) = end {
+                self.remainder = bytes.split_off(end.position);
+                self.scan = Scan {
+                    position: self.remainder.len(),
+                    lines: scan.lines - end.lines,
+                    rows: 0,
+                    ..scan
+                };
+                return Ok(Some(Chunk {
+                    bytes,
+                    lines: end.lines,
+                    rows: end.rows,
+                }));
+            }
+        }
+    }
+}
+
+#[inline]
+fn count_lines(bytes: &[u8]) -> usize {
+    bytes.iter().filter(|x| **x == b'\n').count()
+}
+
+/// A chunk to deserialize: its index within a round of [`ParallelBatchReader::read_chunks`],
+/// its bytes and the line and row numbers (0-based) of its first record.
+type Job = (usize, Vec<u8>, usize, usize);
+
+/// The state required to deserialize a chunk of bytes on a separate thread.
+#[derive(Clone)]
+struct ChunkTask {
+    fields: Arc<Vec<Field>>,
+    projection: Option<Vec<usize>>,
+    delimiter: u8,
+    quote: u8,
+    batch_size: usize,
+    deserialize_column: ColumnDeserializer,
+}
+
+impl ChunkTask {
+    /// Deserializes the [`Job`]s received from `jobs` and sends their batches to `results`,
+    /// until `jobs` is disconnected.
+    fn run(
+        &self,
+        jobs: &Mutex<Receiver<Job>>,
+        results: &Sender<(usize, Vec<Result<RecordBatch>>)>,
+    ) {
+        loop {
+            let job = match jobs.lock() {
+                Ok(jobs) => jobs.recv(),
+                Err(_) => return,
+            };
+            let (index, chunk, line_number, row_number) = match job {
+                Ok(job) => job,
+                Err(_) => return,
+            };
+            let batches = catch_unwind(AssertUnwindSafe(|| {
+                self.deserialize(&chunk, line_number, row_number)
+            }))
+            .unwrap_or_else(|_| {
+                vec![Err(ArrowError::Other(
+                    "A thread deserializing CSV panicked".to_string(),
+                ))]
+            });
+            if results.send((index, batches)).is_err() {
+                return;
+            }
+        }
+    }
+
+    /// Deserializes `chunk`, whose first record is on line `line_number` and is the row
+    /// `row_number` (both 0-based) of the file.
+    /// Deserialization stops at the first error, which is the last item.
+    fn deserialize(
+        &self,
+        chunk: &[u8],
+        line_number: usize,
+        row_number: usize,
+    ) -> Vec<Result<RecordBatch>> {
+        let mut batches = vec![];
+        if let Err(e) = self.deserialize_into(chunk, line_number, row_number, &mut batches) {
+            batches.push(Err(e));
+        }
+        batches
+    }
+
+    fn deserialize_into(
+        &self,
+        chunk: &[u8],
+        line_number: usize,
+        row_number: usize,
+        batches: &mut Vec<Result<RecordBatch>>,
+    ) -> Result<()> {
+        let mut reader = ReaderBuilder::new()
+            .has_headers(false)
+            .delimiter(self.delimiter)
+            .quote(self.quote)
+            .from_reader(Cursor::new(chunk));
+
+        // so that the line numbers of the records (and errors) are the ones in the file
+        let mut position = csv::Position::new();
+        position.set_line(line_number as u64 + 1);
+        reader.seek(position)?;
+
+        let mut rows = vec![ByteRecord::default(); self.batch_size];
+        let mut row_number = row_number;
+        loop {
+            let rows_read = read_rows(&mut reader, 0, &mut rows)?;
+            if rows_read == 0 {
+                return Ok(());
+            }
+            batches.push(Ok(deserialize_batch_with(
+                &rows[..rows_read],
+                &self.fields,
+                self.projection.as_deref(),
+                row_number,
+                self.deserialize_column.as_ref(),
+            )?));
+            row_number += rows_read;
+        }
+    }
+}
+
+/// A [`RecordBatchReader`] that splits a CSV into chunks of bytes on record boundaries and
+/// deserializes them concurrently on `num_threads` threads. Batches are returned in the
+/// order of the rows in the file. Use [`BatchReaderBuilder::build_parallel`] to create it.
+///
+/// Reading is performed on the calling thread; only deserialization happens on other threads.
+/// Record boundaries are found by tracking quotes, which requires quotes within
+/// quoted fields to be escaped by doubling them (the CSV default).
+pub struct ParallelBatchReader<R: Read> {
+    chunks: ChunkReader<R>,
+    first: Option<Chunk>,
+    schema: Schema,
+    sender: Option<Sender<Job>>,
+    receiver: Receiver<(usize, Vec<Result<RecordBatch>>)>,
+    workers: Vec<JoinHandle<()>>,
+    num_threads: usize,
+    line_number: usize,
+    row_number: usize,
+    pending: VecDeque<Result<RecordBatch>>,
+}
+
+impl<R: Read> ParallelBatchReader<R> {
+    fn next_chunk(&mut self) -> Result<Option<Chunk>> {
+        match self.first.take() {
+            Some(first) => Ok(Some(first)),
+            None => self.chunks.next_chunk(),
+        }
+    }
+
+    /// Reads up to `num_threads` chunks and deserializes them concurrently into `pending`.
+    /// Returns `false` when there is nothing left to read.
+    fn read_chunks(&mut self) -> bool {
+        let mut chunks = vec![];
+        let mut error = None;
+        while chunks.len() < self.num_threads {
+            match self.next_chunk() {
+                Ok(Some(chunk)) => chunks.push(chunk),
+                Ok(None) => break,
+                Err(e) => {
+                    // stop reading after an error
+                    self.chunks.eof = true;
+                    self.chunks.remainder.clear();
+                    self.chunks.scan = Scan::default();
+                    error = Some(e);
+                    break;
+                }
+            }
+        }
+        if chunks.is_empty() && error.is_none() {
+            return false;
+        }
+
+        let num_chunks = chunks.len();
+        // `sender` is only `None` after `drop`
+        let sender = self.sender.as_ref().unwrap();
+        for (index, chunk) in chunks.into_iter().enumerate() {
+            let job = (index, chunk.bytes, self.line_number, self.row_number);
+            self.line_number += chunk.lines;
+            self.row_number += chunk.rows;
+            // the workers only stop once `sender` is dropped
+            sender.send(job).unwrap();
+        }
+
+        let mut batches = (0..num_chunks).map(|_| None).collect::<Vec<_>>();
+        for _ in 0..num_chunks {
+            match self.receiver.recv() {
+                Ok((index, chunk_batches)) => batches[index] = Some(chunk_batches),
+                Err(_) => break,
+            }
+        }
+        for chunk_batches in batches {
+            match chunk_batches {
+                Some(chunk_batches) => self.pending.extend(chunk_batches),
+                None => self.pending.push_back(Err(ArrowError::Other(
+                    "A thread deserializing CSV panicked".to_string(),
+                ))),
+            }
+        }
+        if let Some(e) = error {
+            self.pending.push_back(Err(e));
+        }
+        true
+    }
+}
+
+impl<R: Read> Drop for ParallelBatchReader<R> {
+    fn drop(&mut self) {
+        // disconnecting the jobs stops the workers
+        self.sender.take();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+impl<R: Read> Iterator for ParallelBatchReader<R> {
+    type Item = Result<RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(batch) = self.pending.pop_front() {
+                return Some(batch);
+            }
+            if !self.read_chunks() {
+                return None;
+            }
+        }
+    }
+}
+
+impl<R: Read> RecordBatchReader for ParallelBatchReader<R> {
+    fn schema(&self) -> &Schema {
+        &self.schema
+    }
+}
@@ -1,4 +1,5 @@
 //! APIs to read from CSV
+mod batch_reader;
 mod deserialize;
 mod reader;
 
@@ -7,7 +8,10 @@ pub use csv::{ByteRecord, Reader, ReaderBuilder};
 
 mod infer_schema;
 
-pub use super::utils::infer;
-pub use deserialize::{deserialize_batch, deserialize_column};
+pub use super::read_utils::{
+    deserialize_batch, deserialize_column, DeserializeOptions, ParseMode, RejectedCell,
+};
+pub use super::utils::{infer, infer_with_options, InferSchemaOptions};
+pub use batch_reader::{BatchReader, BatchReaderBuilder, ColumnDeserializer, ParallelBatchReader};
 pub use infer_schema::infer_schema;
 pub use reader::*;
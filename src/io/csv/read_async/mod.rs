@@ -7,8 +7,10 @@ mod deserialize;
 mod infer_schema;
 mod reader;
 
-pub use super::utils::infer;
-pub use deserialize::{deserialize_batch, deserialize_column};
+pub use super::read_utils::{
+    deserialize_batch, deserialize_column, DeserializeOptions, ParseMode, RejectedCell,
+};
+pub use super::utils::{infer, infer_with_options, InferSchemaOptions};
 pub use infer_schema::infer_schema;
 pub use reader::*;
 
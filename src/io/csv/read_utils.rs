@@ -1,12 +1,203 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::sync::Arc;
 
-use chrono::Datelike;
+use chrono::{Datelike, Timelike};
 
 // Ideally this trait should not be needed and both `csv` and `csv_async` crates would share
 // the same `ByteRecord` struct. Unfortunately, they do not and thus we must use generics
 // over this trait and materialize the generics for each struct.
-pub(crate) trait ByteRecordGeneric {
+/// A record of a CSV file, implemented by the `ByteRecord` of both `csv` and `csv_async`.
+pub trait ByteRecordGeneric {
+    /// The bytes of the field at `index`, if any
     fn get(&self, index: usize) -> Option<&[u8]>;
+
+    /// The (1-based) line number of the record in the file, when known
+    fn line(&self) -> Option<u64>;
+}
+
+/// A cell that could not be deserialized to the [`DataType`] of its column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RejectedCell {
+    /// The line number of the record containing the cell
+    pub line_number: usize,
+    /// The index of the column of the cell
+    pub column: usize,
+    /// The bytes of the cell
+    pub bytes: Vec<u8>,
+}
+
+impl std::fmt::Display for RejectedCell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {:?}",
+            self.line_number,
+            self.column,
+            String::from_utf8_lossy(&self.bytes)
+        )
+    }
+}
+
+/// How cells that cannot be deserialized are handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseMode {
+    /// They are deserialized as nulls
+    Lenient,
+    /// The first one is returned as an error
+    Strict,
+    /// They are deserialized as nulls and returned as [`RejectedCell`]s
+    Permissive,
+}
+
+impl Default for ParseMode {
+    fn default() -> Self {
+        Self::Lenient
+    }
+}
+
+/// Options used to infer and deserialize the values of a CSV file.
+///
+/// Chrono format strings (see [`chrono::format::strftime`]) are used to parse
+/// [`DataType::Date32`] and [`DataType::Date64`] (`date_format`), [`DataType::Time32`] and
+/// [`DataType::Time64`] (`time_format`) and [`DataType::Timestamp`] (`datetime_format`).
+/// `column_formats` overrides them for specific columns (by index).
+///
+/// `null_values` are matched exactly; `true_values` and `false_values` are matched ASCII
+/// case-insensitively.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeserializeOptions {
+    /// Values deserialized as null (in addition to empty cells of non-string columns)
+    pub null_values: Vec<String>,
+    /// Values deserialized as `true`
+    pub true_values: Vec<String>,
+    /// Values deserialized as `false`
+    pub false_values: Vec<String>,
+    /// Format of dates. When `None`, dates are parsed as `%Y-%m-%d`.
+    pub date_format: Option<String>,
+    /// Format of times. When `None`, times are parsed as `%H:%M:%S%.f`.
+    pub time_format: Option<String>,
+    /// Format of timestamps. When `None`, timestamps without timezone are parsed as
+    /// `%Y-%m-%dT%H:%M:%S%.f` and timestamps with timezone as RFC3339.
+    pub datetime_format: Option<String>,
+    /// Formats of specific columns, overriding the formats above
+    pub column_formats: HashMap<usize, String>,
+    /// The character separating the integer and fractional parts of decimals and floats
+    pub decimal_separator: u8,
+    /// How cells that cannot be deserialized are handled
+    pub mode: ParseMode,
+}
+
+impl Default for DeserializeOptions {
+    fn default() -> Self {
+        Self {
+            null_values: vec![],
+            true_values: vec!["true".to_string()],
+            false_values: vec!["false".to_string()],
+            date_format: None,
+            time_format: None,
+            datetime_format: None,
+            column_formats: HashMap::new(),
+            decimal_separator: b'.',
+            mode: ParseMode::Lenient,
+        }
+    }
+}
+
+impl DeserializeOptions {
+    /// Whether `bytes` is one of the null values
+    #[inline]
+    pub(crate) fn is_null(&self, bytes: &[u8]) -> bool {
+        self.null_values.iter().any(|x| x.as_bytes() == bytes)
+    }
+
+    #[inline]
+    pub(crate) fn parse_boolean(&self, bytes: &[u8]) -> Option<bool> {
+        if self
+            .true_values
+            .iter()
+            .any(|x| x.as_bytes().eq_ignore_ascii_case(bytes))
+        {
+            Some(true)
+        } else if self
+            .false_values
+            .iter()
+            .any(|x| x.as_bytes().eq_ignore_ascii_case(bytes))
+        {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `bytes` with the decimal separator replaced by `.`
+    #[inline]
+    pub(crate) fn normalize_decimal<'a>(&self, bytes: &'a [u8]) -> Cow<'a, [u8]> {
+        if self.decimal_separator == b'.' || !bytes.contains(&self.decimal_separator) {
+            Cow::Borrowed(bytes)
+        } else {
+            Cow::Owned(
+                bytes
+                    .iter()
+                    .map(|x| {
+                        if *x == self.decimal_separator {
+                            b'.'
+                        } else {
+                            *x
+                        }
+                    })
+                    .collect(),
+            )
+        }
+    }
+
+    /// The format of `column` of [`DataType`] `data_type`, if any
+    pub(crate) fn format(&self, column: Option<usize>, data_type: &DataType) -> Option<&str> {
+        if let Some(format) = column.and_then(|column| self.column_formats.get(&column)) {
+            return Some(format.as_str());
+        }
+        match data_type {
+            DataType::Date32 | DataType::Date64 => self.date_format.as_deref(),
+            DataType::Time32(_) | DataType::Time64(_) => self.time_format.as_deref(),
+            DataType::Timestamp(_, _) => self.datetime_format.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// The state of the deserialization of a column
+struct ColumnState<'a> {
+    options: &'a DeserializeOptions,
+    line_number: usize,
+    column: usize,
+    rejected: &'a mut Vec<RejectedCell>,
+}
+
+impl<'a> ColumnState<'a> {
+    #[inline]
+    fn is_null(&self, bytes: &[u8]) -> bool {
+        bytes.is_empty() || self.options.is_null(bytes)
+    }
+
+    #[inline]
+    fn reject<B: ByteRecordGeneric>(&mut self, index: usize, row: &B, bytes: &[u8]) {
+        match self.options.mode {
+            ParseMode::Lenient => {}
+            ParseMode::Strict if !self.rejected.is_empty() => {}
+            _ => {
+                let line_number = row
+                    .line()
+                    .map(|x| x as usize)
+                    .unwrap_or(self.line_number + index);
+                self.rejected.push(RejectedCell {
+                    line_number,
+                    column: self.column,
+                    bytes: bytes.to_vec(),
+                })
+            }
+        }
+    }
 }
 
 use crate::{
@@ -15,30 +206,38 @@ use crate::{
     error::{ArrowError, Result},
     record_batch::RecordBatch,
     temporal_conversions,
-    types::{NativeType, NaturalDataType},
+    types::{days_ms, months_days_ns, NativeType, NaturalDataType},
 };
 
-use super::utils::RFC3339;
+use super::utils::{parse_date, parse_naive_datetime, parse_time, RFC3339};
 
 fn deserialize_primitive<T, B: ByteRecordGeneric, F>(
     rows: &[B],
-    column: usize,
+    state: &mut ColumnState,
     datatype: DataType,
     op: F,
 ) -> Arc<dyn Array>
 where
-    T: NativeType + NaturalDataType + lexical_core::FromLexical,
+    T: NativeType + NaturalDataType,
     F: Fn(&[u8]) -> Option<T>,
 {
-    let iter = rows.iter().map(|row| match row.get(column) {
-        Some(bytes) => {
-            if bytes.is_empty() {
-                return None;
+    let column = state.column;
+    let iter = rows
+        .iter()
+        .enumerate()
+        .map(|(index, row)| match row.get(column) {
+            Some(bytes) => {
+                if state.is_null(bytes) {
+                    return None;
+                }
+                let value = op(bytes);
+                if value.is_none() {
+                    state.reject(index, row, bytes);
+                }
+                value
             }
-            op(bytes)
-        }
-        None => None,
-    });
+            None => None,
+        });
     Arc::new(PrimitiveArray::<T>::from_trusted_len_iter(iter).to(datatype))
 }
 
@@ -86,44 +285,268 @@ fn deserialize_decimal(bytes: &[u8], precision: usize, scale: usize) -> Option<i
     }
 }
 
-fn deserialize_boolean<B, F>(rows: &[B], column: usize, op: F) -> Arc<dyn Array>
+fn deserialize_boolean<B, F>(rows: &[B], state: &mut ColumnState, op: F) -> Arc<dyn Array>
 where
     B: ByteRecordGeneric,
     F: Fn(&[u8]) -> Option<bool>,
 {
-    let iter = rows.iter().map(|row| match row.get(column) {
-        Some(bytes) => {
-            if bytes.is_empty() {
-                return None;
+    let column = state.column;
+    let iter = rows
+        .iter()
+        .enumerate()
+        .map(|(index, row)| match row.get(column) {
+            Some(bytes) => {
+                if state.is_null(bytes) {
+                    return None;
+                }
+                let value = op(bytes);
+                if value.is_none() {
+                    state.reject(index, row, bytes);
+                }
+                value
             }
-            op(bytes)
-        }
-        None => None,
-    });
+            None => None,
+        });
     Arc::new(BooleanArray::from_trusted_len_iter(iter))
 }
 
-fn deserialize_utf8<O: Offset, B: ByteRecordGeneric>(rows: &[B], column: usize) -> Arc<dyn Array> {
-    let iter = rows.iter().map(|row| match row.get(column) {
-        Some(bytes) => simdutf8::basic::from_utf8(bytes).ok(),
-        None => None,
-    });
+fn deserialize_utf8<O: Offset, B: ByteRecordGeneric>(
+    rows: &[B],
+    state: &mut ColumnState,
+) -> Arc<dyn Array> {
+    let column = state.column;
+    let iter = rows
+        .iter()
+        .enumerate()
+        .map(|(index, row)| match row.get(column) {
+            Some(bytes) => {
+                if state.options.is_null(bytes) {
+                    return None;
+                }
+                let value = simdutf8::basic::from_utf8(bytes).ok();
+                if value.is_none() {
+                    state.reject(index, row, bytes);
+                }
+                value
+            }
+            None => None,
+        });
     Arc::new(Utf8Array::<O>::from_trusted_len_iter(iter))
 }
 
 fn deserialize_binary<O: Offset, B: ByteRecordGeneric>(
     rows: &[B],
-    column: usize,
+    state: &mut ColumnState,
 ) -> Arc<dyn Array> {
-    let iter = rows.iter().map(|row| row.get(column));
+    let column = state.column;
+    let iter = rows.iter().map(|row| {
+        row.get(column)
+            .filter(|bytes| !state.options.is_null(bytes))
+    });
     Arc::new(BinaryArray::<O>::from_trusted_len_iter(iter))
 }
 
+fn deserialize_fixed_size_binary<B: ByteRecordGeneric>(
+    rows: &[B],
+    state: &mut ColumnState,
+    size: usize,
+) -> Arc<dyn Array> {
+    let column = state.column;
+    let mut array = MutableFixedSizeBinaryArray::with_capacity(size, rows.len());
+    for (index, row) in rows.iter().enumerate() {
+        let value = match row.get(column) {
+            Some(bytes) if !state.is_null(bytes) => {
+                if bytes.len() == size {
+                    Some(bytes)
+                } else {
+                    state.reject(index, row, bytes);
+                    None
+                }
+            }
+            _ => None,
+        };
+        array.push(value);
+    }
+    Arc::new(FixedSizeBinaryArray::from(array))
+}
+
+fn deserialize_utf8_dictionary<K: DictionaryKey, O: Offset, B: ByteRecordGeneric>(
+    rows: &[B],
+    state: &mut ColumnState,
+) -> Result<Arc<dyn Array>> {
+    let column = state.column;
+    let mut array = MutableDictionaryArray::<K, MutableUtf8Array<O>>::new();
+    for (index, row) in rows.iter().enumerate() {
+        let value = match row.get(column) {
+            Some(bytes) if !state.options.is_null(bytes) => {
+                let value = simdutf8::basic::from_utf8(bytes).ok();
+                if value.is_none() {
+                    state.reject(index, row, bytes);
+                }
+                value
+            }
+            _ => None,
+        };
+        array.try_extend(std::iter::once(value))?;
+    }
+    Ok(array.into_arc())
+}
+
+fn deserialize_binary_dictionary<K: DictionaryKey, O: Offset, B: ByteRecordGeneric>(
+    rows: &[B],
+    state: &mut ColumnState,
+) -> Result<Arc<dyn Array>> {
+    let column = state.column;
+    let mut array = MutableDictionaryArray::<K, MutableBinaryArray<O>>::new();
+    let iter = rows.iter().map(|row| {
+        row.get(column)
+            .filter(|bytes| !state.options.is_null(bytes))
+    });
+    array.try_extend(iter)?;
+    Ok(array.into_arc())
+}
+
+fn deserialize_dictionary<K: DictionaryKey, B: ByteRecordGeneric>(
+    rows: &[B],
+    state: &mut ColumnState,
+    values: &DataType,
+) -> Result<Arc<dyn Array>> {
+    match values.to_logical_type() {
+        DataType::Utf8 => deserialize_utf8_dictionary::<K, i32, _>(rows, state),
+        DataType::LargeUtf8 => deserialize_utf8_dictionary::<K, i64, _>(rows, state),
+        DataType::Binary => deserialize_binary_dictionary::<K, i32, _>(rows, state),
+        DataType::LargeBinary => deserialize_binary_dictionary::<K, i64, _>(rows, state),
+        other => Err(ArrowError::NotYetImplemented(format!(
+            "Deserializing dictionaries of type \"{:?}\" is not implemented",
+            other
+        ))),
+    }
+}
+
+/// Parses `number` (digits with an optional fraction separated by `.` or `,`) multiplied by `scale`
+#[inline]
+fn parse_scaled(number: &str, scale: i64) -> Option<i64> {
+    let (integer, fraction) = match number.find(|c| c == '.' || c == ',') {
+        Some(i) => (&number[..i], &number[i + 1..]),
+        None => (number, ""),
+    };
+    if integer.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    let integer = if integer.is_empty() {
+        0
+    } else {
+        integer.parse::<i64>().ok()?
+    };
+    let mut value = integer.checked_mul(scale)?;
+    let mut digit_scale = scale;
+    for digit in fraction.bytes() {
+        if !digit.is_ascii_digit() {
+            return None;
+        }
+        digit_scale /= 10;
+        value = value.checked_add((digit - b'0') as i64 * digit_scale)?;
+    }
+    Some(value)
+}
+
+/// Splits an optional leading `-` from `string`, returning the sign and the remaining string
 #[inline]
-fn deserialize_datetime<T: chrono::TimeZone>(string: &str, tz: &T) -> Option<i64> {
+fn split_sign(string: &str) -> (i64, &str) {
+    match string.strip_prefix('-') {
+        Some(string) => (-1, string),
+        None => (1, string),
+    }
+}
+
+/// Parses an ISO 8601 duration (e.g. `P1Y2M10DT2H30M1.5S`, `-P3W` or `P1M-2D`) into
+/// its number of months, days and nanoseconds. Components may be individually negative.
+fn parse_iso8601_duration(string: &str) -> Option<(i32, i32, i64)> {
+    let (negative, string) = match string.strip_prefix('-') {
+        Some(string) => (true, string),
+        None => (false, string.strip_prefix('+').unwrap_or(string)),
+    };
+    let string = string.strip_prefix('P')?;
+    let (date, time) = match string.find('T') {
+        Some(i) => (&string[..i], Some(&string[i + 1..])),
+        None => (string, None),
+    };
+    if date.is_empty() && time.map(|x| x.is_empty()).unwrap_or(true) {
+        return None;
+    }
+
+    let mut months = 0i64;
+    let mut days = 0i64;
+    let mut rest = date;
+    while !rest.is_empty() {
+        let (sign, component) = split_sign(rest);
+        let end = component.find(|c: char| !c.is_ascii_digit())?;
+        let value = sign * component[..end].parse::<i64>().ok()?;
+        match component.as_bytes()[end] {
+            b'Y' => months = months.checked_add(value.checked_mul(12)?)?,
+            b'M' => months = months.checked_add(value)?,
+            b'W' => days = days.checked_add(value.checked_mul(7)?)?,
+            b'D' => days = days.checked_add(value)?,
+            _ => return None,
+        }
+        rest = &component[end + 1..];
+    }
+
+    let mut nanoseconds = 0i64;
+    let mut rest = time.unwrap_or("");
+    while !rest.is_empty() {
+        let (sign, component) = split_sign(rest);
+        let end = component.find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ','))?;
+        let scale = match component.as_bytes()[end] {
+            b'H' => 3_600_000_000_000,
+            b'M' => 60_000_000_000,
+            b'S' => 1_000_000_000,
+            _ => return None,
+        };
+        nanoseconds = nanoseconds.checked_add(sign * parse_scaled(&component[..end], scale)?)?;
+        rest = &component[end + 1..];
+    }
+
+    if negative {
+        months = -months;
+        days = -days;
+        nanoseconds = -nanoseconds;
+    }
+    Some((
+        i32::try_from(months).ok()?,
+        i32::try_from(days).ok()?,
+        nanoseconds,
+    ))
+}
+
+#[inline]
+fn deserialize_duration(bytes: &[u8], time_unit: TimeUnit) -> Option<i64> {
+    if let Ok(value) = lexical_core::parse::<i64>(bytes) {
+        return Some(value);
+    }
+    let (months, days, nanoseconds) =
+        parse_iso8601_duration(simdutf8::basic::from_utf8(bytes).ok()?)?;
+    if months != 0 {
+        // the length of a month is not fixed
+        return None;
+    }
+    let nanoseconds = (days as i64)
+        .checked_mul(86_400_000_000_000)?
+        .checked_add(nanoseconds)?;
+    Some(to_time_unit(nanoseconds, time_unit))
+}
+
+#[inline]
+fn deserialize_interval(bytes: &[u8]) -> Option<(i32, i32, i64)> {
+    parse_iso8601_duration(simdutf8::basic::from_utf8(bytes).ok()?)
+}
+
+#[inline]
+fn deserialize_datetime<T: chrono::TimeZone>(string: &str, tz: &T, format: &str) -> Option<i64> {
     let mut parsed = chrono::format::Parsed::new();
-    let fmt = chrono::format::StrftimeItems::new(RFC3339);
-    if chrono::format::parse(&mut parsed, string, fmt).is_ok() {
+    let fmt = chrono::format::StrftimeItems::new(format);
+    chrono::format::parse(&mut parsed, string, fmt).ok()?;
+    if parsed.offset.is_some() {
         parsed
             .to_datetime()
             .map(|x| x.naive_utc())
@@ -131,119 +554,208 @@ fn deserialize_datetime<T: chrono::TimeZone>(string: &str, tz: &T) -> Option<i64
             .map(|x| x.timestamp_nanos())
             .ok()
     } else {
-        None
+        // no offset in the value: it is in the timezone of the column
+        parsed
+            .to_naive_datetime_with_offset(0)
+            .ok()
+            .and_then(|x| tz.from_local_datetime(&x).single())
+            .map(|x| x.timestamp_nanos())
     }
 }
 
-/// Deserializes `column` of `rows` into an [`Array`] of [`DataType`] `datatype`.
-pub(crate) fn deserialize_column<B: ByteRecordGeneric>(
+#[inline]
+fn to_time_unit(nanoseconds: i64, time_unit: TimeUnit) -> i64 {
+    match time_unit {
+        TimeUnit::Second => nanoseconds / 1_000_000_000,
+        TimeUnit::Millisecond => nanoseconds / 1_000_000,
+        TimeUnit::Microsecond => nanoseconds / 1_000,
+        TimeUnit::Nanosecond => nanoseconds,
+    }
+}
+
+#[inline]
+fn time_to_nanoseconds(time: chrono::NaiveTime) -> i64 {
+    time.num_seconds_from_midnight() as i64 * 1_000_000_000 + time.nanosecond() as i64
+}
+
+/// Deserializes `column` of `rows` into an [`Array`] of [`DataType`] `datatype` using `options`.
+/// `line_number` is the line number of the first row, used when the rows do not know their position.
+///
+/// Cells that cannot be deserialized are handled according to `options.mode` (see [`ParseMode`]):
+/// they are deserialized as nulls and, with [`ParseMode::Permissive`], returned alongside the array.
+/// # Errors
+/// With [`ParseMode::Strict`], this function errors on the first non-empty cell that cannot be
+/// deserialized, naming its line number and contents.
+pub fn deserialize_column<B: ByteRecordGeneric>(
     rows: &[B],
     column: usize,
     datatype: DataType,
-    _line_number: usize,
+    line_number: usize,
+    options: &DeserializeOptions,
+) -> Result<(Arc<dyn Array>, Vec<RejectedCell>)> {
+    let mut rejected = vec![];
+    let array =
+        deserialize_column_into(rows, column, datatype, line_number, options, &mut rejected)?;
+    Ok((array, rejected))
+}
+
+/// Deserializes `column` of `rows` like [`deserialize_column`], pushing the rejected cells to `rejected`.
+fn deserialize_column_into<B: ByteRecordGeneric>(
+    rows: &[B],
+    column: usize,
+    datatype: DataType,
+    line_number: usize,
+    options: &DeserializeOptions,
+    rejected: &mut Vec<RejectedCell>,
+) -> Result<Arc<dyn Array>> {
+    let mut state = ColumnState {
+        options,
+        line_number,
+        column,
+        rejected,
+    };
+    let array = deserialize_column_impl(rows, &mut state, datatype.clone())?;
+
+    if options.mode == ParseMode::Strict {
+        if let Some(cell) = state.rejected.pop() {
+            return Err(ArrowError::ExternalFormat(format!(
+                "Could not deserialize {:?} at line {} (column {}) as {:?}",
+                String::from_utf8_lossy(&cell.bytes),
+                cell.line_number,
+                cell.column,
+                datatype
+            )));
+        }
+    }
+    Ok(array)
+}
+
+fn deserialize_column_impl<B: ByteRecordGeneric>(
+    rows: &[B],
+    state: &mut ColumnState,
+    datatype: DataType,
 ) -> Result<Arc<dyn Array>> {
     use DataType::*;
+    let options = state.options;
+    let format = options.format(Some(state.column), &datatype);
     Ok(match datatype {
-        Boolean => deserialize_boolean(rows, column, |bytes| {
-            if bytes.eq_ignore_ascii_case(b"false") {
-                Some(false)
-            } else if bytes.eq_ignore_ascii_case(b"true") {
-                Some(true)
-            } else {
-                None
-            }
-        }),
-        Int8 => deserialize_primitive(rows, column, datatype, |bytes| {
+        Boolean => deserialize_boolean(rows, state, |bytes| options.parse_boolean(bytes)),
+        Int8 => deserialize_primitive(rows, state, datatype, |bytes| {
             lexical_core::parse::<i8>(bytes).ok()
         }),
-        Int16 => deserialize_primitive(rows, column, datatype, |bytes| {
+        Int16 => deserialize_primitive(rows, state, datatype, |bytes| {
             lexical_core::parse::<i16>(bytes).ok()
         }),
-        Int32 => deserialize_primitive(rows, column, datatype, |bytes| {
+        Int32 => deserialize_primitive(rows, state, datatype, |bytes| {
             lexical_core::parse::<i32>(bytes).ok()
         }),
-        Int64 => deserialize_primitive(rows, column, datatype, |bytes| {
+        Int64 => deserialize_primitive(rows, state, datatype, |bytes| {
             lexical_core::parse::<i64>(bytes).ok()
         }),
-        UInt8 => deserialize_primitive(rows, column, datatype, |bytes| {
+        UInt8 => deserialize_primitive(rows, state, datatype, |bytes| {
             lexical_core::parse::<u8>(bytes).ok()
         }),
-        UInt16 => deserialize_primitive(rows, column, datatype, |bytes| {
+        UInt16 => deserialize_primitive(rows, state, datatype, |bytes| {
             lexical_core::parse::<u16>(bytes).ok()
         }),
-        UInt32 => deserialize_primitive(rows, column, datatype, |bytes| {
+        UInt32 => deserialize_primitive(rows, state, datatype, |bytes| {
             lexical_core::parse::<u32>(bytes).ok()
         }),
-        UInt64 => deserialize_primitive(rows, column, datatype, |bytes| {
+        UInt64 => deserialize_primitive(rows, state, datatype, |bytes| {
             lexical_core::parse::<u64>(bytes).ok()
         }),
-        Float32 => deserialize_primitive(rows, column, datatype, |bytes| {
-            lexical_core::parse::<f32>(bytes).ok()
+        Float32 => deserialize_primitive(rows, state, datatype, |bytes| {
+            lexical_core::parse::<f32>(&options.normalize_decimal(bytes)).ok()
         }),
-        Float64 => deserialize_primitive(rows, column, datatype, |bytes| {
-            lexical_core::parse::<f64>(bytes).ok()
+        Float64 => deserialize_primitive(rows, state, datatype, |bytes| {
+            lexical_core::parse::<f64>(&options.normalize_decimal(bytes)).ok()
         }),
-        Date32 => deserialize_primitive(rows, column, datatype, |bytes| {
+        Date32 => deserialize_primitive(rows, state, datatype, |bytes| {
             simdutf8::basic::from_utf8(bytes)
                 .ok()
-                .and_then(|x| x.parse::<chrono::NaiveDate>().ok())
+                .and_then(|x| parse_date(x, format))
                 .map(|x| x.num_days_from_ce() - temporal_conversions::EPOCH_DAYS_FROM_CE)
         }),
-        Date64 => deserialize_primitive(rows, column, datatype, |bytes| {
+        Date64 => deserialize_primitive(rows, state, datatype, |bytes| {
+            let string = simdutf8::basic::from_utf8(bytes).ok()?;
+            let datetime = match format {
+                // a custom format may either contain a time or not
+                Some(_) => parse_naive_datetime(string, format)
+                    .or_else(|| parse_date(string, format).map(|x| x.and_hms(0, 0, 0))),
+                None => parse_naive_datetime(string, None),
+            };
+            datetime.map(|x| x.timestamp_millis())
+        }),
+        Time32(time_unit) => deserialize_primitive(rows, state, datatype, |bytes| {
             simdutf8::basic::from_utf8(bytes)
                 .ok()
-                .and_then(|x| x.parse::<chrono::NaiveDateTime>().ok())
-                .map(|x| x.timestamp_millis())
+                .and_then(|x| parse_time(x, format))
+                .map(|x| to_time_unit(time_to_nanoseconds(x), time_unit) as i32)
         }),
-        Timestamp(TimeUnit::Nanosecond, None) => {
-            deserialize_primitive(rows, column, datatype, |bytes| {
-                simdutf8::basic::from_utf8(bytes)
-                    .ok()
-                    .and_then(|x| x.parse::<chrono::NaiveDateTime>().ok())
-                    .map(|x| x.timestamp_nanos())
-            })
-        }
-        Timestamp(TimeUnit::Microsecond, None) => {
-            deserialize_primitive(rows, column, datatype, |bytes| {
-                simdutf8::basic::from_utf8(bytes)
-                    .ok()
-                    .and_then(|x| x.parse::<chrono::NaiveDateTime>().ok())
-                    .map(|x| x.timestamp_nanos() / 1000)
-            })
-        }
-        Timestamp(time_unit, None) => deserialize_primitive(rows, column, datatype, |bytes| {
+        Time64(time_unit) => deserialize_primitive(rows, state, datatype, |bytes| {
             simdutf8::basic::from_utf8(bytes)
                 .ok()
-                .and_then(|x| x.parse::<chrono::NaiveDateTime>().ok())
-                .map(|x| x.timestamp_nanos())
-                .map(|x| match time_unit {
-                    TimeUnit::Second => x / 1_000_000_000,
-                    TimeUnit::Millisecond => x / 1_000_000,
-                    TimeUnit::Microsecond => x / 1_000,
-                    TimeUnit::Nanosecond => x,
-                })
+                .and_then(|x| parse_time(x, format))
+                .map(|x| to_time_unit(time_to_nanoseconds(x), time_unit))
+        }),
+        Timestamp(time_unit, None) => deserialize_primitive(rows, state, datatype, |bytes| {
+            simdutf8::basic::from_utf8(bytes)
+                .ok()
+                .and_then(|x| parse_naive_datetime(x, format))
+                .map(|x| to_time_unit(x.timestamp_nanos(), time_unit))
         }),
         Timestamp(time_unit, Some(ref tz)) => {
             let tz = temporal_conversions::parse_offset(tz)?;
-            deserialize_primitive(rows, column, datatype, |bytes| {
+            let format = format.unwrap_or(RFC3339);
+            deserialize_primitive(rows, state, datatype, |bytes| {
                 simdutf8::basic::from_utf8(bytes)
                     .ok()
-                    .and_then(|x| deserialize_datetime(x, &tz))
-                    .map(|x| match time_unit {
-                        TimeUnit::Second => x / 1_000_000_000,
-                        TimeUnit::Millisecond => x / 1_000_000,
-                        TimeUnit::Microsecond => x / 1_000,
-                        TimeUnit::Nanosecond => x,
-                    })
+                    .and_then(|x| deserialize_datetime(x, &tz, format))
+                    .map(|x| to_time_unit(x, time_unit))
             })
         }
-        Decimal(precision, scale) => deserialize_primitive(rows, column, datatype, |x| {
-            deserialize_decimal(x, precision, scale)
+        Duration(time_unit) => deserialize_primitive(rows, state, datatype, |bytes| {
+            deserialize_duration(bytes, time_unit)
         }),
-        Utf8 => deserialize_utf8::<i32, _>(rows, column),
-        LargeUtf8 => deserialize_utf8::<i64, _>(rows, column),
-        Binary => deserialize_binary::<i32, _>(rows, column),
-        LargeBinary => deserialize_binary::<i64, _>(rows, column),
+        Interval(IntervalUnit::YearMonth) => {
+            deserialize_primitive(rows, state, datatype, |bytes| {
+                deserialize_interval(bytes).and_then(|(months, days, nanoseconds)| {
+                    (days == 0 && nanoseconds == 0).then(|| months)
+                })
+            })
+        }
+        Interval(IntervalUnit::DayTime) => deserialize_primitive(rows, state, datatype, |bytes| {
+            deserialize_interval(bytes).and_then(|(months, days, nanoseconds)| {
+                if months != 0 || nanoseconds % 1_000_000 != 0 {
+                    return None;
+                }
+                let milliseconds = i32::try_from(nanoseconds / 1_000_000).ok()?;
+                Some(days_ms::new(days, milliseconds))
+            })
+        }),
+        Interval(IntervalUnit::MonthDayNano) => {
+            deserialize_primitive(rows, state, datatype, |bytes| {
+                deserialize_interval(bytes).map(|(months, days, nanoseconds)| {
+                    months_days_ns::new(months, days, nanoseconds)
+                })
+            })
+        }
+        Decimal(precision, scale) => deserialize_primitive(rows, state, datatype, |x| {
+            deserialize_decimal(&options.normalize_decimal(x), precision, scale)
+        }),
+        Utf8 => deserialize_utf8::<i32, _>(rows, state),
+        LargeUtf8 => deserialize_utf8::<i64, _>(rows, state),
+        Binary => deserialize_binary::<i32, _>(rows, state),
+        LargeBinary => deserialize_binary::<i64, _>(rows, state),
+        FixedSizeBinary(size) => deserialize_fixed_size_binary(rows, state, size),
+        Dictionary(key_type, values) => match_integer_type!(key_type, |$T| {
+            deserialize_dictionary::<$T, _>(rows, state, values.as_ref())?
+        }),
+        Float16 => {
+            return Err(ArrowError::NotYetImplemented(
+                "Float16 is not supported by this crate".to_string(),
+            ))
+        }
         other => {
             return Err(ArrowError::NotYetImplemented(format!(
                 "Deserializing type \"{:?}\" is not implemented",
@@ -253,18 +765,16 @@ pub(crate) fn deserialize_column<B: ByteRecordGeneric>(
     })
 }
 
-/// Deserializes rows [`ByteRecord`] into a [`RecordBatch`].
-/// Note that this is a convenience function: column deserialization
-/// is trivially parallelizable (e.g. rayon).
-pub(crate) fn deserialize_batch<F, B: ByteRecordGeneric>(
+/// Deserializes rows into a [`RecordBatch`], deserializing each column with `deserialize_column`.
+pub(crate) fn deserialize_batch_with<F, B: ByteRecordGeneric>(
     rows: &[B],
     fields: &[Field],
     projection: Option<&[usize]>,
     line_number: usize,
-    deserialize_column: F,
+    mut deserialize_column: F,
 ) -> Result<RecordBatch>
 where
-    F: Fn(&[B], usize, DataType, usize) -> Result<Arc<dyn Array>>,
+    F: FnMut(&[B], usize, DataType, usize) -> Result<Arc<dyn Array>>,
 {
     let projection: Vec<usize> = match projection {
         Some(v) => v.to_vec(),
@@ -284,9 +794,46 @@ where
             let column = *column;
             let field = &fields[column];
             let data_type = field.data_type();
-            deserialize_column(rows, column, data_type.clone(), line_number)
+            deserialize_column(rows, column, data_type.clone(), line_number).map_err(|error| {
+                match error {
+                    ArrowError::ExternalFormat(message) => ArrowError::ExternalFormat(format!(
+                        "{} in column \"{}\"",
+                        message,
+                        field.name()
+                    )),
+                    error => error,
+                }
+            })
         })
         .collect::<Result<Vec<_>>>()?;
 
     RecordBatch::try_new(schema, columns)
 }
+
+/// Deserializes rows into a [`RecordBatch`] of the (projected) `fields` using `options`,
+/// returning the cells that could not be deserialized alongside it (see [`deserialize_column`]).
+/// `line_number` is the line number of the first row, used when the rows do not know their position.
+///
+/// Note that this is a convenience function: column deserialization
+/// is trivially parallelizable (e.g. rayon).
+/// # Errors
+/// Errors of the format of a cell are annotated with the name of its column.
+pub fn deserialize_batch<B: ByteRecordGeneric>(
+    rows: &[B],
+    fields: &[Field],
+    projection: Option<&[usize]>,
+    line_number: usize,
+    options: &DeserializeOptions,
+) -> Result<(RecordBatch, Vec<RejectedCell>)> {
+    let mut rejected = vec![];
+    let batch = deserialize_batch_with(
+        rows,
+        fields,
+        projection,
+        line_number,
+        |rows, column, datatype, line_number| {
+            deserialize_column_into(rows, column, datatype, line_number, options, &mut rejected)
+        },
+    )?;
+    Ok((batch, rejected))
+}
@@ -1,36 +1,53 @@
 use std::collections::HashSet;
 
-use crate::datatypes::{DataType, Field, TimeUnit};
+use crate::datatypes::{DataType, Field, IntegerType, Schema, TimeUnit};
+
+use super::read_utils::{ByteRecordGeneric, DeserializeOptions};
 
 pub(super) const RFC3339: &str = "%Y-%m-%dT%H:%M:%S%.f%:z";
 
-fn is_boolean(bytes: &[u8]) -> bool {
-    bytes.eq_ignore_ascii_case(b"true") | bytes.eq_ignore_ascii_case(b"false")
+#[inline]
+pub(super) fn parse_date(string: &str, format: Option<&str>) -> Option<chrono::NaiveDate> {
+    match format {
+        Some(format) => chrono::NaiveDate::parse_from_str(string, format).ok(),
+        None => string.parse().ok(),
+    }
 }
 
-fn is_float(bytes: &[u8]) -> bool {
-    lexical_core::parse::<f64>(bytes).is_ok()
+#[inline]
+pub(super) fn parse_time(string: &str, format: Option<&str>) -> Option<chrono::NaiveTime> {
+    match format {
+        Some(format) => chrono::NaiveTime::parse_from_str(string, format).ok(),
+        None => string.parse().ok(),
+    }
 }
 
-fn is_integer(bytes: &[u8]) -> bool {
-    lexical_core::parse::<i64>(bytes).is_ok()
+#[inline]
+pub(super) fn parse_naive_datetime(
+    string: &str,
+    format: Option<&str>,
+) -> Option<chrono::NaiveDateTime> {
+    match format {
+        Some(format) => chrono::NaiveDateTime::parse_from_str(string, format).ok(),
+        None => string.parse().ok(),
+    }
 }
 
-fn is_date(string: &str) -> bool {
-    string.parse::<chrono::NaiveDate>().is_ok()
+fn is_boolean(bytes: &[u8]) -> bool {
+    bytes.eq_ignore_ascii_case(b"true") | bytes.eq_ignore_ascii_case(b"false")
 }
 
-fn is_time(string: &str) -> bool {
-    string.parse::<chrono::NaiveTime>().is_ok()
+fn is_float(bytes: &[u8]) -> bool {
+    lexical_core::parse::<f64>(bytes).is_ok()
 }
 
-fn is_naive_datetime(string: &str) -> bool {
-    string.parse::<chrono::NaiveDateTime>().is_ok()
+fn is_integer(bytes: &[u8]) -> bool {
+    lexical_core::parse::<i64>(bytes).is_ok()
 }
 
-fn is_datetime(string: &str) -> Option<String> {
+fn is_datetime(string: &str, format: &str) -> Option<String> {
     let mut parsed = chrono::format::Parsed::new();
-    let fmt = chrono::format::StrftimeItems::new(RFC3339);
+    let fmt = chrono::format::StrftimeItems::new(format);
     if chrono::format::parse(&mut parsed, string, fmt).is_ok() {
         parsed.offset.map(|x| {
             let hours = x / 60 / 60;
@@ -42,7 +59,8 @@ fn is_datetime(string: &str) -> Option<String> {
     }
 }
 
-/// Infers [`DataType`] from `bytes`
+/// Infers [`DataType`] from `bytes`, consistently with [`infer_with_options`] with the default
+/// [`DeserializeOptions`].
 /// # Implementation
 /// * case insensitive "true" or "false" are mapped to [`DataType::Boolean`]
 /// * parsable to integer is mapped to [`DataType::Int64`]
@@ -61,14 +79,57 @@ pub fn infer(bytes: &[u8]) -> DataType {
     } else if is_float(bytes) {
         DataType::Float64
     } else if let Ok(string) = simdutf8::basic::from_utf8(bytes) {
-        if is_date(string) {
+        if parse_date(string, None).is_some() {
             DataType::Date32
-        } else if is_time(string) {
+        } else if parse_time(string, None).is_some() {
             DataType::Time32(TimeUnit::Millisecond)
-        } else if is_naive_datetime(string) {
+        } else if parse_naive_datetime(string, None).is_some() {
             DataType::Timestamp(TimeUnit::Millisecond, None)
-        } else if let Some(offset) = is_datetime(string) {
+        } else if let Some(offset) = is_datetime(string, RFC3339) {
+            DataType::Timestamp(TimeUnit::Millisecond, Some(offset))
+        } else {
+            DataType::Utf8
+        }
+    } else {
+        // invalid utf8
+        DataType::Binary
+    }
+}
+
+/// Infers [`DataType`] from `bytes` using `options`. Use it with
+/// [`infer_schema`](super::read::infer_schema) so that the schema is consistent with
+/// deserializing with the same `options`.
+/// # Implementation
+/// Like [`infer`], with the following differences:
+/// * `options.null_values` are mapped to [`DataType::Null`], which is ignored when merging
+///   the types of a column. Add `""` to them to ignore empty values.
+/// * `options.true_values` and `options.false_values` are mapped to [`DataType::Boolean`]
+/// * floats are parsed using `options.decimal_separator`
+/// * dates, times and datetimes are parsed using `options.date_format`, `options.time_format`
+///   and `options.datetime_format`; per-column formats are not used during inference
+pub fn infer_with_options(bytes: &[u8], options: &DeserializeOptions) -> DataType {
+    if options.is_null(bytes) {
+        DataType::Null
+    } else if options.parse_boolean(bytes).is_some() {
+        DataType::Boolean
+    } else if is_integer(bytes) {
+        DataType::Int64
+    } else if is_float(&options.normalize_decimal(bytes)) {
+        DataType::Float64
+    } else if let Ok(string) = simdutf8::basic::from_utf8(bytes) {
+        let date_format = options.format(None, &DataType::Date32);
+        let time_format = options.format(None, &DataType::Time32(TimeUnit::Millisecond));
+        let datetime_format =
+            options.format(None, &DataType::Timestamp(TimeUnit::Millisecond, None));
+        if parse_date(string, date_format).is_some() {
+            DataType::Date32
+        } else if parse_time(string, time_format).is_some() {
+            DataType::Time32(TimeUnit::Millisecond)
+        } else if let Some(offset) = is_datetime(string, datetime_format.unwrap_or(RFC3339)) {
+            // checked first since naive parsing ignores offsets in custom formats
             DataType::Timestamp(TimeUnit::Millisecond, Some(offset))
+        } else if parse_naive_datetime(string, datetime_format).is_some() {
+            DataType::Timestamp(TimeUnit::Millisecond, None)
         } else {
             DataType::Utf8
         }
@@ -79,10 +140,18 @@ pub fn infer(bytes: &[u8]) -> DataType {
 }
 
 fn merge_fields(field_name: &str, possibilities: &mut HashSet<DataType>) -> Field {
+    // nulls are compatible with any type
+    if possibilities.len() > 1 {
+        possibilities.remove(&DataType::Null);
+    }
     // determine data type based on possible types
     // if there are incompatible types, use DataType::Utf8
     let data_type = match possibilities.len() {
-        1 => possibilities.drain().next().unwrap(),
+        // a column of nulls is read as strings
+        1 => match possibilities.drain().next().unwrap() {
+            DataType::Null => DataType::Utf8,
+            data_type => data_type,
+        },
         2 => {
             if possibilities.contains(&DataType::Int64)
                 && possibilities.contains(&DataType::Float64)
@@ -99,13 +168,145 @@ fn merge_fields(field_name: &str, possibilities: &mut HashSet<DataType>) -> Fiel
     Field::new(field_name, data_type, true)
 }
 
-pub(crate) fn merge_schema(
-    headers: &[String],
-    column_types: &mut [HashSet<DataType>],
-) -> Vec<Field> {
+fn merge_schema(headers: &[String], column_types: &mut [HashSet<DataType>]) -> Vec<Field> {
     headers
         .iter()
         .zip(column_types.iter_mut())
         .map(|(field_name, possibilities)| merge_fields(field_name, possibilities))
         .collect()
 }
+
+/// Tracks the distinct values of a column, up to a maximum cardinality
+struct Cardinality {
+    values: Option<HashSet<Vec<u8>>>,
+    max_cardinality: usize,
+    rows: usize,
+}
+
+impl Cardinality {
+    pub fn new(max_cardinality: usize) -> Self {
+        Self {
+            values: Some(HashSet::new()),
+            max_cardinality,
+            rows: 0,
+        }
+    }
+
+    #[inline]
+    pub fn insert(&mut self, bytes: &[u8]) {
+        self.rows += 1;
+        if let Some(values) = &mut self.values {
+            if !values.contains(bytes) {
+                values.insert(bytes.to_vec());
+                if values.len() > self.max_cardinality {
+                    // the column has a high cardinality: stop tracking it
+                    self.values = None;
+                }
+            }
+        }
+    }
+
+    /// Whether the column has at most `max_cardinality` distinct values and each of them
+    /// is repeated, on average, at least twice.
+    #[inline]
+    fn is_low(&self) -> bool {
+        self.values
+            .as_ref()
+            .map(|values| !values.is_empty() && values.len() * 2 <= self.rows)
+            .unwrap_or(false)
+    }
+}
+
+/// Converts [`DataType::Utf8`] fields whose [`Cardinality`] is low to dictionary-encoded fields
+fn infer_dictionaries(fields: Vec<Field>, cardinalities: &[Cardinality]) -> Vec<Field> {
+    fields
+        .into_iter()
+        .zip(cardinalities.iter())
+        .map(|(mut field, cardinality)| {
+            if field.data_type == DataType::Utf8 && cardinality.is_low() {
+                field.data_type =
+                    DataType::Dictionary(IntegerType::UInt32, Box::new(DataType::Utf8));
+            }
+            field
+        })
+        .collect()
+}
+
+/// Options to infer a [`Schema`] from a CSV file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct InferSchemaOptions {
+    /// The maximum number of records read to infer the schema. When `None`, all records are read.
+    pub max_rows: Option<usize>,
+    /// Whether the first record is a header with the names of the fields. When `false`,
+    /// fields are named `column_{i}` (starting at 1).
+    pub has_header: bool,
+    /// When `Some`, columns of [`DataType::Utf8`] with at most this number of distinct values,
+    /// each repeated on average at least twice, are inferred as [`DataType::Dictionary`]
+    /// of `u32` keys.
+    pub max_cardinality: Option<usize>,
+}
+
+/// The state of the inference of a [`Schema`] from the records of a CSV file, shared by
+/// the sync and async readers.
+pub(crate) struct SchemaInference {
+    headers: Vec<String>,
+    column_types: Vec<HashSet<DataType>>,
+    cardinalities: Vec<Cardinality>,
+}
+
+impl SchemaInference {
+    /// Creates a new [`SchemaInference`] from the first record of the file (the header, if any).
+    pub fn new<'a, I: Iterator<Item = &'a str>>(first: I, options: &InferSchemaOptions) -> Self {
+        let headers: Vec<String> = if options.has_header {
+            first.map(|x| x.to_string()).collect()
+        } else {
+            (0..first.count())
+                .map(|i| format!("column_{}", i + 1))
+                .collect()
+        };
+
+        let column_types = vec![HashSet::new(); headers.len()];
+        let cardinalities = options
+            .max_cardinality
+            .map(|max_cardinality| {
+                (0..headers.len())
+                    .map(|_| Cardinality::new(max_cardinality))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            headers,
+            column_types,
+            cardinalities,
+        }
+    }
+
+    /// Updates the inference with a record, inferring the [`DataType`] of each value with `infer`.
+    pub fn update<B: ByteRecordGeneric, F: Fn(&[u8]) -> DataType>(
+        &mut self,
+        record: &B,
+        infer: &F,
+    ) {
+        for (i, column) in self.column_types.iter_mut().enumerate() {
+            if let Some(bytes) = record.get(i) {
+                column.insert(infer(bytes));
+            }
+        }
+        for (i, cardinality) in self.cardinalities.iter_mut().enumerate() {
+            if let Some(bytes) = record.get(i) {
+                cardinality.insert(bytes);
+            }
+        }
+    }
+
+    /// Returns the inferred [`Schema`]
+    pub fn finish(mut self) -> Schema {
+        let fields = merge_schema(&self.headers, &mut self.column_types);
+        let fields = if self.cardinalities.is_empty() {
+            fields
+        } else {
+            infer_dictionaries(fields, &self.cardinalities)
+        };
+        Schema::new(fields)
+    }
+}
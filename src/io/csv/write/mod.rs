@@ -1,19 +1,28 @@
 //! APIs to write to CSV
-mod iterator;
 mod serialize;
 
-use iterator::StreamingIterator;
+use crate::io::iterator::StreamingIterator;
 
 use std::io::Write;
 
-// re-export necessary public APIs from csv
-pub use csv::{ByteRecord, Writer, WriterBuilder};
-
 pub use serialize::*;
 
 use crate::record_batch::RecordBatch;
 use crate::{datatypes::Schema, error::Result};
 
+/// Returns a [`csv::Writer`] over `writer` that delimits and quotes values according to `options`
+fn new_writer<W: Write>(writer: W, options: &SerializeOptions) -> csv::Writer<W> {
+    csv::WriterBuilder::new()
+        .delimiter(options.delimiter)
+        .quote_style(match options.quote_style {
+            QuoteStyle::Always => csv::QuoteStyle::Always,
+            QuoteStyle::Necessary => csv::QuoteStyle::Necessary,
+            QuoteStyle::NonNumeric => csv::QuoteStyle::NonNumeric,
+            QuoteStyle::Never => csv::QuoteStyle::Never,
+        })
+        .from_writer(writer)
+}
+
 /// Creates serializers that iterate over each column of `batch` and serialize each item according
 /// to `options`.
 fn new_serializers<'a>(
@@ -27,31 +36,26 @@ fn new_serializers<'a>(
         .collect()
 }
 
-/// Serializes a [`RecordBatch`] as vector of `ByteRecord`.
-/// The vector is guaranteed to have `batch.num_rows()` entries.
-/// Each `ByteRecord` is guaranteed to have `batch.num_columns()` fields.
-pub fn serialize(batch: &RecordBatch, options: &SerializeOptions) -> Result<Vec<ByteRecord>> {
-    let mut serializers = new_serializers(batch, options)?;
-
-    let mut records = vec![ByteRecord::with_capacity(0, batch.num_columns()); batch.num_rows()];
-    records.iter_mut().for_each(|record| {
-        serializers
-            .iter_mut()
-            // `unwrap` is infalible because `array.len()` equals `num_rows` on a `RecordBatch`
-            .for_each(|iter| record.push_field(iter.next().unwrap()));
-    });
-    Ok(records)
+/// Serializes a [`RecordBatch`] to CSV according to `options`, one line per row.
+/// This is the CPU-bounded part of [`write_batch`]: batches can be serialized in parallel and
+/// the result written to the file in order.
+pub fn serialize(batch: &RecordBatch, options: &SerializeOptions) -> Result<Vec<u8>> {
+    let mut buffer = vec![];
+    write_batch(&mut buffer, batch, options)?;
+    Ok(buffer)
 }
 
-/// Writes the data in a `RecordBatch` to `writer` according to the serialization options `options`.
+/// Writes the data in a `RecordBatch` to `writer` according to the serialization options `options`,
+/// including how values are delimited and quoted.
 pub fn write_batch<W: Write>(
-    writer: &mut Writer<W>,
+    writer: &mut W,
     batch: &RecordBatch,
     options: &SerializeOptions,
 ) -> Result<()> {
+    let mut writer = new_writer(writer, options);
     let mut serializers = new_serializers(batch, options)?;
 
-    let mut record = ByteRecord::with_capacity(0, batch.num_columns());
+    let mut record = csv::ByteRecord::with_capacity(0, batch.num_columns());
 
     // this is where the (expensive) transposition happens: the outer loop is on rows, the inner on columns
     (0..batch.num_rows()).try_for_each(|_| {
@@ -63,16 +67,23 @@ pub fn write_batch<W: Write>(
         record.clear();
         Result::Ok(())
     })?;
+    writer.flush()?;
     Ok(())
 }
 
-/// Writes a header to `writer` according to `schema`
-pub fn write_header<W: Write>(writer: &mut Writer<W>, schema: &Schema) -> Result<()> {
+/// Writes a header to `writer` according to `schema`, delimited and quoted according to `options`
+pub fn write_header<W: Write>(
+    writer: &mut W,
+    schema: &Schema,
+    options: &SerializeOptions,
+) -> Result<()> {
+    let mut writer = new_writer(writer, options);
     let fields = schema
         .fields()
         .iter()
         .map(|field| field.name().to_string())
         .collect::<Vec<_>>();
     writer.write_record(&fields)?;
+    writer.flush()?;
     Ok(())
 }
@@ -1,18 +1,42 @@
 use lexical_core::ToLexical;
+use std::io::Write;
 
-use crate::datatypes::IntegerType;
 use crate::temporal_conversions;
-use crate::types::{Index, NativeType};
+use crate::types::{days_ms, months_days_ns, NativeType};
 use crate::util::lexical_to_bytes_mut;
 use crate::{
-    array::{Array, BinaryArray, BooleanArray, PrimitiveArray, Utf8Array},
-    datatypes::{DataType, TimeUnit},
-    error::Result,
+    array::*,
+    datatypes::{DataType, IntervalUnit, TimeUnit},
+    error::{ArrowError, Result},
 };
 
-use super::iterator::{BufStreamingIterator, StreamingIterator};
-use crate::array::{DictionaryArray, DictionaryKey, Offset};
-use std::any::Any;
+use crate::io::iterator::{BufStreamingIterator, StreamingIterator};
+use crate::io::json;
+
+/// How binary values ([`DataType::Binary`], [`DataType::LargeBinary`] and
+/// [`DataType::FixedSizeBinary`]) are written
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum BinaryFormat {
+    /// The bytes are written as is
+    Raw,
+    /// The bytes are written as lower case hexadecimal
+    Hex,
+    /// The bytes are written as (standard) base64
+    Base64,
+}
+
+/// When values are quoted
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum QuoteStyle {
+    /// Every value is quoted
+    Always,
+    /// Values are quoted only when needed (e.g. when they contain the delimiter)
+    Necessary,
+    /// Values that are not numbers are quoted
+    NonNumeric,
+    /// Values are never quoted
+    Never,
+}
 
 /// Options to serialize logical types to CSV
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
@@ -25,8 +49,24 @@ pub struct SerializeOptions {
     pub time32_format: String,
     /// used for [`DataType::Time64`]
     pub time64_format: String,
-    /// used for [`DataType::Timestamp`]
+    /// used for [`DataType::Timestamp`] without timezone
     pub timestamp_format: String,
+    /// used for [`DataType::Timestamp`] with timezone
+    pub timestamp_tz_format: String,
+    /// The timezone (e.g. `+01:00`) that timestamps with timezone are written in.
+    /// When `None`, the timezone of the array is used.
+    pub timezone: Option<String>,
+    /// The representation of null values
+    pub null: String,
+    /// The number of decimal places of floats. When `None`, the shortest representation
+    /// that round-trips is used.
+    pub float_precision: Option<usize>,
+    /// How binary values are written
+    pub binary_format: BinaryFormat,
+    /// The delimiter between values
+    pub delimiter: u8,
+    /// When values are quoted
+    pub quote_style: QuoteStyle,
 }
 
 impl Default for SerializeOptions {
@@ -37,106 +77,263 @@ impl Default for SerializeOptions {
             time32_format: "%T".to_string(),
             time64_format: "%T".to_string(),
             timestamp_format: "%FT%H:%M:%S.%9f".to_string(),
+            timestamp_tz_format: "%FT%H:%M:%S.%9f%:z".to_string(),
+            timezone: None,
+            null: "".to_string(),
+            float_precision: None,
+            binary_format: BinaryFormat::Raw,
+            delimiter: b',',
+            quote_style: QuoteStyle::Necessary,
         }
     }
 }
 
-fn primitive_write<'a, T: NativeType + ToLexical>(
-    array: &'a PrimitiveArray<T>,
-) -> Box<dyn StreamingIterator<Item = [u8]> + 'a> {
+/// Returns a [`StreamingIterator`] over `iter` that serializes its values with `f`
+/// and its nulls as `null`.
+fn new_iterator<'a, I, T, F>(
+    iter: I,
+    null: &'a str,
+    f: F,
+) -> Box<dyn StreamingIterator<Item = [u8]> + 'a>
+where
+    I: Iterator<Item = Option<T>> + 'a,
+    T: 'a,
+    F: Fn(T, &mut Vec<u8>) + 'a,
+{
     Box::new(BufStreamingIterator::new(
-        array.iter(),
-        |x, buf| {
-            if let Some(x) = x {
-                lexical_to_bytes_mut(*x, buf)
-            }
+        iter,
+        move |x, buf| match x {
+            Some(x) => f(x, buf),
+            None => buf.extend_from_slice(null.as_bytes()),
         },
         vec![],
     ))
 }
 
+fn primitive_write<'a, T: NativeType + ToLexical>(
+    array: &'a PrimitiveArray<T>,
+    options: &'a SerializeOptions,
+) -> Box<dyn StreamingIterator<Item = [u8]> + 'a> {
+    new_iterator(array.iter(), &options.null, |x, buf| {
+        lexical_to_bytes_mut(*x, buf)
+    })
+}
+
+fn float_write<'a, T: NativeType + ToLexical + std::fmt::Display>(
+    array: &'a PrimitiveArray<T>,
+    options: &'a SerializeOptions,
+) -> Box<dyn StreamingIterator<Item = [u8]> + 'a> {
+    match options.float_precision {
+        Some(precision) => new_iterator(array.iter(), &options.null, move |x, buf| {
+            write!(buf, "{:.*}", precision, x).unwrap()
+        }),
+        None => primitive_write(array, options),
+    }
+}
+
 macro_rules! dyn_primitive {
-    ($ty:ty, $array:expr) => {{
+    ($ty:ty, $array:expr, $options:expr) => {{
+        let array = $array.as_any().downcast_ref().unwrap();
+        primitive_write::<$ty>(array, $options)
+    }};
+}
+
+macro_rules! dyn_float {
+    ($ty:ty, $array:expr, $options:expr) => {{
         let array = $array.as_any().downcast_ref().unwrap();
-        primitive_write::<$ty>(array)
+        float_write::<$ty>(array, $options)
     }};
 }
 
 macro_rules! dyn_date {
-    ($ty:ident, $fn:expr, $array:expr, $format:expr) => {{
+    ($ty:ident, $fn:expr, $array:expr, $format:expr, $options:expr) => {{
         let array = $array
             .as_any()
             .downcast_ref::<PrimitiveArray<$ty>>()
             .unwrap();
-        Box::new(BufStreamingIterator::new(
-            array.iter(),
-            move |x, buf| {
-                if let Some(x) = x {
-                    buf.extend_from_slice(($fn)(*x).format($format).to_string().as_bytes())
-                }
-            },
-            vec![],
-        ))
+        new_iterator(array.iter(), &$options.null, move |x, buf| {
+            buf.extend_from_slice(($fn)(*x).format($format).to_string().as_bytes())
+        })
     }};
 }
 
+fn timestamp_tz_write<'a, T>(
+    array: &'a PrimitiveArray<i64>,
+    time_unit: TimeUnit,
+    timezone: T,
+    options: &'a SerializeOptions,
+) -> Box<dyn StreamingIterator<Item = [u8]> + 'a>
+where
+    T: chrono::TimeZone + 'a,
+    T::Offset: std::fmt::Display,
+{
+    new_iterator(array.iter(), &options.null, move |x, buf| {
+        let datetime = temporal_conversions::timestamp_to_datetime(*x, time_unit, &timezone);
+        write!(buf, "{}", datetime.format(&options.timestamp_tz_format)).unwrap()
+    })
+}
+
+#[cfg(feature = "chrono-tz")]
+fn timestamp_tz_write_named<'a>(
+    array: &'a PrimitiveArray<i64>,
+    time_unit: TimeUnit,
+    timezone: &str,
+    options: &'a SerializeOptions,
+) -> Result<Box<dyn StreamingIterator<Item = [u8]> + 'a>> {
+    let timezone = temporal_conversions::parse_offset_tz(timezone)?;
+    Ok(timestamp_tz_write(array, time_unit, timezone, options))
+}
+
+#[cfg(not(feature = "chrono-tz"))]
+fn timestamp_tz_write_named<'a>(
+    _: &'a PrimitiveArray<i64>,
+    _: TimeUnit,
+    timezone: &str,
+    _: &'a SerializeOptions,
+) -> Result<Box<dyn StreamingIterator<Item = [u8]> + 'a>> {
+    Err(ArrowError::InvalidArgumentError(format!(
+        "timezone \"{}\" cannot be parsed (feature chrono-tz is not active)",
+        timezone
+    )))
+}
+
+/// Writes `nanoseconds` as seconds with the minimal number of decimal places
+fn write_seconds(nanoseconds: i128, buf: &mut Vec<u8>) {
+    if nanoseconds < 0 {
+        buf.push(b'-');
+    }
+    let nanoseconds = nanoseconds.unsigned_abs();
+    let seconds = nanoseconds / 1_000_000_000;
+    let fraction = nanoseconds % 1_000_000_000;
+    if fraction == 0 {
+        write!(buf, "{}", seconds).unwrap()
+    } else {
+        let fraction = format!("{:09}", fraction);
+        write!(buf, "{}.{}", seconds, fraction.trim_end_matches('0')).unwrap()
+    }
+}
+
+/// Writes an ISO 8601 duration, e.g. `P1M2DT3.5S`
+fn write_iso8601(months: i32, days: i32, nanoseconds: i128, buf: &mut Vec<u8>) {
+    buf.push(b'P');
+    if months != 0 {
+        write!(buf, "{}M", months).unwrap();
+    }
+    if days != 0 {
+        write!(buf, "{}D", days).unwrap();
+    }
+    if nanoseconds != 0 || (months == 0 && days == 0) {
+        buf.push(b'T');
+        write_seconds(nanoseconds, buf);
+        buf.push(b'S');
+    }
+}
+
+/// Converts `value` in `time_unit` to nanoseconds. `i128` is used so that no `i64` overflows.
+#[inline]
+fn to_nanoseconds(value: i64, time_unit: TimeUnit) -> i128 {
+    let value = value as i128;
+    match time_unit {
+        TimeUnit::Second => value * 1_000_000_000,
+        TimeUnit::Millisecond => value * 1_000_000,
+        TimeUnit::Microsecond => value * 1_000,
+        TimeUnit::Nanosecond => value,
+    }
+}
+
+fn write_decimal(value: i128, scale: usize, buf: &mut Vec<u8>) {
+    if scale == 0 {
+        write!(buf, "{}", value).unwrap();
+        return;
+    }
+    if value < 0 {
+        buf.push(b'-');
+    }
+    let value = value.unsigned_abs();
+    let factor = 10u128.pow(scale as u32);
+    write!(
+        buf,
+        "{}.{:0width$}",
+        value / factor,
+        value % factor,
+        width = scale
+    )
+    .unwrap()
+}
+
+fn write_binary(bytes: &[u8], format: BinaryFormat, buf: &mut Vec<u8>) {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    match format {
+        BinaryFormat::Raw => buf.extend_from_slice(bytes),
+        BinaryFormat::Hex => bytes.iter().for_each(|byte| {
+            buf.push(HEX[(byte >> 4) as usize]);
+            buf.push(HEX[(byte & 0x0f) as usize]);
+        }),
+        BinaryFormat::Base64 => buf.extend_from_slice(base64::encode(bytes).as_bytes()),
+    }
+}
+
 /// Returns a [`StreamingIterator`] that yields `&[u8]` serialized from `array` according to `options`.
 /// For numeric types, this serializes as usual. For dates, times and timestamps, it uses `options` to
-/// Supported types:
+/// format them. Supported types:
 /// * boolean
-/// * numeric types (i.e. floats, int, uint)
-/// * times and dates
-/// * naive timestamps (timestamps without timezone information)
+/// * numeric types (i.e. floats, int, uint) and decimals
+/// * times, dates and timestamps (with and without timezone)
+/// * durations and intervals, as ISO 8601 durations (e.g. `P1M2DT3.5S`)
+/// * strings and binary (see [`BinaryFormat`])
+/// * dictionaries, serialized as their values
+/// * nested types (lists, structs, maps and unions), as JSON (see [`json::new_serializer`])
 /// # Error
-/// This function errors if any of the logical types in `batch` is not supported.
+/// This function errors if any of the logical types in `batch` is not supported
+/// or if a timezone cannot be parsed.
 pub fn new_serializer<'a>(
     array: &'a dyn Array,
     options: &'a SerializeOptions,
 ) -> Result<Box<dyn StreamingIterator<Item = [u8]> + 'a>> {
-    Ok(match array.data_type() {
+    Ok(match array.data_type().to_logical_type() {
+        DataType::Null => new_iterator(
+            std::iter::repeat(None::<()>).take(array.len()),
+            &options.null,
+            |_, _| {},
+        ),
         DataType::Boolean => {
             let array = array.as_any().downcast_ref::<BooleanArray>().unwrap();
-            Box::new(BufStreamingIterator::new(
-                array.iter(),
-                |x, buf| {
-                    if let Some(x) = x {
-                        if x {
-                            buf.extend_from_slice(b"true");
-                        } else {
-                            buf.extend_from_slice(b"false");
-                        }
-                    }
-                },
-                vec![],
-            ))
+            new_iterator(array.iter(), &options.null, |x, buf| {
+                if x {
+                    buf.extend_from_slice(b"true");
+                } else {
+                    buf.extend_from_slice(b"false");
+                }
+            })
         }
         DataType::UInt8 => {
-            dyn_primitive!(u8, array)
+            dyn_primitive!(u8, array, options)
         }
         DataType::UInt16 => {
-            dyn_primitive!(u16, array)
+            dyn_primitive!(u16, array, options)
         }
         DataType::UInt32 => {
-            dyn_primitive!(u32, array)
+            dyn_primitive!(u32, array, options)
         }
         DataType::UInt64 => {
-            dyn_primitive!(u64, array)
+            dyn_primitive!(u64, array, options)
         }
         DataType::Int8 => {
-            dyn_primitive!(i8, array)
+            dyn_primitive!(i8, array, options)
         }
         DataType::Int16 => {
-            dyn_primitive!(i16, array)
+            dyn_primitive!(i16, array, options)
         }
         DataType::Int32 => {
-            dyn_primitive!(i32, array)
+            dyn_primitive!(i32, array, options)
         }
         DataType::Date32 => {
             dyn_date!(
                 i32,
                 temporal_conversions::date32_to_datetime,
                 array,
-                &options.date32_format
+                &options.date32_format,
+                options
             )
         }
         DataType::Time32(TimeUnit::Second) => {
@@ -144,7 +341,8 @@ pub fn new_serializer<'a>(
                 i32,
                 temporal_conversions::time32s_to_time,
                 array,
-                &options.time32_format
+                &options.time32_format,
+                options
             )
         }
         DataType::Time32(TimeUnit::Millisecond) => {
@@ -152,18 +350,20 @@ pub fn new_serializer<'a>(
                 i32,
                 temporal_conversions::time32ms_to_time,
                 array,
-                &options.time32_format
+                &options.time32_format,
+                options
             )
         }
         DataType::Int64 => {
-            dyn_primitive!(i64, array)
+            dyn_primitive!(i64, array, options)
         }
         DataType::Date64 => {
             dyn_date!(
                 i64,
                 temporal_conversions::date64_to_datetime,
                 array,
-                &options.date64_format
+                &options.date64_format,
+                options
             )
         }
         DataType::Time64(TimeUnit::Microsecond) => {
@@ -171,7 +371,8 @@ pub fn new_serializer<'a>(
                 i64,
                 temporal_conversions::time64us_to_time,
                 array,
-                &options.time64_format
+                &options.time64_format,
+                options
             )
         }
         DataType::Time64(TimeUnit::Nanosecond) => {
@@ -179,7 +380,8 @@ pub fn new_serializer<'a>(
                 i64,
                 temporal_conversions::time64ns_to_time,
                 array,
-                &options.time64_format
+                &options.time64_format,
+                options
             )
         }
         DataType::Timestamp(TimeUnit::Second, None) => {
@@ -187,7 +389,8 @@ pub fn new_serializer<'a>(
                 i64,
                 temporal_conversions::timestamp_s_to_datetime,
                 array,
-                &options.timestamp_format
+                &options.timestamp_format,
+                options
             )
         }
         DataType::Timestamp(TimeUnit::Millisecond, None) => {
@@ -195,7 +398,8 @@ pub fn new_serializer<'a>(
                 i64,
                 temporal_conversions::timestamp_ms_to_datetime,
                 array,
-                &options.timestamp_format
+                &options.timestamp_format,
+                options
             )
         }
         DataType::Timestamp(TimeUnit::Microsecond, None) => {
@@ -203,7 +407,8 @@ pub fn new_serializer<'a>(
                 i64,
                 temporal_conversions::timestamp_us_to_datetime,
                 array,
-                &options.timestamp_format
+                &options.timestamp_format,
+                options
             )
         }
         DataType::Timestamp(TimeUnit::Nanosecond, None) => {
@@ -211,106 +416,168 @@ pub fn new_serializer<'a>(
                 i64,
                 temporal_conversions::timestamp_ns_to_datetime,
                 array,
-                &options.timestamp_format
+                &options.timestamp_format,
+                options
             )
         }
+        DataType::Timestamp(time_unit, Some(timezone)) => {
+            let array = array
+                .as_any()
+                .downcast_ref::<PrimitiveArray<i64>>()
+                .unwrap();
+            let timezone = options.timezone.as_ref().unwrap_or(timezone);
+            match temporal_conversions::parse_offset(timezone) {
+                Ok(timezone) => timestamp_tz_write(array, *time_unit, timezone, options),
+                Err(_) => timestamp_tz_write_named(array, *time_unit, timezone, options)?,
+            }
+        }
         DataType::Float32 => {
-            dyn_primitive!(f32, array)
+            dyn_float!(f32, array, options)
         }
         DataType::Float64 => {
-            dyn_primitive!(f64, array)
+            dyn_float!(f64, array, options)
+        }
+        DataType::Decimal(_, scale) => {
+            let scale = *scale;
+            let array = array
+                .as_any()
+                .downcast_ref::<PrimitiveArray<i128>>()
+                .unwrap();
+            new_iterator(array.iter(), &options.null, move |x, buf| {
+                write_decimal(*x, scale, buf)
+            })
+        }
+        DataType::Duration(time_unit) => {
+            let time_unit = *time_unit;
+            let array = array
+                .as_any()
+                .downcast_ref::<PrimitiveArray<i64>>()
+                .unwrap();
+            new_iterator(array.iter(), &options.null, move |x, buf| {
+                let nanoseconds = to_nanoseconds(*x, time_unit);
+                if nanoseconds < 0 {
+                    buf.push(b'-');
+                }
+                write_iso8601(0, 0, nanoseconds.unsigned_abs() as i128, buf)
+            })
+        }
+        DataType::Interval(IntervalUnit::YearMonth) => {
+            let array = array
+                .as_any()
+                .downcast_ref::<PrimitiveArray<i32>>()
+                .unwrap();
+            new_iterator(array.iter(), &options.null, |x, buf| {
+                write_iso8601(*x, 0, 0, buf)
+            })
+        }
+        DataType::Interval(IntervalUnit::DayTime) => {
+            let array = array
+                .as_any()
+                .downcast_ref::<PrimitiveArray<days_ms>>()
+                .unwrap();
+            new_iterator(array.iter(), &options.null, |x, buf| {
+                write_iso8601(0, x.days(), x.milliseconds() as i128 * 1_000_000, buf)
+            })
+        }
+        DataType::Interval(IntervalUnit::MonthDayNano) => {
+            let array = array
+                .as_any()
+                .downcast_ref::<PrimitiveArray<months_days_ns>>()
+                .unwrap();
+            new_iterator(array.iter(), &options.null, |x, buf| {
+                write_iso8601(x.months(), x.days(), x.ns() as i128, buf)
+            })
         }
         DataType::Utf8 => {
             let array = array.as_any().downcast_ref::<Utf8Array<i32>>().unwrap();
-            Box::new(BufStreamingIterator::new(
-                array.iter(),
-                |x, buf| {
-                    if let Some(x) = x {
-                        buf.extend_from_slice(x.as_bytes());
-                    }
-                },
-                vec![],
-            ))
+            new_iterator(array.iter(), &options.null, |x, buf| {
+                buf.extend_from_slice(x.as_bytes());
+            })
         }
         DataType::LargeUtf8 => {
             let array = array.as_any().downcast_ref::<Utf8Array<i64>>().unwrap();
-            Box::new(BufStreamingIterator::new(
-                array.iter(),
-                |x, buf| {
-                    if let Some(x) = x {
-                        buf.extend_from_slice(x.as_bytes());
-                    }
-                },
-                vec![],
-            ))
+            new_iterator(array.iter(), &options.null, |x, buf| {
+                buf.extend_from_slice(x.as_bytes());
+            })
         }
         DataType::Binary => {
             let array = array.as_any().downcast_ref::<BinaryArray<i32>>().unwrap();
-            Box::new(BufStreamingIterator::new(
-                array.iter(),
-                |x, buf| {
-                    if let Some(x) = x {
-                        buf.extend_from_slice(x);
-                    }
-                },
-                vec![],
-            ))
+            new_iterator(array.iter(), &options.null, move |x, buf| {
+                write_binary(x, options.binary_format, buf)
+            })
         }
         DataType::LargeBinary => {
             let array = array.as_any().downcast_ref::<BinaryArray<i64>>().unwrap();
+            new_iterator(array.iter(), &options.null, move |x, buf| {
+                write_binary(x, options.binary_format, buf)
+            })
+        }
+        DataType::FixedSizeBinary(_) => {
+            let array = array
+                .as_any()
+                .downcast_ref::<FixedSizeBinaryArray>()
+                .unwrap();
+            new_iterator(array.iter(), &options.null, move |x, buf| {
+                write_binary(x, options.binary_format, buf)
+            })
+        }
+        DataType::Dictionary(key_type, _) => match_integer_type!(key_type, |$T| {
+            serialize_dictionary::<$T>(array, options)?
+        }),
+        DataType::List(_)
+        | DataType::LargeList(_)
+        | DataType::FixedSizeList(_, _)
+        | DataType::Struct(_)
+        | DataType::Map(_, _)
+        | DataType::Union(_, _, _) => {
+            let json_options = json::SerializeOptions {
+                binary_format: match options.binary_format {
+                    BinaryFormat::Raw => json::BinaryFormat::Utf8,
+                    BinaryFormat::Hex => json::BinaryFormat::Hex,
+                    BinaryFormat::Base64 => json::BinaryFormat::Base64,
+                },
+                ..Default::default()
+            };
+            let mut values = json::new_serializer(array, &json_options);
             Box::new(BufStreamingIterator::new(
-                array.iter(),
-                |x, buf| {
-                    if let Some(x) = x {
-                        buf.extend_from_slice(x);
+                0..array.len(),
+                move |index, buf| {
+                    values.advance();
+                    if array.is_valid(index) {
+                        buf.extend_from_slice(values.get().unwrap())
+                    } else {
+                        buf.extend_from_slice(options.null.as_bytes())
                     }
                 },
                 vec![],
             ))
         }
-        DataType::Dictionary(keys_dt, values_dt) => match &**values_dt {
-            DataType::LargeUtf8 => match *keys_dt {
-                IntegerType::UInt32 => serialize_utf8_dict::<u32, i64>(array.as_any()),
-                IntegerType::UInt64 => serialize_utf8_dict::<u64, i64>(array.as_any()),
-                _ => todo!(),
-            },
-            DataType::Utf8 => match *keys_dt {
-                IntegerType::UInt32 => serialize_utf8_dict::<u32, i32>(array.as_any()),
-                IntegerType::UInt64 => serialize_utf8_dict::<u64, i32>(array.as_any()),
-                _ => todo!(),
-            },
-            _ => {
-                panic!("only dictionary with string values are supported by csv writer")
-            }
-        },
-        dt => panic!("data type: {} not supported by csv writer", dt),
+        dt => {
+            return Err(ArrowError::NotYetImplemented(format!(
+                "Writing data type {} to CSV is not supported",
+                dt
+            )))
+        }
     })
 }
 
-/// Helper for serializing a dictonary array. The generic parameters are:
-/// - `K` for the type of the keys of the dictionary
-/// - `O` for the type of the offsets in the Utf8Array: {i32, i64}
-fn serialize_utf8_dict<'a, K: DictionaryKey + Index, O: Offset>(
-    array: &'a dyn Any,
-) -> Box<dyn StreamingIterator<Item = [u8]> + 'a> {
-    let array = array.downcast_ref::<DictionaryArray<K>>().unwrap();
+/// Helper for serializing a dictionary array. The values are serialized once and each
+/// key is serialized as its (serialized) value.
+fn serialize_dictionary<'a, K: DictionaryKey>(
+    array: &'a dyn Array,
+    options: &'a SerializeOptions,
+) -> Result<Box<dyn StreamingIterator<Item = [u8]> + 'a>> {
+    let array = array.as_any().downcast_ref::<DictionaryArray<K>>().unwrap();
     let keys = array.keys();
-    let values = array
-        .values()
-        .as_any()
-        .downcast_ref::<Utf8Array<O>>()
-        .unwrap();
-    Box::new(BufStreamingIterator::new(
-        keys.iter(),
-        move |x, buf| {
-            if let Some(x) = x {
-                let i = Index::to_usize(x);
-                if !values.is_null(i) {
-                    let val = values.value(i);
-                    buf.extend_from_slice(val.as_bytes());
-                }
-            }
-        },
-        vec![],
-    ))
+    let values = array.values().as_ref();
+
+    let mut serialized = new_serializer(values, options)?;
+    let mut serialized_values = Vec::with_capacity(values.len());
+    while let Some(value) = serialized.next() {
+        serialized_values.push(value.to_vec());
+    }
+
+    Ok(new_iterator(keys.iter(), &options.null, move |x, buf| {
+        buf.extend_from_slice(&serialized_values[x.to_usize().unwrap()])
+    }))
 }
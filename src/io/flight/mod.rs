@@ -16,6 +16,11 @@ use crate::{
     record_batch::RecordBatch,
 };
 
+mod stream;
+pub use stream::{
+    decode_iter, decode_stream, encode_iter, encode_stream, FlightDataDecoder, FlightDataEncoder,
+};
+
 /// Serializes a [`RecordBatch`] to a vector of [`FlightData`] representing the serialized dictionaries
 /// and a [`FlightData`] representing the batch.
 pub fn serialize_batch(
@@ -25,7 +30,7 @@ pub fn serialize_batch(
     let mut dictionary_tracker = DictionaryTracker::new(false);
 
     let (encoded_dictionaries, encoded_batch) =
-        encoded_batch(batch, &mut dictionary_tracker, options)
+        encoded_batch(batch, &mut dictionary_tracker, options, None)
             .expect("DictionaryTracker configured above to not error on replacement");
 
     let flight_dictionaries = encoded_dictionaries.into_iter().map(Into::into).collect();
@@ -0,0 +1,275 @@
+//! Encoding and decoding of streams of [`RecordBatch`]es as [`FlightData`] messages.
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arrow_format::flight::data::FlightData;
+use arrow_format::ipc;
+use futures::{Stream, StreamExt};
+
+use crate::{
+    array::*,
+    datatypes::*,
+    error::{ArrowError, Result},
+    io::ipc::fb_to_schema,
+    io::ipc::read::{read_dictionary, read_record_batch},
+    io::ipc::write::common::{encoded_batch, DictionaryTracker, WriteOptions},
+    record_batch::RecordBatch,
+};
+
+use super::serialize_schema;
+
+/// Encodes a sequence of [`RecordBatch`]es sharing a [`Schema`] into [`FlightData`] messages.
+///
+/// The first message is always the schema. Dictionaries are tracked across batches, so
+/// that each dictionary is only sent when it is first seen or when it changes. Batches whose
+/// encoded message is larger than `max_message_size` are split in smaller batches (dictionaries
+/// are never split), so that messages stay within the limits of the transport (e.g. gRPC).
+pub struct FlightDataEncoder {
+    schema: Arc<Schema>,
+    options: WriteOptions,
+    max_message_size: usize,
+    dictionary_tracker: DictionaryTracker,
+    schema_sent: bool,
+}
+
+impl FlightDataEncoder {
+    /// Creates a new [`FlightDataEncoder`]. Use `usize::MAX` as `max_message_size`
+    /// to never split batches.
+    pub fn new(schema: Arc<Schema>, options: WriteOptions, max_message_size: usize) -> Self {
+        Self {
+            schema,
+            options,
+            max_message_size: max_message_size.max(1),
+            dictionary_tracker: DictionaryTracker::new(false),
+            schema_sent: false,
+        }
+    }
+
+    /// Returns the [`Schema`] of the encoded batches
+    pub fn schema(&self) -> &Arc<Schema> {
+        &self.schema
+    }
+
+    /// Encodes `batch` into one or more [`FlightData`], preceded by the schema
+    /// if this is the first call, and by the dictionaries that were not yet sent.
+    /// # Errors
+    /// This function errors iff the schema of `batch` differs from the encoder's schema.
+    pub fn encode(&mut self, batch: &RecordBatch) -> Result<Vec<FlightData>> {
+        if batch.schema().fields() != self.schema.fields() {
+            return Err(ArrowError::InvalidArgumentError(
+                "The schema of the batch must equal the schema of the encoder".to_string(),
+            ));
+        }
+        let mut messages = vec![];
+        if !self.schema_sent {
+            messages.push(self.encode_schema());
+        }
+        self.encode_batch(batch, &mut messages)?;
+        Ok(messages)
+    }
+
+    /// Returns the [`FlightData`] of the schema, marking it as sent.
+    pub fn encode_schema(&mut self) -> FlightData {
+        self.schema_sent = true;
+        serialize_schema(&self.schema)
+    }
+
+    fn encode_batch(&mut self, batch: &RecordBatch, messages: &mut Vec<FlightData>) -> Result<()> {
+        let (dictionaries, message) =
+            encoded_batch(batch, &mut self.dictionary_tracker, &self.options, None)?;
+        messages.extend(dictionaries.into_iter().map(Into::into));
+
+        let size = message.ipc_message.len() + message.arrow_data.len();
+        let num_rows = batch.num_rows();
+        if size <= self.max_message_size || num_rows <= 1 {
+            messages.push(message.into());
+            return Ok(());
+        }
+
+        // split the batch in chunks that are expected to fit in a message, estimating the size
+        // of each row from the size of the encoded batch. Chunks that are still too large
+        // (e.g. because rows have different sizes) are split again.
+        let chunks = ((size + self.max_message_size - 1) / self.max_message_size).max(2);
+        let chunk_length = (num_rows + chunks - 1) / chunks;
+        let mut offset = 0;
+        while offset < num_rows {
+            let length = chunk_length.min(num_rows - offset);
+            self.encode_batch(&slice(batch, offset, length)?, messages)?;
+            offset += length;
+        }
+        Ok(())
+    }
+}
+
+fn slice(batch: &RecordBatch, offset: usize, length: usize) -> Result<RecordBatch> {
+    let columns = batch
+        .columns()
+        .iter()
+        .map(|array| Arc::from(array.slice(offset, length)))
+        .collect();
+    RecordBatch::try_new(batch.schema().clone(), columns)
+}
+
+/// Returns an [`Iterator`] of [`FlightData`] encoding `batches` with a [`FlightDataEncoder`].
+/// The schema is always the first message, even if there are no batches.
+pub fn encode_iter<I>(
+    schema: Arc<Schema>,
+    batches: I,
+    options: WriteOptions,
+    max_message_size: usize,
+) -> impl Iterator<Item = Result<FlightData>>
+where
+    I: IntoIterator<Item = Result<RecordBatch>>,
+{
+    let mut encoder = FlightDataEncoder::new(schema, options, max_message_size);
+    let schema = encoder.encode_schema();
+    std::iter::once(Ok(schema)).chain(
+        batches
+            .into_iter()
+            .flat_map(move |batch| flatten(batch.and_then(|batch| encoder.encode(&batch)))),
+    )
+}
+
+/// Returns a [`Stream`] of [`FlightData`] encoding `batches` with a [`FlightDataEncoder`].
+/// The schema is always the first message, even if there are no batches.
+pub fn encode_stream<S>(
+    schema: Arc<Schema>,
+    batches: S,
+    options: WriteOptions,
+    max_message_size: usize,
+) -> impl Stream<Item = Result<FlightData>>
+where
+    S: Stream<Item = Result<RecordBatch>>,
+{
+    let mut encoder = FlightDataEncoder::new(schema, options, max_message_size);
+    let schema = encoder.encode_schema();
+    futures::stream::once(async move { Ok(schema) }).chain(batches.flat_map(move |batch| {
+        futures::stream::iter(flatten(batch.and_then(|batch| encoder.encode(&batch))))
+    }))
+}
+
+fn flatten(messages: Result<Vec<FlightData>>) -> Vec<Result<FlightData>> {
+    match messages {
+        Ok(messages) => messages.into_iter().map(Ok).collect(),
+        Err(error) => vec![Err(error)],
+    }
+}
+
+/// Decodes a sequence of [`FlightData`] messages into [`RecordBatch`]es.
+///
+/// The first message must be the schema. Dictionary messages update the dictionaries
+/// used to decode subsequent record batches.
+#[derive(Debug, Default)]
+pub struct FlightDataDecoder {
+    schema: Option<(Arc<Schema>, bool)>,
+    dictionaries: HashMap<usize, Arc<dyn Array>>,
+}
+
+impl FlightDataDecoder {
+    /// Creates a new [`FlightDataDecoder`] that expects the schema as its first message.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new [`FlightDataDecoder`] for a stream whose schema was obtained by other means
+    /// (e.g. from a `FlightInfo`).
+    pub fn with_schema(schema: Arc<Schema>, is_little_endian: bool) -> Self {
+        Self {
+            schema: Some((schema, is_little_endian)),
+            dictionaries: Default::default(),
+        }
+    }
+
+    /// Returns the [`Schema`] of the stream, if it was already received.
+    pub fn schema(&self) -> Option<&Arc<Schema>> {
+        self.schema.as_ref().map(|x| &x.0)
+    }
+
+    /// Decodes a [`FlightData`], returning `Some` when it contains a [`RecordBatch`]
+    /// and `None` when it contains a schema or a dictionary.
+    pub fn decode(&mut self, data: &FlightData) -> Result<Option<RecordBatch>> {
+        let message = ipc::Message::root_as_message(&data.data_header[..])
+            .map_err(|err| ArrowError::Ipc(format!("Unable to get root as message: {:?}", err)))?;
+
+        match message.header_type() {
+            ipc::Message::MessageHeader::Schema => {
+                let ipc_schema = message.header_as_schema().ok_or_else(|| {
+                    ArrowError::Ipc("Unable to read IPC message as schema".to_string())
+                })?;
+                let (schema, is_little_endian) = fb_to_schema(ipc_schema);
+                self.schema = Some((Arc::new(schema), is_little_endian));
+                self.dictionaries.clear();
+                Ok(None)
+            }
+            ipc::Message::MessageHeader::DictionaryBatch => {
+                let (schema, is_little_endian) = self.expect_schema()?;
+                let batch = message.header_as_dictionary_batch().ok_or_else(|| {
+                    ArrowError::Ipc("Unable to read IPC message as dictionary batch".to_string())
+                })?;
+                let mut reader = std::io::Cursor::new(&data.data_body);
+                read_dictionary(
+                    batch,
+                    &schema,
+                    is_little_endian,
+                    &mut self.dictionaries,
+                    &mut reader,
+                    0,
+                )?;
+                Ok(None)
+            }
+            ipc::Message::MessageHeader::RecordBatch => {
+                let (schema, is_little_endian) = self.expect_schema()?;
+                let batch = message.header_as_record_batch().ok_or_else(|| {
+                    ArrowError::Ipc("Unable to read IPC message as record batch".to_string())
+                })?;
+                let mut reader = std::io::Cursor::new(&data.data_body);
+                read_record_batch(
+                    batch,
+                    schema,
+                    None,
+                    is_little_endian,
+                    &self.dictionaries,
+                    message.version(),
+                    &mut reader,
+                    0,
+                )
+                .map(Some)
+            }
+            t => Err(ArrowError::Ipc(format!(
+                "Reading types other than record batches not yet supported, unable to read {:?}",
+                t
+            ))),
+        }
+    }
+
+    fn expect_schema(&self) -> Result<(Arc<Schema>, bool)> {
+        self.schema.clone().ok_or_else(|| {
+            ArrowError::Ipc(
+                "The schema must be received before dictionaries and record batches".to_string(),
+            )
+        })
+    }
+}
+
+/// Returns an [`Iterator`] of [`RecordBatch`]es decoded from `messages` with a [`FlightDataDecoder`].
+pub fn decode_iter<I>(messages: I) -> impl Iterator<Item = Result<RecordBatch>>
+where
+    I: IntoIterator<Item = Result<FlightData>>,
+{
+    let mut decoder = FlightDataDecoder::new();
+    messages
+        .into_iter()
+        .filter_map(move |data| data.and_then(|data| decoder.decode(&data)).transpose())
+}
+
+/// Returns a [`Stream`] of [`RecordBatch`]es decoded from `messages` with a [`FlightDataDecoder`].
+pub fn decode_stream<S>(messages: S) -> impl Stream<Item = Result<RecordBatch>>
+where
+    S: Stream<Item = Result<FlightData>>,
+{
+    let mut decoder = FlightDataDecoder::new();
+    messages.filter_map(move |data| {
+        let batch = data.and_then(|data| decoder.decode(&data)).transpose();
+        async move { batch }
+    })
+}
@@ -28,31 +28,48 @@ pub fn decompress_zstd(_input_buf: &[u8], _output_buf: &mut [u8]) -> Result<()>
     Err(ArrowError::Ipc("The crate was compiled without IPC compression. Use `io_ipc_compression` to read compressed IPC.".to_string()))
 }
 
+/// The zstd level used when none is declared.
+#[cfg(feature = "io_ipc_compression")]
+const DEFAULT_ZSTD_LEVEL: i32 = 17;
+
 #[cfg(feature = "io_ipc_compression")]
 #[cfg_attr(docsrs, doc(cfg(feature = "io_ipc_compression")))]
-pub fn compress_lz4(input_buf: &[u8], output_buf: &mut Vec<u8>) -> Result<()> {
+pub fn compress_lz4(input_buf: &[u8], output_buf: &mut Vec<u8>, level: Option<i32>) -> Result<()> {
     use std::io::Write;
-    let mut encoder = lz4::EncoderBuilder::new().build(output_buf).unwrap();
+    let mut builder = lz4::EncoderBuilder::new();
+    if let Some(level) = level {
+        builder.level(level.max(0) as u32);
+    }
+    let mut encoder = builder.build(output_buf)?;
     encoder.write_all(input_buf)?;
     encoder.finish().1.map_err(|e| e.into())
 }
 
 #[cfg(feature = "io_ipc_compression")]
 #[cfg_attr(docsrs, doc(cfg(feature = "io_ipc_compression")))]
-pub fn compress_zstd(input_buf: &[u8], output_buf: &mut Vec<u8>) -> Result<()> {
+pub fn compress_zstd(input_buf: &[u8], output_buf: &mut Vec<u8>, level: Option<i32>) -> Result<()> {
     use std::io::Write;
-    let mut encoder = zstd::Encoder::new(output_buf, 17)?.auto_finish();
+    let level = level.unwrap_or(DEFAULT_ZSTD_LEVEL);
+    let mut encoder = zstd::Encoder::new(output_buf, level)?.auto_finish();
     encoder.write_all(input_buf).map_err(|e| e.into())
 }
 
 #[cfg(not(feature = "io_ipc_compression"))]
-pub fn compress_lz4(_input_buf: &[u8], _output_buf: &mut Vec<u8>) -> Result<()> {
+pub fn compress_lz4(
+    _input_buf: &[u8],
+    _output_buf: &mut Vec<u8>,
+    _level: Option<i32>,
+) -> Result<()> {
     use crate::error::ArrowError;
     Err(ArrowError::Ipc("The crate was compiled without IPC compression. Use `io_ipc_compression` to write compressed IPC.".to_string()))
 }
 
 #[cfg(not(feature = "io_ipc_compression"))]
-pub fn compress_zstd(_input_buf: &[u8], _output_buf: &mut Vec<u8>) -> Result<()> {
+pub fn compress_zstd(
+    _input_buf: &[u8],
+    _output_buf: &mut Vec<u8>,
+    _level: Option<i32>,
+) -> Result<()> {
     use crate::error::ArrowError;
     Err(ArrowError::Ipc("The crate was compiled without IPC compression. Use `io_ipc_compression` to write compressed IPC.".to_string()))
 }
@@ -66,7 +83,7 @@ mod tests {
     fn round_trip_zstd() {
         let data: Vec<u8> = (0..200u8).map(|x| x % 10).collect();
         let mut buffer = vec![];
-        compress_zstd(&data, &mut buffer).unwrap();
+        compress_zstd(&data, &mut buffer, None).unwrap();
 
         let mut result = vec![0; 200];
         decompress_zstd(&buffer, &mut result).unwrap();
@@ -78,10 +95,29 @@ mod tests {
     fn round_trip_lz4() {
         let data: Vec<u8> = (0..200u8).map(|x| x % 10).collect();
         let mut buffer = vec![];
-        compress_lz4(&data, &mut buffer).unwrap();
+        compress_lz4(&data, &mut buffer, None).unwrap();
 
         let mut result = vec![0; 200];
         decompress_lz4(&buffer, &mut result).unwrap();
         assert_eq!(data, result);
     }
+
+    #[cfg(feature = "io_ipc_compression")]
+    #[test]
+    fn round_trip_levels() {
+        let data: Vec<u8> = (0..200u8).map(|x| x % 10).collect();
+        for level in [1, 9] {
+            let mut buffer = vec![];
+            compress_zstd(&data, &mut buffer, Some(level)).unwrap();
+            let mut result = vec![0; 200];
+            decompress_zstd(&buffer, &mut result).unwrap();
+            assert_eq!(data, result);
+
+            let mut buffer = vec![];
+            compress_lz4(&data, &mut buffer, Some(level)).unwrap();
+            let mut result = vec![0; 200];
+            decompress_lz4(&buffer, &mut result).unwrap();
+            assert_eq!(data, result);
+        }
+    }
 }
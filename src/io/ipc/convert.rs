@@ -68,6 +68,41 @@ pub fn schema_to_fb_offset<'a>(
     builder.finish()
 }
 
+/// Serializes custom key-value `metadata` of a message or footer into `fbb`,
+/// returning `None` when there is no metadata to write.
+pub(crate) fn custom_metadata_to_fb<'a>(
+    fbb: &mut FlatBufferBuilder<'a>,
+    metadata: Option<&BTreeMap<String, String>>,
+) -> Option<WIPOffset<Vector<'a, ForwardsUOffset<ipc::KeyValue<'a>>>>> {
+    let metadata = metadata.filter(|metadata| !metadata.is_empty())?;
+    let kv_vec = metadata
+        .iter()
+        .map(|(k, v)| {
+            let kv_args = ipc::KeyValueArgs {
+                key: Some(fbb.create_string(k.as_str())),
+                value: Some(fbb.create_string(v.as_str())),
+            };
+            ipc::KeyValue::create(fbb, &kv_args)
+        })
+        .collect::<Vec<_>>();
+    Some(fbb.create_vector(&kv_vec))
+}
+
+/// Deserializes the custom key-value metadata of a message or footer.
+pub(crate) fn fb_to_custom_metadata(
+    list: Option<Vector<ForwardsUOffset<ipc::KeyValue>>>,
+) -> BTreeMap<String, String> {
+    list.map(|list| {
+        list.iter()
+            .filter_map(|kv| match (kv.key(), kv.value()) {
+                (Some(k), Some(v)) => Some((k.to_string(), v.to_string())),
+                _ => None,
+            })
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
 fn read_metadata(field: &ipc::Field) -> Metadata {
     if let Some(list) = field.custom_metadata() {
         let mut metadata_map = BTreeMap::default();
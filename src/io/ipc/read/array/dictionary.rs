@@ -26,7 +26,8 @@ pub fn read_dictionary<T: DictionaryKey, R: Read + Seek>(
 where
     Vec<u8>: TryInto<T::Bytes>,
 {
-    let id = field.dict_id().unwrap() as usize;
+    // `Field::dict_id` is not used since it returns `None` for extension types
+    let id = field.dict_id as usize;
     let values = dictionaries
         .get(&id)
         .ok_or_else(|| {
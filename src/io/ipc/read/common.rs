@@ -162,10 +162,33 @@ pub fn read_record_batch<R: Read + Seek>(
     RecordBatch::try_new(schema, columns)
 }
 
+/// Returns the (sorted) projection and the projected [`Schema`] of `schema`.
+/// # Panic
+/// Panics iff the projection is not in increasing order (e.g. `[1, 0]` nor `[0, 1, 1]` are valid)
+pub(super) fn prepare_projection(
+    schema: &Schema,
+    projection: Vec<usize>,
+) -> (Vec<usize>, Arc<Schema>) {
+    projection.windows(2).for_each(|x| {
+        assert!(
+            x[0] < x[1],
+            "The projection on IPC must be ordered and non-overlapping"
+        );
+    });
+    let fields = schema.fields();
+    let fields = projection.iter().map(|x| fields[*x].clone()).collect();
+    let schema = Arc::new(Schema {
+        fields,
+        metadata: schema.metadata().clone(),
+    });
+    (projection, schema)
+}
+
 fn find_first_dict_field_d(id: usize, data_type: &DataType) -> Option<&Field> {
     use DataType::*;
     match data_type {
         Dictionary(_, inner) => find_first_dict_field_d(id, inner.as_ref()),
+        Extension(_, inner, _) => find_first_dict_field_d(id, inner.as_ref()),
         Map(field, _) => find_first_dict_field(id, field.as_ref()),
         List(field) => find_first_dict_field(id, field.as_ref()),
         LargeList(field) => find_first_dict_field(id, field.as_ref()),
@@ -191,7 +214,7 @@ fn find_first_dict_field_d(id: usize, data_type: &DataType) -> Option<&Field> {
 }
 
 fn find_first_dict_field(id: usize, field: &Field) -> Option<&Field> {
-    if let DataType::Dictionary(_, _) = &field.data_type {
+    if let DataType::Dictionary(_, _) = field.data_type.to_logical_type() {
         if field.dict_id as usize == id {
             return Some(field);
         }
@@ -233,9 +256,10 @@ pub fn read_dictionary<R: Read + Seek>(
     // As the dictionary batch does not contain the type of the
     // values array, we need to retrieve this from the schema.
     // Get an array representing this dictionary's values.
-    let dictionary_values: ArrayRef = match first_field.data_type() {
+    let dictionary_values: ArrayRef = match first_field.data_type().to_logical_type() {
         DataType::Dictionary(_, ref value_type) => {
-            // Make a fake schema for the dictionary batch.
+            // Make a fake schema for the dictionary batch. Dictionaries nested in the values
+            // are declared by the fields of `value_type` and must have already been read.
             let schema = Arc::new(Schema {
                 fields: vec![Field::new("", value_type.as_ref().clone(), false)],
                 metadata: HashMap::new(),
@@ -21,6 +21,16 @@ fn read_swapped<T: NativeType, R: Read + Seek>(
     let mut slice = vec![0u8; length * std::mem::size_of::<T>()];
     reader.read_exact(&mut slice)?;
 
+    swap_from_bytes(&slice, buffer, is_little_endian);
+    Ok(())
+}
+
+/// Fills `buffer` with the values in `slice`, whose endianess differs from the machine's.
+fn swap_from_bytes<T: NativeType>(
+    slice: &[u8],
+    buffer: &mut MutableBuffer<T>,
+    is_little_endian: bool,
+) {
     let chunks = slice.chunks_exact(std::mem::size_of::<T>());
     if !is_little_endian {
         // machine is little endian, file is big endian
@@ -28,20 +38,17 @@ fn read_swapped<T: NativeType, R: Read + Seek>(
             .as_mut_slice()
             .iter_mut()
             .zip(chunks)
-            .try_for_each(|(slot, chunk)| {
+            .for_each(|(slot, chunk)| {
                 let a: T::Bytes = match chunk.try_into() {
                     Ok(a) => a,
                     Err(_) => unreachable!(),
                 };
                 *slot = T::from_be_bytes(a);
-                Result::Ok(())
-            })
-            .unwrap();
+            });
     } else {
         // machine is big endian, file is little endian
         todo!("reading little endian files from big endian machines not yet implemented.")
     }
-    Ok(())
 }
 
 fn read_uncompressed_buffer<T: NativeType, R: Read + Seek>(
@@ -85,6 +92,44 @@ fn read_uncompressed_buffer<T: NativeType, R: Read + Seek>(
     Ok(buffer)
 }
 
+/// Decompresses `compressed` into `out_slice`.
+///
+/// `compressed` starts with the uncompressed length of the buffer (as a little endian `i64`).
+/// A length of `-1` declares that the data that follows is not compressed.
+fn decompress(compressed: &[u8], out_slice: &mut [u8], compression: BodyCompression) -> Result<()> {
+    if compressed.len() < 8 {
+        if out_slice.is_empty() {
+            // empty buffers are not required to declare their length
+            return Ok(());
+        }
+        return Err(ArrowError::Ipc(
+            "A compressed IPC buffer must start with its uncompressed length".to_string(),
+        ));
+    }
+    let uncompressed_length = i64::from_le_bytes(compressed[..8].try_into().unwrap());
+    let data = &compressed[8..];
+
+    if uncompressed_length == -1 {
+        if data.len() < out_slice.len() {
+            return Err(ArrowError::Ipc(format!(
+                "The uncompressed IPC buffer has {} bytes but {} bytes were expected",
+                data.len(),
+                out_slice.len()
+            )));
+        }
+        out_slice.copy_from_slice(&data[..out_slice.len()]);
+        return Ok(());
+    }
+
+    match compression.codec() {
+        CompressionType::LZ4_FRAME => compression::decompress_lz4(data, out_slice),
+        CompressionType::ZSTD => compression::decompress_zstd(data, out_slice),
+        _ => Err(ArrowError::NotYetImplemented(
+            "Compression format".to_string(),
+        )),
+    }
+}
+
 fn read_compressed_buffer<T: NativeType, R: Read + Seek>(
     reader: &mut R,
     buffer_length: usize,
@@ -92,12 +137,6 @@ fn read_compressed_buffer<T: NativeType, R: Read + Seek>(
     is_little_endian: bool,
     compression: BodyCompression,
 ) -> Result<MutableBuffer<T>> {
-    if is_little_endian != is_native_little_endian() {
-        return Err(ArrowError::NotYetImplemented(
-            "Reading compressed and big endian IPC".to_string(),
-        ));
-    }
-
     // it is undefined behavior to call read_exact on un-initialized, https://doc.rust-lang.org/std/io/trait.Read.html#tymethod.read
     // see also https://github.com/MaikKlein/ash/issues/354#issue-781730580
     let mut buffer = MutableBuffer::<T>::from_len_zeroed(length);
@@ -107,28 +146,23 @@ fn read_compressed_buffer<T: NativeType, R: Read + Seek>(
     let mut slice = vec![0u8; buffer_length];
     reader.read_exact(&mut slice)?;
 
-    // Safety:
-    // This is safe because T is NativeType, which by definition can be transmuted to u8
-    let out_slice = unsafe {
-        std::slice::from_raw_parts_mut(
-            buffer.as_mut_ptr() as *mut u8,
-            length * std::mem::size_of::<T>(),
-        )
-    };
-
-    match compression.codec() {
-        CompressionType::LZ4_FRAME => {
-            compression::decompress_lz4(&slice[8..], out_slice)?;
-            Ok(buffer)
-        }
-        CompressionType::ZSTD => {
-            compression::decompress_zstd(&slice[8..], out_slice)?;
-            Ok(buffer)
-        }
-        _ => Err(ArrowError::NotYetImplemented(
-            "Compression format".to_string(),
-        )),
+    if is_little_endian == is_native_little_endian() {
+        // Safety:
+        // This is safe because T is NativeType, which by definition can be transmuted to u8
+        let out_slice = unsafe {
+            std::slice::from_raw_parts_mut(
+                buffer.as_mut_ptr() as *mut u8,
+                length * std::mem::size_of::<T>(),
+            )
+        };
+        decompress(&slice, out_slice, compression)?;
+    } else {
+        // slow case where we must decompress and then reverse bits
+        let mut out_slice = vec![0u8; length * std::mem::size_of::<T>()];
+        decompress(&slice, &mut out_slice, compression)?;
+        swap_from_bytes(&out_slice, &mut buffer, is_little_endian);
     }
+    Ok(buffer)
 }
 
 pub fn read_buffer<T: NativeType, R: Read + Seek>(
@@ -183,19 +217,8 @@ fn read_compressed_bitmap<R: Read + Seek>(
     let mut slice = vec![0u8; bytes];
     reader.read_exact(&mut slice)?;
 
-    match compression.codec() {
-        CompressionType::LZ4_FRAME => {
-            compression::decompress_lz4(&slice[8..], &mut buffer)?;
-            Ok(buffer)
-        }
-        CompressionType::ZSTD => {
-            compression::decompress_zstd(&slice[8..], &mut buffer)?;
-            Ok(buffer)
-        }
-        _ => Err(ArrowError::NotYetImplemented(
-            "Non LZ4 compressed IPC".to_string(),
-        )),
-    }
+    decompress(&slice, &mut buffer, compression)?;
+    Ok(buffer)
 }
 
 pub fn read_bitmap<R: Read + Seek>(
@@ -15,7 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::io::{Read, Seek, SeekFrom};
 use std::sync::Arc;
 
@@ -51,6 +51,9 @@ pub struct FileMetadata {
     version: ipc::Schema::MetadataVersion,
 
     is_little_endian: bool,
+
+    /// The custom metadata of the file's footer
+    custom_metadata: BTreeMap<String, String>,
 }
 
 impl FileMetadata {
@@ -58,6 +61,11 @@ impl FileMetadata {
     pub fn schema(&self) -> &Arc<Schema> {
         &self.schema
     }
+
+    /// Returns the custom metadata stored in the file's footer.
+    pub fn custom_metadata(&self) -> &BTreeMap<String, String> {
+        &self.custom_metadata
+    }
 }
 
 /// Arrow File reader
@@ -66,6 +74,7 @@ pub struct FileReader<R: Read + Seek> {
     metadata: FileMetadata,
     current_block: usize,
     projection: Option<(Vec<usize>, Arc<Schema>)>,
+    batch_metadata: BTreeMap<String, String>,
 }
 
 /// Read the IPC file's metadata
@@ -119,6 +128,8 @@ pub fn read_file_metadata<R: Read + Seek>(reader: &mut R) -> Result<FileMetadata
     let (schema, is_little_endian) = convert::fb_to_schema(ipc_schema);
     let schema = Arc::new(schema);
 
+    let custom_metadata = convert::fb_to_custom_metadata(footer.custom_metadata());
+
     let mut dictionaries = Default::default();
 
     for block in footer.dictionaries().unwrap() {
@@ -166,16 +177,17 @@ pub fn read_file_metadata<R: Read + Seek>(reader: &mut R) -> Result<FileMetadata
         total_blocks,
         dictionaries,
         version: footer.version(),
+        custom_metadata,
     })
 }
 
-/// Read the IPC file's metadata
+/// Reads the record batch at `block`, alongside the custom metadata of its message
 pub fn read_batch<R: Read + Seek>(
     reader: &mut R,
     metadata: &FileMetadata,
     projection: Option<(&[usize], Arc<Schema>)>,
     block: usize,
-) -> Result<Option<RecordBatch>> {
+) -> Result<Option<(RecordBatch, BTreeMap<String, String>)>> {
     let block = metadata.blocks[block];
 
     // read length
@@ -210,6 +222,7 @@ pub fn read_batch<R: Read + Seek>(
             let batch = message.header_as_record_batch().ok_or_else(|| {
                 ArrowError::Ipc("Unable to read IPC message as record batch".to_string())
             })?;
+            let custom_metadata = convert::fb_to_custom_metadata(message.custom_metadata());
             read_record_batch(
                 batch,
                 metadata.schema.clone(),
@@ -220,7 +233,7 @@ pub fn read_batch<R: Read + Seek>(
                 reader,
                 block.offset() as u64 + block.metaDataLength() as u64,
             )
-            .map(Some)
+            .map(|batch| Some((batch, custom_metadata)))
         }
         ipc::Message::MessageHeader::NONE => Ok(None),
         t => Err(ArrowError::Ipc(format!(
@@ -235,28 +248,14 @@ impl<R: Read + Seek> FileReader<R> {
     /// # Panic
     /// Panics iff the projection is not in increasing order (e.g. `[1, 0]` nor `[0, 1, 1]` are valid)
     pub fn new(reader: R, metadata: FileMetadata, projection: Option<Vec<usize>>) -> Self {
-        if let Some(projection) = projection.as_ref() {
-            projection.windows(2).for_each(|x| {
-                assert!(
-                    x[0] < x[1],
-                    "The projection on IPC must be ordered and non-overlapping"
-                );
-            });
-        }
-        let projection = projection.map(|projection| {
-            let fields = metadata.schema().fields();
-            let fields = projection.iter().map(|x| fields[*x].clone()).collect();
-            let schema = Arc::new(Schema {
-                fields,
-                metadata: metadata.schema().metadata().clone(),
-            });
-            (projection, schema)
-        });
+        let projection =
+            projection.map(|projection| prepare_projection(metadata.schema(), projection));
         Self {
             reader,
             metadata,
             projection,
             current_block: 0,
+            batch_metadata: Default::default(),
         }
     }
 
@@ -268,6 +267,12 @@ impl<R: Read + Seek> FileReader<R> {
             .unwrap_or(&self.metadata.schema)
     }
 
+    /// Return the custom metadata of the message of the last record batch
+    /// returned by this iterator
+    pub fn batch_metadata(&self) -> &BTreeMap<String, String> {
+        &self.batch_metadata
+    }
+
     /// Consumes this FileReader, returning the underlying reader
     pub fn into_inner(self) -> R {
         self.reader
@@ -290,6 +295,12 @@ impl<R: Read + Seek> Iterator for FileReader<R> {
                     .map(|x| (x.0.as_ref(), x.1.clone())),
                 block,
             )
+            .map(|maybe_batch| {
+                maybe_batch.map(|(batch, custom_metadata)| {
+                    self.batch_metadata = custom_metadata;
+                    batch
+                })
+            })
             .transpose()
         } else {
             None
@@ -15,7 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::io::Read;
 use std::sync::Arc;
 
@@ -107,13 +107,26 @@ impl StreamState {
     }
 }
 
-/// Reads the next item, yielding `None` if the stream is done,
-/// and a [`StreamState`] otherwise.
-pub fn read_next<R: Read>(
+/// The outcome of reading a message from the stream.
+enum Next {
+    /// A live stream without data
+    Waiting,
+    /// A record batch that was deserialized, alongside the custom metadata of its message
+    Batch(RecordBatch, BTreeMap<String, String>),
+    /// A record batch message whose body was skipped
+    Skipped,
+}
+
+/// Reads messages until a record batch is found, yielding `None` if the stream is done.
+/// Dictionary batches are always read, since subsequent record batches may depend on them.
+/// When `skip` is true, the body of the record batch is consumed without being deserialized.
+fn read_next_message<R: Read>(
     reader: &mut R,
     metadata: &StreamMetadata,
     dictionaries: &mut HashMap<usize, Arc<dyn Array>>,
-) -> Result<Option<StreamState>> {
+    projection: Option<(&[usize], Arc<Schema>)>,
+    skip: bool,
+) -> Result<Option<Next>> {
     // determine metadata length
     let mut meta_size: [u8; 4] = [0; 4];
 
@@ -124,7 +137,7 @@ pub fn read_next<R: Read>(
                 // Handle EOF without the "0xFFFFFFFF 0x00000000"
                 // valid according to:
                 // https://arrow.apache.org/docs/format/Columnar.html#ipc-streaming-format
-                Ok(Some(StreamState::Waiting))
+                Ok(Some(Next::Waiting))
             } else {
                 Err(ArrowError::from(e))
             };
@@ -148,8 +161,7 @@ pub fn read_next<R: Read>(
     let mut meta_buffer = vec![0; meta_len as usize];
     reader.read_exact(&mut meta_buffer)?;
 
-    let vecs = &meta_buffer.to_vec();
-    let message = ipc::Message::root_as_message(vecs)
+    let message = ipc::Message::root_as_message(&meta_buffer)
         .map_err(|err| ArrowError::Ipc(format!("Unable to get root as message: {:?}", err)))?;
 
     match message.header_type() {
@@ -160,8 +172,16 @@ pub fn read_next<R: Read>(
             let batch = message.header_as_record_batch().ok_or_else(|| {
                 ArrowError::Ipc("Unable to read IPC message as record batch".to_string())
             })?;
+            let body_length = message.bodyLength() as u64;
+            let custom_metadata = convert::fb_to_custom_metadata(message.custom_metadata());
+
+            if skip {
+                skip_body(reader, body_length)?;
+                return Ok(Some(Next::Skipped));
+            }
+
             // read the block that makes up the record batch into a buffer
-            let mut buf = vec![0; message.bodyLength() as usize];
+            let mut buf = vec![0; body_length as usize];
             reader.read_exact(&mut buf)?;
 
             let mut reader = std::io::Cursor::new(buf);
@@ -169,14 +189,14 @@ pub fn read_next<R: Read>(
             read_record_batch(
                 batch,
                 metadata.schema.clone(),
-                None,
+                projection,
                 metadata.is_little_endian,
                 dictionaries,
                 metadata.version,
                 &mut reader,
                 0,
             )
-            .map(|x| Some(StreamState::Some(x)))
+            .map(|x| Some(Next::Batch(x, custom_metadata)))
         }
         ipc::Message::MessageHeader::DictionaryBatch => {
             let batch = message.header_as_dictionary_batch().ok_or_else(|| {
@@ -198,9 +218,9 @@ pub fn read_next<R: Read>(
             )?;
 
             // read the next message until we encounter a RecordBatch
-            read_next(reader, metadata, dictionaries)
+            read_next_message(reader, metadata, dictionaries, projection, skip)
         }
-        ipc::Message::MessageHeader::NONE => Ok(Some(StreamState::Waiting)),
+        ipc::Message::MessageHeader::NONE => Ok(Some(Next::Waiting)),
         t => Err(ArrowError::Ipc(format!(
             "Reading types other than record batches not yet supported, unable to read {:?} ",
             t
@@ -208,6 +228,18 @@ pub fn read_next<R: Read>(
     }
 }
 
+/// Consumes `length` bytes from `reader` without storing them.
+fn skip_body<R: Read>(reader: &mut R, length: u64) -> Result<()> {
+    let skipped = std::io::copy(&mut reader.by_ref().take(length), &mut std::io::sink())?;
+    if skipped != length {
+        return Err(ArrowError::Ipc(format!(
+            "The message body declares {} bytes but the stream ended after {} bytes",
+            length, skipped
+        )));
+    }
+    Ok(())
+}
+
 /// Arrow Stream reader.
 ///
 /// An [`Iterator`] over an Arrow stream that yields a result of [`StreamState`]s.
@@ -219,6 +251,8 @@ pub struct StreamReader<R: Read> {
     metadata: StreamMetadata,
     dictionaries: HashMap<usize, Arc<dyn Array>>,
     finished: bool,
+    projection: Option<(Vec<usize>, Arc<Schema>)>,
+    batch_metadata: BTreeMap<String, String>,
 }
 
 impl<R: Read> StreamReader<R> {
@@ -226,19 +260,30 @@ impl<R: Read> StreamReader<R> {
     ///
     /// The first message in the stream is the schema, the reader will fail if it does not
     /// encounter a schema.
-    /// To check if the reader is done, use `is_finished(self)`
-    pub fn new(reader: R, metadata: StreamMetadata) -> Self {
+    /// To check if the reader is done, use `is_finished(self)`.
+    /// Use `projection` to only deserialize certain columns; the buffers of the remaining
+    /// columns are skipped.
+    /// # Panic
+    /// Panics iff the projection is not in increasing order (e.g. `[1, 0]` nor `[0, 1, 1]` are valid)
+    pub fn new(reader: R, metadata: StreamMetadata, projection: Option<Vec<usize>>) -> Self {
+        let projection =
+            projection.map(|projection| prepare_projection(&metadata.schema, projection));
         Self {
             reader,
             metadata,
             dictionaries: Default::default(),
             finished: false,
+            projection,
+            batch_metadata: Default::default(),
         }
     }
 
-    /// Return the schema of the stream
+    /// Return the schema of the stream, taking the projection into account
     pub fn schema(&self) -> &Arc<Schema> {
-        &self.metadata.schema
+        self.projection
+            .as_ref()
+            .map(|x| &x.1)
+            .unwrap_or(&self.metadata.schema)
     }
 
     /// Check if the stream is finished
@@ -246,15 +291,60 @@ impl<R: Read> StreamReader<R> {
         self.finished
     }
 
+    /// Skips up to `n` record batches without deserializing them.
+    ///
+    /// Dictionary batches found along the way are still read, since subsequent
+    /// record batches may depend on them.
+    /// Returns the number of skipped record batches, which is smaller than `n`
+    /// when the stream finishes or is waiting for data.
+    pub fn skip_batches(&mut self, n: usize) -> Result<usize> {
+        let mut skipped = 0;
+        while skipped < n && !self.finished {
+            match read_next_message(
+                &mut self.reader,
+                &self.metadata,
+                &mut self.dictionaries,
+                None,
+                true,
+            )? {
+                None => self.finished = true,
+                Some(Next::Waiting) => break,
+                Some(Next::Skipped) | Some(Next::Batch(..)) => skipped += 1,
+            }
+        }
+        Ok(skipped)
+    }
+
+    /// Return the custom metadata of the message of the last record batch
+    /// returned by this iterator
+    pub fn batch_metadata(&self) -> &BTreeMap<String, String> {
+        &self.batch_metadata
+    }
+
     fn maybe_next(&mut self) -> Result<Option<StreamState>> {
         if self.finished {
             return Ok(None);
         }
-        let batch = read_next(&mut self.reader, &self.metadata, &mut self.dictionaries)?;
-        if batch.is_none() {
-            self.finished = true;
-        }
-        Ok(batch)
+        let next = read_next_message(
+            &mut self.reader,
+            &self.metadata,
+            &mut self.dictionaries,
+            self.projection
+                .as_ref()
+                .map(|x| (x.0.as_ref(), x.1.clone())),
+            false,
+        )?;
+        Ok(match next {
+            None => {
+                self.finished = true;
+                None
+            }
+            Some(Next::Batch(batch, custom_metadata)) => {
+                self.batch_metadata = custom_metadata;
+                Some(StreamState::Some(batch))
+            }
+            Some(Next::Waiting) | Some(Next::Skipped) => Some(StreamState::Waiting),
+        })
     }
 }
 
@@ -1,4 +1,7 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
 
 use arrow_format::ipc;
 use arrow_format::ipc::flatbuffers::FlatBufferBuilder;
@@ -7,6 +10,7 @@ use arrow_format::ipc::Message::CompressionType;
 use crate::array::*;
 use crate::datatypes::*;
 use crate::error::{ArrowError, Result};
+use crate::io::ipc::convert::custom_metadata_to_fb;
 use crate::io::ipc::endianess::is_native_little_endian;
 use crate::record_batch::RecordBatch;
 
@@ -21,14 +25,43 @@ pub enum Compression {
     ZSTD,
 }
 
+/// Options declaring how buffers are compressed.
+///
+/// Buffers whose compressed size is not smaller than their uncompressed size
+/// are always written uncompressed, as allowed by the IPC specification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CompressionOptions {
+    /// The codec to use
+    pub codec: Compression,
+    /// The level passed to the codec. When `None`, a codec-specific default is used.
+    pub level: Option<i32>,
+    /// Buffers with less than this number of bytes are written uncompressed.
+    pub min_size: usize,
+}
+
+impl From<Compression> for CompressionOptions {
+    fn from(codec: Compression) -> Self {
+        Self {
+            codec,
+            level: None,
+            min_size: 0,
+        }
+    }
+}
+
 /// Options declaring the behaviour of writing to IPC
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub struct WriteOptions {
-    /// Whether the buffers should be compressed and which codec to use.
+    /// Whether the buffers should be compressed and how.
     /// Note: to use compression the crate must be compiled with feature `io_ipc_compression`.
-    pub compression: Option<Compression>,
+    pub compression: Option<CompressionOptions>,
 }
 
+/// Encodes every dictionary of `array` that was not yet emitted by `dictionary_tracker`
+/// into `encoded_dictionaries`, at any nesting depth.
+///
+/// Dictionaries nested in the values of a dictionary are encoded before it, since
+/// the values of a dictionary can only be read once the dictionaries they refer to are known.
 fn encode_dictionary(
     field: &Field,
     array: &Arc<dyn Array>,
@@ -41,12 +74,12 @@ fn encode_dictionary(
         Utf8 | LargeUtf8 | Binary | LargeBinary | Primitive(_) | Boolean | Null
         | FixedSizeBinary => Ok(()),
         Dictionary(key_type) => match_integer_type!(key_type, |$T| {
-            let dict_id = field
-                .dict_id()
-                .expect("All Dictionary types have `dict_id`");
+            // `Field::dict_id` is not used since it returns `None` for extension types
+            let dict_id = field.dict_id;
 
             let values = array.as_any().downcast_ref::<DictionaryArray<$T>>().unwrap().values();
-            // todo: this is won't work for Dict<Dict<...>>;
+            // the values of a dictionary are not a field of the schema; the dictionaries
+            // nested in them are declared by the fields of their data type.
             let field = Field::new("item", values.data_type().clone(), true);
             encode_dictionary(&field,
                 values,
@@ -73,11 +106,7 @@ fn encode_dictionary(
                 .downcast_ref::<StructArray>()
                 .unwrap()
                 .values();
-            let fields = if let DataType::Struct(fields) = array.data_type() {
-                fields
-            } else {
-                unreachable!()
-            };
+            let fields = StructArray::get_fields(array.data_type());
             fields
                 .iter()
                 .zip(values.iter())
@@ -97,11 +126,7 @@ fn encode_dictionary(
                 .downcast_ref::<ListArray<i32>>()
                 .unwrap()
                 .values();
-            let field = if let DataType::List(field) = field.data_type() {
-                field.as_ref()
-            } else {
-                unreachable!()
-            };
+            let field = ListArray::<i32>::get_child_field(field.data_type());
             encode_dictionary(
                 field,
                 values,
@@ -116,11 +141,7 @@ fn encode_dictionary(
                 .downcast_ref::<ListArray<i64>>()
                 .unwrap()
                 .values();
-            let field = if let DataType::LargeList(field) = field.data_type() {
-                field.as_ref()
-            } else {
-                unreachable!()
-            };
+            let field = ListArray::<i64>::get_child_field(field.data_type());
             encode_dictionary(
                 field,
                 values,
@@ -135,11 +156,7 @@ fn encode_dictionary(
                 .downcast_ref::<FixedSizeListArray>()
                 .unwrap()
                 .values();
-            let field = if let DataType::FixedSizeList(field, _) = field.data_type() {
-                field.as_ref()
-            } else {
-                unreachable!()
-            };
+            let field = FixedSizeListArray::get_child_and_size(field.data_type()).0;
             encode_dictionary(
                 field,
                 values,
@@ -154,11 +171,7 @@ fn encode_dictionary(
                 .downcast_ref::<UnionArray>()
                 .unwrap()
                 .fields();
-            let fields = if let DataType::Union(fields, _, _) = field.data_type() {
-                fields
-            } else {
-                unreachable!()
-            };
+            let fields = UnionArray::get_fields(field.data_type());
             fields
                 .iter()
                 .zip(values.iter())
@@ -174,11 +187,7 @@ fn encode_dictionary(
         }
         Map => {
             let values = array.as_any().downcast_ref::<MapArray>().unwrap().field();
-            let field = if let DataType::Map(field, _) = field.data_type() {
-                field.as_ref()
-            } else {
-                unreachable!()
-            };
+            let field = MapArray::get_field(field.data_type());
             encode_dictionary(
                 field,
                 values,
@@ -190,12 +199,49 @@ fn encode_dictionary(
     }
 }
 
+/// Errors iff `data_type` contains a dictionary whose values are themselves
+/// dictionary-encoded, e.g. `Dictionary(_, Dictionary(_, _))`.
+///
+/// An IPC field declares a single dictionary encoding, so such a type has no representation
+/// in an IPC schema. Dictionaries nested in a child of the values (e.g. the field of a struct
+/// or of a list) are declared by that child and are supported at any depth.
+fn check_dictionaries(data_type: &DataType) -> Result<()> {
+    use DataType::*;
+    match data_type {
+        Dictionary(_, values) => {
+            if let Dictionary(_, _) = values.to_logical_type() {
+                return Err(ArrowError::InvalidArgumentError(format!(
+                    "The Arrow IPC schema can only declare one dictionary encoding per field, \
+                    and thus can't represent {:?}. Wrap the inner dictionary in a struct or a list instead.",
+                    data_type
+                )));
+            }
+            check_dictionaries(values)
+        }
+        Extension(_, inner, _) => check_dictionaries(inner),
+        List(field) | LargeList(field) | FixedSizeList(field, _) | Map(field, _) => {
+            check_dictionaries(field.data_type())
+        }
+        Struct(fields) | Union(fields, _, _) => fields
+            .iter()
+            .try_for_each(|field| check_dictionaries(field.data_type())),
+        _ => Ok(()),
+    }
+}
+
+/// Encodes `batch` and the dictionaries that were not yet emitted by `dictionary_tracker`.
+/// `custom_metadata`, when not empty, is attached to the record batch's message.
 pub fn encoded_batch(
     batch: &RecordBatch,
     dictionary_tracker: &mut DictionaryTracker,
     options: &WriteOptions,
+    custom_metadata: Option<&BTreeMap<String, String>>,
 ) -> Result<(Vec<EncodedData>, EncodedData)> {
     let schema = batch.schema();
+    schema
+        .fields()
+        .iter()
+        .try_for_each(|field| check_dictionaries(field.data_type()))?;
     let mut encoded_dictionaries = Vec::with_capacity(schema.fields().len());
 
     for (field, column) in schema.fields().iter().zip(batch.columns()) {
@@ -208,14 +254,18 @@ pub fn encoded_batch(
         )?;
     }
 
-    let encoded_message = record_batch_to_bytes(batch, options);
+    let encoded_message = record_batch_to_bytes(batch, options, custom_metadata);
 
     Ok((encoded_dictionaries, encoded_message))
 }
 
 /// Write a `RecordBatch` into two sets of bytes, one for the header (ipc::Schema::Message) and the
 /// other for the batch's data
-fn record_batch_to_bytes(batch: &RecordBatch, options: &WriteOptions) -> EncodedData {
+fn record_batch_to_bytes(
+    batch: &RecordBatch,
+    options: &WriteOptions,
+    custom_metadata: Option<&BTreeMap<String, String>>,
+) -> EncodedData {
     let mut fbb = FlatBufferBuilder::new();
 
     let mut nodes: Vec<ipc::Message::FieldNode> = vec![];
@@ -239,7 +289,7 @@ fn record_batch_to_bytes(batch: &RecordBatch, options: &WriteOptions) -> Encoded
     let nodes = fbb.create_vector(&nodes);
 
     let compression = if let Some(compression) = options.compression {
-        let compression = match compression {
+        let compression = match compression.codec {
             Compression::LZ4 => CompressionType::LZ4_FRAME,
             Compression::ZSTD => CompressionType::ZSTD,
         };
@@ -261,12 +311,16 @@ fn record_batch_to_bytes(batch: &RecordBatch, options: &WriteOptions) -> Encoded
         let b = batch_builder.finish();
         b.as_union_value()
     };
+    let custom_metadata = custom_metadata_to_fb(&mut fbb, custom_metadata);
     // create an ipc::Schema::Message
     let mut message = ipc::Message::MessageBuilder::new(&mut fbb);
     message.add_version(ipc::Schema::MetadataVersion::V5);
     message.add_header_type(ipc::Message::MessageHeader::RecordBatch);
     message.add_bodyLength(arrow_data.len() as i64);
     message.add_header(root);
+    if let Some(custom_metadata) = custom_metadata {
+        message.add_custom_metadata(custom_metadata);
+    }
     let root = message.finish();
     fbb.finish(root, None);
     let finished_data = fbb.finished_data();
@@ -307,7 +361,7 @@ fn dictionary_batch_to_bytes(
     let nodes = fbb.create_vector(&nodes);
 
     let compression = if let Some(compression) = options.compression {
-        let compression = match compression {
+        let compression = match compression.codec {
             Compression::LZ4 => CompressionType::LZ4_FRAME,
             Compression::ZSTD => CompressionType::ZSTD,
         };
@@ -380,7 +434,7 @@ impl DictionaryTracker {
     ///   has never been seen before, return `Ok(true)` to indicate that the dictionary was just
     ///   inserted.
     pub fn insert(&mut self, dict_id: i64, array: &Arc<dyn Array>) -> Result<bool> {
-        let values = match array.data_type() {
+        let values = match array.data_type().to_logical_type() {
             DataType::Dictionary(key_type, _) => {
                 match_integer_type!(key_type, |$T| {
                     let array = array
@@ -5,7 +5,7 @@ mod serialize;
 mod stream;
 mod writer;
 
-pub use common::{Compression, WriteOptions};
+pub use common::{Compression, CompressionOptions, WriteOptions};
 pub use schema::schema_to_bytes;
 pub use serialize::{write, write_dictionary};
 pub use stream::StreamWriter;
@@ -10,7 +10,7 @@ use crate::{
 
 use super::super::compression;
 use super::super::endianess::is_native_little_endian;
-use super::common::{pad_to_8, Compression};
+use super::common::{pad_to_8, Compression, CompressionOptions};
 
 fn _write_primitive<T: NativeType>(
     array: &PrimitiveArray<T>,
@@ -18,7 +18,7 @@ fn _write_primitive<T: NativeType>(
     arrow_data: &mut Vec<u8>,
     offset: &mut i64,
     is_little_endian: bool,
-    compression: Option<Compression>,
+    compression: Option<CompressionOptions>,
 ) {
     write_bitmap(
         array.validity(),
@@ -45,7 +45,7 @@ fn write_primitive<T: NativeType>(
     arrow_data: &mut Vec<u8>,
     offset: &mut i64,
     is_little_endian: bool,
-    compression: Option<Compression>,
+    compression: Option<CompressionOptions>,
 ) {
     let array = array.as_any().downcast_ref::<PrimitiveArray<T>>().unwrap();
     _write_primitive(
@@ -64,7 +64,7 @@ fn write_boolean(
     arrow_data: &mut Vec<u8>,
     offset: &mut i64,
     _: bool,
-    compression: Option<Compression>,
+    compression: Option<CompressionOptions>,
 ) {
     let array = array.as_any().downcast_ref::<BooleanArray>().unwrap();
 
@@ -95,7 +95,7 @@ fn write_generic_binary<O: Offset>(
     arrow_data: &mut Vec<u8>,
     offset: &mut i64,
     is_little_endian: bool,
-    compression: Option<Compression>,
+    compression: Option<CompressionOptions>,
 ) {
     write_bitmap(
         validity,
@@ -143,7 +143,7 @@ fn write_binary<O: Offset>(
     arrow_data: &mut Vec<u8>,
     offset: &mut i64,
     is_little_endian: bool,
-    compression: Option<Compression>,
+    compression: Option<CompressionOptions>,
 ) {
     let array = array.as_any().downcast_ref::<BinaryArray<O>>().unwrap();
     write_generic_binary(
@@ -164,7 +164,7 @@ fn write_utf8<O: Offset>(
     arrow_data: &mut Vec<u8>,
     offset: &mut i64,
     is_little_endian: bool,
-    compression: Option<Compression>,
+    compression: Option<CompressionOptions>,
 ) {
     let array = array.as_any().downcast_ref::<Utf8Array<O>>().unwrap();
     write_generic_binary(
@@ -185,7 +185,7 @@ fn write_fixed_size_binary(
     arrow_data: &mut Vec<u8>,
     offset: &mut i64,
     _is_little_endian: bool,
-    compression: Option<Compression>,
+    compression: Option<CompressionOptions>,
 ) {
     let array = array
         .as_any()
@@ -209,7 +209,7 @@ fn write_list<O: Offset>(
     nodes: &mut Vec<Message::FieldNode>,
     offset: &mut i64,
     is_little_endian: bool,
-    compression: Option<Compression>,
+    compression: Option<CompressionOptions>,
 ) {
     let array = array.as_any().downcast_ref::<ListArray<O>>().unwrap();
     let offsets = array.offsets();
@@ -267,7 +267,7 @@ pub fn write_struct(
     nodes: &mut Vec<Message::FieldNode>,
     offset: &mut i64,
     is_little_endian: bool,
-    compression: Option<Compression>,
+    compression: Option<CompressionOptions>,
 ) {
     let array = array.as_any().downcast_ref::<StructArray>().unwrap();
     write_bitmap(
@@ -298,7 +298,7 @@ pub fn write_union(
     nodes: &mut Vec<Message::FieldNode>,
     offset: &mut i64,
     is_little_endian: bool,
-    compression: Option<Compression>,
+    compression: Option<CompressionOptions>,
 ) {
     let array = array.as_any().downcast_ref::<UnionArray>().unwrap();
 
@@ -341,7 +341,7 @@ fn write_map(
     nodes: &mut Vec<Message::FieldNode>,
     offset: &mut i64,
     is_little_endian: bool,
-    compression: Option<Compression>,
+    compression: Option<CompressionOptions>,
 ) {
     let array = array.as_any().downcast_ref::<MapArray>().unwrap();
     let offsets = array.offsets();
@@ -399,7 +399,7 @@ fn write_fixed_size_list(
     nodes: &mut Vec<Message::FieldNode>,
     offset: &mut i64,
     is_little_endian: bool,
-    compression: Option<Compression>,
+    compression: Option<CompressionOptions>,
 ) {
     let array = array.as_any().downcast_ref::<FixedSizeListArray>().unwrap();
     write_bitmap(
@@ -430,7 +430,7 @@ pub fn _write_dictionary<K: DictionaryKey>(
     nodes: &mut Vec<Message::FieldNode>,
     offset: &mut i64,
     is_little_endian: bool,
-    compression: Option<Compression>,
+    compression: Option<CompressionOptions>,
     write_keys: bool,
 ) -> usize {
     let array = array.as_any().downcast_ref::<DictionaryArray<K>>().unwrap();
@@ -466,7 +466,7 @@ pub fn write_dictionary(
     nodes: &mut Vec<Message::FieldNode>,
     offset: &mut i64,
     is_little_endian: bool,
-    compression: Option<Compression>,
+    compression: Option<CompressionOptions>,
     write_keys: bool,
 ) -> usize {
     match array.data_type() {
@@ -495,7 +495,7 @@ pub fn write(
     nodes: &mut Vec<Message::FieldNode>,
     offset: &mut i64,
     is_little_endian: bool,
-    compression: Option<Compression>,
+    compression: Option<CompressionOptions>,
 ) {
     nodes.push(Message::FieldNode::new(
         array.len() as i64,
@@ -640,19 +640,11 @@ fn write_bytes(
     buffers: &mut Vec<Schema::Buffer>,
     arrow_data: &mut Vec<u8>,
     offset: &mut i64,
-    compression: Option<Compression>,
+    compression: Option<CompressionOptions>,
 ) {
     let start = arrow_data.len();
     if let Some(compression) = compression {
-        arrow_data.extend_from_slice(&(bytes.len() as i64).to_le_bytes());
-        match compression {
-            Compression::LZ4 => {
-                compression::compress_lz4(bytes, arrow_data).unwrap();
-            }
-            Compression::ZSTD => {
-                compression::compress_zstd(bytes, arrow_data).unwrap();
-            }
-        }
+        compress(bytes, arrow_data, compression);
     } else {
         arrow_data.extend_from_slice(bytes);
     };
@@ -670,7 +662,7 @@ fn write_bitmap(
     buffers: &mut Vec<Schema::Buffer>,
     arrow_data: &mut Vec<u8>,
     offset: &mut i64,
-    compression: Option<Compression>,
+    compression: Option<CompressionOptions>,
 ) {
     match bitmap {
         Some(bitmap) => {
@@ -700,7 +692,7 @@ fn write_buffer<T: NativeType>(
     arrow_data: &mut Vec<u8>,
     offset: &mut i64,
     is_little_endian: bool,
-    compression: Option<Compression>,
+    compression: Option<CompressionOptions>,
 ) {
     let start = arrow_data.len();
     if let Some(compression) = compression {
@@ -740,28 +732,39 @@ fn _write_compressed_buffer_from_iter<T: NativeType, I: TrustedLen<Item = T>>(
     buffer: I,
     arrow_data: &mut Vec<u8>,
     is_little_endian: bool,
-    compression: Compression,
+    compression: CompressionOptions,
 ) {
     let len = buffer.size_hint().0;
     let mut swapped = Vec::with_capacity(len * std::mem::size_of::<T>());
-    if is_little_endian {
-        buffer
-            .map(|x| T::to_le_bytes(&x))
-            .for_each(|x| swapped.extend_from_slice(x.as_ref()));
-    } else {
-        buffer
-            .map(|x| T::to_be_bytes(&x))
-            .for_each(|x| swapped.extend_from_slice(x.as_ref()))
-    };
-    arrow_data.extend_from_slice(&(swapped.len() as i64).to_le_bytes());
-    match compression {
-        Compression::LZ4 => {
-            compression::compress_lz4(&swapped, arrow_data).unwrap();
+    _write_buffer_from_iter(buffer, &mut swapped, is_little_endian);
+    compress(&swapped, arrow_data, compression);
+}
+
+/// Writes `bytes` to `arrow_data` prefixed by their uncompressed length, compressing them with `compression`.
+///
+/// Buffers smaller than `compression.min_size`, or whose compressed size would not be
+/// smaller than their uncompressed size, are written uncompressed and prefixed by `-1`,
+/// as declared in the IPC specification.
+fn compress(bytes: &[u8], arrow_data: &mut Vec<u8>, compression: CompressionOptions) {
+    let start = arrow_data.len();
+    if bytes.len() >= compression.min_size {
+        arrow_data.extend_from_slice(&(bytes.len() as i64).to_le_bytes());
+        match compression.codec {
+            Compression::LZ4 => {
+                compression::compress_lz4(bytes, arrow_data, compression.level).unwrap();
+            }
+            Compression::ZSTD => {
+                compression::compress_zstd(bytes, arrow_data, compression.level).unwrap();
+            }
         }
-        Compression::ZSTD => {
-            compression::compress_zstd(&swapped, arrow_data).unwrap();
+        if arrow_data.len() - start - 8 < bytes.len() {
+            return;
         }
+        // compression does not help: write the buffer uncompressed instead
+        arrow_data.truncate(start);
     }
+    arrow_data.extend_from_slice(&(-1i64).to_le_bytes());
+    arrow_data.extend_from_slice(bytes);
 }
 
 fn _write_buffer<T: NativeType>(buffer: &[T], arrow_data: &mut Vec<u8>, is_little_endian: bool) {
@@ -783,7 +786,7 @@ fn _write_compressed_buffer<T: NativeType>(
     buffer: &[T],
     arrow_data: &mut Vec<u8>,
     is_little_endian: bool,
-    compression: Compression,
+    compression: CompressionOptions,
 ) {
     if is_little_endian == is_native_little_endian() {
         let bytes = unsafe {
@@ -792,17 +795,15 @@ fn _write_compressed_buffer<T: NativeType>(
                 buffer.len() * std::mem::size_of::<T>(),
             )
         };
-        arrow_data.extend_from_slice(&(bytes.len() as i64).to_le_bytes());
-        match compression {
-            Compression::LZ4 => {
-                compression::compress_lz4(bytes, arrow_data).unwrap();
-            }
-            Compression::ZSTD => {
-                compression::compress_zstd(bytes, arrow_data).unwrap();
-            }
-        }
+        compress(bytes, arrow_data, compression);
     } else {
-        todo!()
+        // the bytes must be swapped before being compressed
+        _write_compressed_buffer_from_iter(
+            buffer.iter().copied(),
+            arrow_data,
+            is_little_endian,
+            compression,
+        )
     }
 }
 
@@ -814,7 +815,7 @@ fn write_buffer_from_iter<T: NativeType, I: TrustedLen<Item = T>>(
     arrow_data: &mut Vec<u8>,
     offset: &mut i64,
     is_little_endian: bool,
-    compression: Option<Compression>,
+    compression: Option<CompressionOptions>,
 ) {
     let start = arrow_data.len();
 
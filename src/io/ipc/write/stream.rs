@@ -20,6 +20,7 @@
 //! The `FileWriter` and `StreamWriter` have similar interfaces,
 //! however the `FileWriter` expects a reader that supports `Seek`ing
 
+use std::collections::BTreeMap;
 use std::io::Write;
 
 use super::common::{encoded_batch, DictionaryTracker, EncodedData, WriteOptions};
@@ -66,14 +67,36 @@ impl<W: Write> StreamWriter<W> {
 
     /// Write a record batch to the stream
     pub fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        self.write_batch(batch, None)
+    }
+
+    /// Write a record batch to the stream, attaching `metadata` as the
+    /// custom metadata of its message.
+    pub fn write_with_metadata(
+        &mut self,
+        batch: &RecordBatch,
+        metadata: &BTreeMap<String, String>,
+    ) -> Result<()> {
+        self.write_batch(batch, Some(metadata))
+    }
+
+    fn write_batch(
+        &mut self,
+        batch: &RecordBatch,
+        metadata: Option<&BTreeMap<String, String>>,
+    ) -> Result<()> {
         if self.finished {
             return Err(ArrowError::Ipc(
                 "Cannot write record batch to stream writer as it is closed".to_string(),
             ));
         }
 
-        let (encoded_dictionaries, encoded_message) =
-            encoded_batch(batch, &mut self.dictionary_tracker, &self.write_options)?;
+        let (encoded_dictionaries, encoded_message) = encoded_batch(
+            batch,
+            &mut self.dictionary_tracker,
+            &self.write_options,
+            metadata,
+        )?;
 
         for encoded_dictionary in encoded_dictionaries {
             write_message(&mut self.writer, encoded_dictionary)?;
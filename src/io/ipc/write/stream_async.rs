@@ -1,4 +1,6 @@
 //! `async` writing of arrow streams
+use std::collections::BTreeMap;
+
 use futures::AsyncWrite;
 
 pub use super::common::WriteOptions;
@@ -45,6 +47,24 @@ impl<W: AsyncWrite + Unpin + Send> StreamWriter<W> {
 
     /// Writes a [`RecordBatch`] to the stream
     pub async fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        self.write_batch(batch, None).await
+    }
+
+    /// Writes a [`RecordBatch`] to the stream, attaching `metadata` as the
+    /// custom metadata of its message.
+    pub async fn write_with_metadata(
+        &mut self,
+        batch: &RecordBatch,
+        metadata: &BTreeMap<String, String>,
+    ) -> Result<()> {
+        self.write_batch(batch, Some(metadata)).await
+    }
+
+    async fn write_batch(
+        &mut self,
+        batch: &RecordBatch,
+        metadata: Option<&BTreeMap<String, String>>,
+    ) -> Result<()> {
         if self.finished {
             return Err(ArrowError::Ipc(
                 "Cannot write record batch to stream writer as it is closed".to_string(),
@@ -52,8 +72,12 @@ impl<W: AsyncWrite + Unpin + Send> StreamWriter<W> {
         }
 
         // todo: move this out of the `async` since this is blocking.
-        let (encoded_dictionaries, encoded_message) =
-            encoded_batch(batch, &mut self.dictionary_tracker, &self.write_options)?;
+        let (encoded_dictionaries, encoded_message) = encoded_batch(
+            batch,
+            &mut self.dictionary_tracker,
+            &self.write_options,
+            metadata,
+        )?;
 
         for encoded_dictionary in encoded_dictionaries {
             write_message(&mut self.writer, encoded_dictionary).await?;
@@ -20,6 +20,7 @@
 //! The `FileWriter` and `StreamWriter` have similar interfaces,
 //! however the `FileWriter` expects a reader that supports `Seek`ing
 
+use std::collections::BTreeMap;
 use std::io::Write;
 
 use arrow_format::ipc;
@@ -88,6 +89,24 @@ impl<W: Write> FileWriter<W> {
 
     /// Write a record batch to the file
     pub fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        self.write_batch(batch, None)
+    }
+
+    /// Write a record batch to the file, attaching `metadata` as the
+    /// custom metadata of its message.
+    pub fn write_with_metadata(
+        &mut self,
+        batch: &RecordBatch,
+        metadata: &BTreeMap<String, String>,
+    ) -> Result<()> {
+        self.write_batch(batch, Some(metadata))
+    }
+
+    fn write_batch(
+        &mut self,
+        batch: &RecordBatch,
+        metadata: Option<&BTreeMap<String, String>>,
+    ) -> Result<()> {
         if self.finished {
             return Err(ArrowError::Ipc(
                 "Cannot write record batch to file writer as it is closed".to_string(),
@@ -95,7 +114,7 @@ impl<W: Write> FileWriter<W> {
         }
 
         let (encoded_dictionaries, encoded_message) =
-            encoded_batch(batch, &mut self.dictionary_tracker, &self.options)?;
+            encoded_batch(batch, &mut self.dictionary_tracker, &self.options, metadata)?;
 
         for encoded_dictionary in encoded_dictionaries {
             let (meta, data) = write_message(&mut self.writer, encoded_dictionary)?;
@@ -119,6 +138,16 @@ impl<W: Write> FileWriter<W> {
 
     /// Write footer and closing tag, then mark the writer as done
     pub fn finish(&mut self) -> Result<()> {
+        self.finish_footer(None)
+    }
+
+    /// Write footer with `metadata` as its custom metadata and closing tag,
+    /// then mark the writer as done
+    pub fn finish_with_metadata(&mut self, metadata: &BTreeMap<String, String>) -> Result<()> {
+        self.finish_footer(Some(metadata))
+    }
+
+    fn finish_footer(&mut self, metadata: Option<&BTreeMap<String, String>>) -> Result<()> {
         // write EOS
         write_continuation(&mut self.writer, 0)?;
 
@@ -126,6 +155,7 @@ impl<W: Write> FileWriter<W> {
         let dictionaries = fbb.create_vector(&self.dictionary_blocks);
         let record_batches = fbb.create_vector(&self.record_blocks);
         let schema = convert::schema_to_fb_offset(&mut fbb, &self.schema);
+        let custom_metadata = convert::custom_metadata_to_fb(&mut fbb, metadata);
 
         let root = {
             let mut footer_builder = ipc::File::FooterBuilder::new(&mut fbb);
@@ -133,6 +163,9 @@ impl<W: Write> FileWriter<W> {
             footer_builder.add_schema(schema);
             footer_builder.add_dictionaries(dictionaries);
             footer_builder.add_recordBatches(record_batches);
+            if let Some(custom_metadata) = custom_metadata {
+                footer_builder.add_custom_metadata(custom_metadata);
+            }
             footer_builder.finish()
         };
         fbb.finish(root, None);
@@ -9,6 +9,26 @@ pub use write::*;
 
 use crate::error::ArrowError;
 
+/// How binary values ([`DataType::Binary`](crate::datatypes::DataType::Binary),
+/// [`DataType::LargeBinary`](crate::datatypes::DataType::LargeBinary) and
+/// [`DataType::FixedSizeBinary`](crate::datatypes::DataType::FixedSizeBinary))
+/// are represented as JSON strings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BinaryFormat {
+    /// The bytes are the (utf8) bytes of the string. Invalid utf8 is replaced when writing.
+    Utf8,
+    /// The bytes are encoded as (standard) base64
+    Base64,
+    /// The bytes are encoded as lower case hexadecimal
+    Hex,
+}
+
+impl Default for BinaryFormat {
+    fn default() -> Self {
+        Self::Utf8
+    }
+}
+
 impl From<serde_json::error::Error> for ArrowError {
     fn from(error: serde_json::error::Error) -> Self {
         ArrowError::External("".to_string(), Box::new(error))
@@ -18,20 +18,25 @@
 use std::hash::Hasher;
 use std::{collections::hash_map::DefaultHasher, sync::Arc};
 
+use chrono::{Datelike, Timelike};
 use hash_hasher::HashedMap;
 use indexmap::map::IndexMap as HashMap;
 use num_traits::NumCast;
 use serde_json::Value;
 
-use crate::types::NaturalDataType;
+use crate::types::{days_ms, months_days_ns, NaturalDataType};
 use crate::{
     array::*,
     bitmap::MutableBitmap,
     buffer::MutableBuffer,
-    datatypes::{DataType, IntervalUnit},
+    datatypes::{DataType, IntervalUnit, TimeUnit},
+    temporal_conversions::EPOCH_DAYS_FROM_CE,
     types::NativeType,
 };
 
+use super::super::write::{TIMESTAMP_FORMAT, TIMESTAMP_TZ_FORMAT};
+use super::super::BinaryFormat;
+
 /// A function that converts a &Value into an optional tuple of a byte slice and a Value.
 /// This is used to create a dictionary, where the hashing depends on the DataType of the child object.
 type Extract = Box<dyn Fn(&Value) -> Option<(u64, &Value)>>;
@@ -99,12 +104,181 @@ fn read_float<T: NativeType + NaturalDataType + NumCast>(
     PrimitiveArray::from_trusted_len_iter(iter).to(data_type)
 }
 
-fn read_binary<O: Offset>(rows: &[&Value]) -> BinaryArray<O> {
+#[inline]
+pub(super) fn decode_str(value: &str, format: BinaryFormat) -> Option<Vec<u8>> {
+    match format {
+        BinaryFormat::Utf8 => Some(value.as_bytes().to_vec()),
+        BinaryFormat::Base64 => base64::decode(value).ok(),
+        BinaryFormat::Hex => hex::decode(value).ok(),
+    }
+}
+
+fn decode_binary(value: &Value, format: BinaryFormat) -> Option<Vec<u8>> {
+    match value {
+        Value::String(v) => decode_str(v, format),
+        _ => None,
+    }
+}
+
+fn read_binary<O: Offset>(rows: &[&Value], format: BinaryFormat) -> BinaryArray<O> {
+    let iter = rows.iter().map(|row| decode_binary(row, format));
+    BinaryArray::from_trusted_len_iter(iter)
+}
+
+fn read_fixed_size_binary(
+    rows: &[&Value],
+    data_type: DataType,
+    format: BinaryFormat,
+) -> FixedSizeBinaryArray {
+    let size = FixedSizeBinaryArray::get_size(&data_type);
+    let mut validity = MutableBitmap::with_capacity(rows.len());
+    let mut values = MutableBuffer::<u8>::with_capacity(rows.len() * size);
+    rows.iter().for_each(|row| {
+        // values of a different size are read as nulls
+        match decode_binary(row, format).filter(|x| x.len() == size) {
+            Some(value) => {
+                values.extend_from_slice(&value);
+                validity.push(true);
+            }
+            None => {
+                values.extend_constant(size, 0);
+                validity.push(false);
+            }
+        }
+    });
+    FixedSizeBinaryArray::from_data(data_type, values.into(), validity.into())
+}
+
+#[inline]
+pub(super) fn parse_date(value: &str) -> Option<i32> {
+    value
+        .parse::<chrono::NaiveDate>()
+        .ok()
+        .map(|x| x.num_days_from_ce() - EPOCH_DAYS_FROM_CE)
+}
+
+#[inline]
+pub(super) fn parse_time(value: &str, time_unit: TimeUnit) -> Option<i64> {
+    value.parse::<chrono::NaiveTime>().ok().map(|x| {
+        let seconds = x.num_seconds_from_midnight() as i64;
+        let nanoseconds = x.nanosecond() as i64;
+        match time_unit {
+            TimeUnit::Second => seconds,
+            TimeUnit::Millisecond => seconds * 1_000 + nanoseconds / 1_000_000,
+            TimeUnit::Microsecond => seconds * 1_000_000 + nanoseconds / 1_000,
+            TimeUnit::Nanosecond => seconds * 1_000_000_000 + nanoseconds,
+        }
+    })
+}
+
+#[inline]
+pub(super) fn parse_timestamp(value: &str, time_unit: TimeUnit) -> Option<i64> {
+    let datetime = chrono::DateTime::parse_from_str(value, TIMESTAMP_TZ_FORMAT)
+        .map(|x| x.naive_utc())
+        .or_else(|_| chrono::NaiveDateTime::parse_from_str(value, TIMESTAMP_FORMAT))
+        .ok()?;
+    let seconds = datetime.timestamp();
+    let nanoseconds = datetime.timestamp_subsec_nanos() as i64;
+    match time_unit {
+        TimeUnit::Second => Some(seconds),
+        TimeUnit::Millisecond => seconds
+            .checked_mul(1_000)
+            .map(|x| x + nanoseconds / 1_000_000),
+        TimeUnit::Microsecond => seconds
+            .checked_mul(1_000_000)
+            .map(|x| x + nanoseconds / 1_000),
+        TimeUnit::Nanosecond => seconds.checked_mul(1_000_000_000).map(|x| x + nanoseconds),
+    }
+}
+
+/// Reads a temporal type from its ISO 8601 representation (a string) or its integer representation
+fn read_temporal<T: NativeType + NaturalDataType + NumCast, F: Fn(&str) -> Option<i64>>(
+    rows: &[&Value],
+    data_type: DataType,
+    parse: F,
+) -> PrimitiveArray<T> {
+    let iter = rows.iter().map(|row| match row {
+        Value::Number(number) => number.as_i64().and_then(num_traits::cast::<i64, T>),
+        Value::String(value) => parse(value).and_then(num_traits::cast::<i64, T>),
+        _ => None,
+    });
+    PrimitiveArray::from_trusted_len_iter(iter).to(data_type)
+}
+
+#[inline]
+pub(super) fn parse_decimal(value: &str, scale: usize) -> Option<i128> {
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(value) => (true, value),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let (integer, fraction) = match value.find('.') {
+        Some(i) => (&value[..i], &value[i + 1..]),
+        None => (value, ""),
+    };
+    if (integer.is_empty() && fraction.is_empty()) || fraction.len() > scale {
+        return None;
+    }
+    let integer = if integer.is_empty() {
+        0
+    } else {
+        integer.parse::<u128>().ok()? as i128
+    };
+    let fraction = if fraction.is_empty() {
+        0
+    } else {
+        fraction.parse::<u128>().ok()? as i128 * 10i128.pow((scale - fraction.len()) as u32)
+    };
+    let value = integer
+        .checked_mul(10i128.pow(scale as u32))?
+        .checked_add(fraction)?;
+    Some(if negative { -value } else { value })
+}
+
+fn read_decimal(rows: &[&Value], data_type: DataType) -> PrimitiveArray<i128> {
+    let scale = if let DataType::Decimal(_, scale) = data_type {
+        scale
+    } else {
+        unreachable!()
+    };
     let iter = rows.iter().map(|row| match row {
-        Value::String(v) => Some(v.as_bytes()),
+        Value::String(value) => parse_decimal(value, scale),
+        Value::Number(number) => match number.as_i64() {
+            Some(x) => (x as i128).checked_mul(10i128.pow(scale as u32)),
+            None => number
+                .as_f64()
+                .map(|x| (x * 10f64.powi(scale as i32)).round() as i128),
+        },
         _ => None,
     });
-    BinaryArray::from_trusted_len_iter(iter)
+    PrimitiveArray::from_trusted_len_iter(iter).to(data_type)
+}
+
+#[inline]
+fn get_i64(value: &serde_json::Map<String, Value>, key: &str) -> Option<i64> {
+    value.get(key).and_then(|x| x.as_i64())
+}
+
+fn read_days_ms(rows: &[&Value], data_type: DataType) -> PrimitiveArray<days_ms> {
+    let iter = rows.iter().map(|row| match row {
+        Value::Object(value) => Some(days_ms::new(
+            get_i64(value, "days")? as i32,
+            get_i64(value, "milliseconds")? as i32,
+        )),
+        _ => None,
+    });
+    PrimitiveArray::from_trusted_len_iter(iter).to(data_type)
+}
+
+fn read_months_days_ns(rows: &[&Value], data_type: DataType) -> PrimitiveArray<months_days_ns> {
+    let iter = rows.iter().map(|row| match row {
+        Value::Object(value) => Some(months_days_ns::new(
+            get_i64(value, "months")? as i32,
+            get_i64(value, "days")? as i32,
+            get_i64(value, "nanoseconds")?,
+        )),
+        _ => None,
+    });
+    PrimitiveArray::from_trusted_len_iter(iter).to(data_type)
 }
 
 fn read_boolean(rows: &[&Value]) -> BooleanArray {
@@ -125,7 +299,11 @@ fn read_utf8<O: Offset>(rows: &[&Value]) -> Utf8Array<O> {
     Utf8Array::<O>::from_trusted_len_iter(iter)
 }
 
-fn read_list<O: Offset>(rows: &[&Value], data_type: DataType) -> ListArray<O> {
+fn read_list<O: Offset>(
+    rows: &[&Value],
+    data_type: DataType,
+    format: BinaryFormat,
+) -> ListArray<O> {
     let child = ListArray::<O>::get_child_type(&data_type);
 
     let mut validity = MutableBitmap::with_capacity(rows.len());
@@ -150,12 +328,12 @@ fn read_list<O: Offset>(rows: &[&Value], data_type: DataType) -> ListArray<O> {
         }
     });
 
-    let values = read(&inner, child.clone());
+    let values = read(&inner, child.clone(), format);
 
     ListArray::<O>::from_data(data_type, offsets.into(), values, validity.into())
 }
 
-fn read_struct(rows: &[&Value], data_type: DataType) -> StructArray {
+fn read_struct(rows: &[&Value], data_type: DataType, format: BinaryFormat) -> StructArray {
     let fields = StructArray::get_fields(&data_type);
 
     let mut values = fields
@@ -185,13 +363,165 @@ fn read_struct(rows: &[&Value], data_type: DataType) -> StructArray {
 
     let values = values
         .into_iter()
-        .map(|(_, (data_type, values))| read(&values, data_type.clone()))
+        .map(|(_, (data_type, values))| read(&values, data_type.clone(), format))
         .collect::<Vec<_>>();
 
     StructArray::from_data(data_type, values, None)
 }
 
-fn read_dictionary<K: DictionaryKey>(rows: &[&Value], data_type: DataType) -> DictionaryArray<K> {
+fn read_fixed_size_list(
+    rows: &[&Value],
+    data_type: DataType,
+    format: BinaryFormat,
+) -> FixedSizeListArray {
+    let (child, size) = FixedSizeListArray::get_child_and_size(&data_type);
+
+    let mut validity = MutableBitmap::with_capacity(rows.len());
+    let mut inner = Vec::<&Value>::with_capacity(rows.len() * size);
+    rows.iter().for_each(|row| match row {
+        Value::Array(value) if value.len() == size => {
+            inner.extend(value.iter());
+            validity.push(true);
+        }
+        _ => {
+            // lists of a different size are read as nulls
+            inner.extend(std::iter::repeat(&Value::Null).take(size));
+            validity.push(false);
+        }
+    });
+
+    let values = read(&inner, child.data_type().clone(), format);
+    FixedSizeListArray::from_data(data_type, values, validity.into())
+}
+
+fn read_map(rows: &[&Value], data_type: DataType, format: BinaryFormat) -> MapArray {
+    let entries = MapArray::get_field(&data_type).data_type().clone();
+    let fields = StructArray::get_fields(&entries);
+    let (key_type, value_type) = (fields[0].data_type(), fields[1].data_type());
+    let is_string_key = matches!(key_type, DataType::Utf8 | DataType::LargeUtf8);
+
+    let mut validity = MutableBitmap::with_capacity(rows.len());
+    let mut keys = Vec::<Value>::with_capacity(rows.len());
+    let mut values = Vec::<&Value>::with_capacity(rows.len());
+    let mut offsets = MutableBuffer::<i32>::with_capacity(rows.len() + 1);
+    offsets.push(0);
+    rows.iter().for_each(|row| {
+        if let Value::Object(value) = row {
+            value.iter().for_each(|(key, value)| {
+                // keys that are not strings are written as their JSON text
+                let key = if is_string_key {
+                    Value::String(key.clone())
+                } else {
+                    serde_json::from_str(key).unwrap_or(Value::Null)
+                };
+                keys.push(key);
+                values.push(value);
+            });
+            validity.push(true);
+        } else {
+            validity.push(false);
+        }
+        offsets.push(keys.len() as i32);
+    });
+
+    let keys = keys.iter().collect::<Vec<_>>();
+    let keys = read(&keys, key_type.clone(), format);
+    let values = read(&values, value_type.clone(), format);
+    let entries = StructArray::from_data(entries, vec![keys, values], None);
+
+    MapArray::from_data(
+        data_type,
+        offsets.into(),
+        Arc::new(entries),
+        validity.into(),
+    )
+}
+
+/// Whether `value` is a natural JSON representation of `data_type`.
+fn is_natural(value: &Value, data_type: &DataType) -> bool {
+    use DataType::*;
+    let data_type = data_type.to_logical_type();
+    match value {
+        Value::Bool(_) => matches!(data_type, Boolean),
+        Value::Number(n) => match data_type {
+            Int8 | Int16 | Int32 | Int64 | UInt8 | UInt16 | UInt32 | UInt64 => {
+                n.is_i64() || n.is_u64()
+            }
+            Float32 | Float64 | Decimal(_, _) => true,
+            _ => false,
+        },
+        Value::String(_) => matches!(data_type, Utf8 | LargeUtf8),
+        Value::Array(_) => matches!(data_type, List(_) | LargeList(_) | FixedSizeList(_, _)),
+        Value::Object(_) => matches!(data_type, Struct(_) | Map(_, _)),
+        Value::Null => false,
+    }
+}
+
+fn read_union(rows: &[&Value], data_type: DataType, format: BinaryFormat) -> UnionArray {
+    let fields = UnionArray::get_fields(&data_type);
+    let is_sparse = UnionArray::is_sparse(&data_type);
+    let ids = match data_type.to_logical_type() {
+        DataType::Union(_, ids, _) => ids.clone(),
+        _ => unreachable!(),
+    };
+
+    // the selected field of each row: the first whose type is a natural representation of
+    // the value, or the first field when there is none.
+    let selected = rows
+        .iter()
+        .map(|row| {
+            fields
+                .iter()
+                .position(|field| is_natural(row, field.data_type()))
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+
+    let types = selected
+        .iter()
+        .map(|i| ids.as_ref().map(|ids| ids[*i]).unwrap_or(*i as i32) as i8)
+        .collect::<MutableBuffer<i8>>();
+
+    let (arrays, offsets) = if is_sparse {
+        let arrays = fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let values = rows
+                    .iter()
+                    .zip(selected.iter())
+                    .map(|(row, selected)| if *selected == i { *row } else { &Value::Null })
+                    .collect::<Vec<_>>();
+                read(&values, field.data_type().clone(), format)
+            })
+            .collect::<Vec<_>>();
+        (arrays, None)
+    } else {
+        let mut values = vec![Vec::<&Value>::new(); fields.len()];
+        let offsets = rows
+            .iter()
+            .zip(selected.iter())
+            .map(|(row, selected)| {
+                values[*selected].push(*row);
+                (values[*selected].len() - 1) as i32
+            })
+            .collect::<MutableBuffer<i32>>();
+        let arrays = fields
+            .iter()
+            .zip(values.iter())
+            .map(|(field, values)| read(values, field.data_type().clone(), format))
+            .collect::<Vec<_>>();
+        (arrays, Some(offsets.into()))
+    };
+
+    UnionArray::from_data(data_type, types.into(), arrays, offsets)
+}
+
+fn read_dictionary<K: DictionaryKey>(
+    rows: &[&Value],
+    data_type: DataType,
+    format: BinaryFormat,
+) -> DictionaryArray<K> {
     let child = DictionaryArray::<K>::get_child(&data_type);
 
     let mut map = HashedMap::<u64, K>::default();
@@ -218,28 +548,50 @@ fn read_dictionary<K: DictionaryKey>(rows: &[&Value], data_type: DataType) -> Di
         .collect::<PrimitiveArray<K>>()
         .to(K::DATA_TYPE);
 
-    let values = read(&inner, child.clone());
+    let values = read(&inner, child.clone(), format);
     DictionaryArray::<K>::from_data(keys, values)
 }
 
-pub fn read(rows: &[&Value], data_type: DataType) -> Arc<dyn Array> {
-    match &data_type {
+/// Deserializes `rows` into an [`Array`] of [`DataType`] `data_type`, decoding binary values
+/// according to `format`. Values that cannot be represented in `data_type` are read as nulls.
+pub fn read(rows: &[&Value], data_type: DataType, format: BinaryFormat) -> Arc<dyn Array> {
+    match data_type.to_logical_type() {
         DataType::Null => Arc::new(NullArray::from_data(data_type, rows.len())),
         DataType::Boolean => Arc::new(read_boolean(rows)),
         DataType::Int8 => Arc::new(read_int::<i8>(rows, data_type)),
         DataType::Int16 => Arc::new(read_int::<i16>(rows, data_type)),
-        DataType::Int32
-        | DataType::Date32
-        | DataType::Time32(_)
-        | DataType::Interval(IntervalUnit::YearMonth) => Arc::new(read_int::<i32>(rows, data_type)),
-        DataType::Interval(IntervalUnit::DayTime) => {
-            unimplemented!("There is no natural representation of DayTime in JSON.")
+        DataType::Int32 | DataType::Interval(IntervalUnit::YearMonth) => {
+            Arc::new(read_int::<i32>(rows, data_type))
+        }
+        DataType::Date32 => Arc::new(read_temporal::<i32, _>(rows, data_type, |x| {
+            parse_date(x).map(|x| x as i64)
+        })),
+        DataType::Date64 => Arc::new(read_temporal::<i64, _>(rows, data_type, |x| {
+            parse_date(x).map(|x| x as i64 * 86_400_000)
+        })),
+        DataType::Time32(unit) => {
+            let unit = *unit;
+            Arc::new(read_temporal::<i32, _>(rows, data_type, |x| {
+                parse_time(x, unit)
+            }))
+        }
+        DataType::Time64(unit) => {
+            let unit = *unit;
+            Arc::new(read_temporal::<i64, _>(rows, data_type, |x| {
+                parse_time(x, unit)
+            }))
+        }
+        DataType::Timestamp(unit, _) => {
+            let unit = *unit;
+            Arc::new(read_temporal::<i64, _>(rows, data_type, |x| {
+                parse_timestamp(x, unit)
+            }))
+        }
+        DataType::Interval(IntervalUnit::DayTime) => Arc::new(read_days_ms(rows, data_type)),
+        DataType::Interval(IntervalUnit::MonthDayNano) => {
+            Arc::new(read_months_days_ns(rows, data_type))
         }
-        DataType::Int64
-        | DataType::Date64
-        | DataType::Time64(_)
-        | DataType::Timestamp(_, _)
-        | DataType::Duration(_) => Arc::new(read_int::<i64>(rows, data_type)),
+        DataType::Int64 | DataType::Duration(_) => Arc::new(read_int::<i64>(rows, data_type)),
         DataType::UInt8 => Arc::new(read_int::<u8>(rows, data_type)),
         DataType::UInt16 => Arc::new(read_int::<u16>(rows, data_type)),
         DataType::UInt32 => Arc::new(read_int::<u32>(rows, data_type)),
@@ -247,23 +599,23 @@ pub fn read(rows: &[&Value], data_type: DataType) -> Arc<dyn Array> {
         DataType::Float16 => unreachable!(),
         DataType::Float32 => Arc::new(read_float::<f32>(rows, data_type)),
         DataType::Float64 => Arc::new(read_float::<f64>(rows, data_type)),
+        DataType::Decimal(_, _) => Arc::new(read_decimal(rows, data_type)),
         DataType::Utf8 => Arc::new(read_utf8::<i32>(rows)),
         DataType::LargeUtf8 => Arc::new(read_utf8::<i64>(rows)),
-        DataType::List(_) => Arc::new(read_list::<i32>(rows, data_type)),
-        DataType::LargeList(_) => Arc::new(read_list::<i64>(rows, data_type)),
-        DataType::Binary => Arc::new(read_binary::<i32>(rows)),
-        DataType::LargeBinary => Arc::new(read_binary::<i64>(rows)),
-        DataType::Struct(_) => Arc::new(read_struct(rows, data_type)),
+        DataType::List(_) => Arc::new(read_list::<i32>(rows, data_type, format)),
+        DataType::LargeList(_) => Arc::new(read_list::<i64>(rows, data_type, format)),
+        DataType::FixedSizeList(_, _) => Arc::new(read_fixed_size_list(rows, data_type, format)),
+        DataType::Binary => Arc::new(read_binary::<i32>(rows, format)),
+        DataType::LargeBinary => Arc::new(read_binary::<i64>(rows, format)),
+        DataType::FixedSizeBinary(_) => Arc::new(read_fixed_size_binary(rows, data_type, format)),
+        DataType::Struct(_) => Arc::new(read_struct(rows, data_type, format)),
+        DataType::Map(_, _) => Arc::new(read_map(rows, data_type, format)),
+        DataType::Union(_, _, _) => Arc::new(read_union(rows, data_type, format)),
         DataType::Dictionary(key_type, _) => {
             match_integer_type!(key_type, |$T| {
-                Arc::new(read_dictionary::<$T>(rows, data_type))
+                Arc::new(read_dictionary::<$T>(rows, data_type, format))
             })
         }
-        _ => todo!(),
-        /*
-        DataType::FixedSizeBinary(_) => Box::new(FixedSizeBinaryArray::new_empty(data_type)),
-        DataType::FixedSizeList(_, _) => Box::new(FixedSizeListArray::new_empty(data_type)),
-        DataType::Decimal(_, _) => Box::new(PrimitiveArray::<i128>::new_empty(data_type)),
-        */
+        DataType::Extension(_, _, _) => unreachable!(),
     }
 }
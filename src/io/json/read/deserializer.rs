@@ -0,0 +1,1201 @@
+//! Deserializes JSON directly into arrays of a known [`Schema`], without intermediate
+//! [`serde_json::Value`]s.
+use std::borrow::Cow;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::hash::Hasher;
+use std::io::{BufRead, BufReader, Read};
+use std::sync::Arc;
+
+use hash_hasher::HashedMap;
+use num_traits::NumCast;
+use serde::de::{
+    DeserializeSeed, Deserializer, Error as _, IgnoredAny, MapAccess, SeqAccess, Visitor,
+};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::array::*;
+use crate::bitmap::MutableBitmap;
+use crate::buffer::MutableBuffer;
+use crate::datatypes::{DataType, Field, IntegerType, IntervalUnit, Schema};
+use crate::error::{ArrowError, Result};
+use crate::record_batch::RecordBatch;
+use crate::types::NativeType;
+
+use super::super::BinaryFormat;
+use super::deserialize::{decode_str, parse_date, parse_decimal, parse_time, parse_timestamp};
+
+/// A [`MutablePrimitiveArray`] that accepts JSON numbers, booleans and strings
+trait PrimitiveColumn {
+    fn push_i64(&mut self, value: i64);
+    fn push_u64(&mut self, value: u64);
+    fn push_f64(&mut self, value: f64);
+    /// Booleans are read as integers, same as [`Reader`](super::Reader)
+    #[inline]
+    fn push_bool(&mut self, value: bool) {
+        self.push_i64(value as i64)
+    }
+    /// Strings are read as nulls, unless the type has a string representation
+    #[inline]
+    fn push_str(&mut self, _: &str) {
+        self.push_null()
+    }
+    fn push_null(&mut self);
+    fn len(&self) -> usize;
+    fn finish(self: Box<Self>) -> Arc<dyn Array>;
+}
+
+/// Integers: floats are read as nulls, same as [`Reader`](super::Reader)
+struct IntegerColumn<T: NativeType>(MutablePrimitiveArray<T>);
+
+impl<T: NativeType + NumCast> PrimitiveColumn for IntegerColumn<T> {
+    #[inline]
+    fn push_i64(&mut self, value: i64) {
+        self.0.push(num_traits::cast(value))
+    }
+
+    #[inline]
+    fn push_u64(&mut self, value: u64) {
+        // `serde_json` only represents positive integers up to `i64::MAX` as `i64`
+        self.0
+            .push(num_traits::cast::<u64, i64>(value).and_then(num_traits::cast))
+    }
+
+    #[inline]
+    fn push_f64(&mut self, _: f64) {
+        self.0.push(None)
+    }
+
+    #[inline]
+    fn push_null(&mut self) {
+        self.0.push(None)
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn finish(self: Box<Self>) -> Arc<dyn Array> {
+        Arc::new(PrimitiveArray::from(self.0))
+    }
+}
+
+struct FloatColumn<T: NativeType>(MutablePrimitiveArray<T>);
+
+impl<T: NativeType + NumCast> PrimitiveColumn for FloatColumn<T> {
+    #[inline]
+    fn push_i64(&mut self, value: i64) {
+        self.push_f64(value as f64)
+    }
+
+    #[inline]
+    fn push_u64(&mut self, value: u64) {
+        self.push_f64(value as f64)
+    }
+
+    #[inline]
+    fn push_f64(&mut self, value: f64) {
+        self.0.push(num_traits::cast(value))
+    }
+
+    #[inline]
+    fn push_null(&mut self) {
+        self.0.push(None)
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn finish(self: Box<Self>) -> Arc<dyn Array> {
+        Arc::new(PrimitiveArray::from(self.0))
+    }
+}
+
+/// A function that parses the ISO 8601 representation of a temporal type
+type Parse = Box<dyn Fn(&str) -> Option<i64>>;
+
+/// Temporal types: read from their integer representation or parsed from their ISO 8601
+/// representation, same as [`Reader`](super::Reader)
+struct TemporalColumn<T: NativeType> {
+    array: MutablePrimitiveArray<T>,
+    parse: Parse,
+}
+
+impl<T: NativeType + NumCast> PrimitiveColumn for TemporalColumn<T> {
+    #[inline]
+    fn push_i64(&mut self, value: i64) {
+        self.array.push(num_traits::cast(value))
+    }
+
+    #[inline]
+    fn push_u64(&mut self, value: u64) {
+        self.array
+            .push(num_traits::cast::<u64, i64>(value).and_then(num_traits::cast))
+    }
+
+    #[inline]
+    fn push_f64(&mut self, _: f64) {
+        self.array.push(None)
+    }
+
+    #[inline]
+    fn push_bool(&mut self, _: bool) {
+        self.array.push(None)
+    }
+
+    #[inline]
+    fn push_str(&mut self, value: &str) {
+        self.array
+            .push((self.parse)(value).and_then(num_traits::cast))
+    }
+
+    #[inline]
+    fn push_null(&mut self) {
+        self.array.push(None)
+    }
+
+    fn len(&self) -> usize {
+        self.array.len()
+    }
+
+    fn finish(self: Box<Self>) -> Arc<dyn Array> {
+        Arc::new(PrimitiveArray::from(self.array))
+    }
+}
+
+/// Decimals: integers and floats are scaled and strings are parsed, same as [`Reader`](super::Reader)
+struct DecimalColumn {
+    array: MutablePrimitiveArray<i128>,
+    scale: usize,
+}
+
+impl PrimitiveColumn for DecimalColumn {
+    #[inline]
+    fn push_i64(&mut self, value: i64) {
+        self.array
+            .push((value as i128).checked_mul(10i128.pow(self.scale as u32)))
+    }
+
+    #[inline]
+    fn push_u64(&mut self, value: u64) {
+        match num_traits::cast::<u64, i64>(value) {
+            Some(value) => self.push_i64(value),
+            None => self.push_f64(value as f64),
+        }
+    }
+
+    #[inline]
+    fn push_f64(&mut self, value: f64) {
+        self.array
+            .push(Some((value * 10f64.powi(self.scale as i32)).round() as i128))
+    }
+
+    #[inline]
+    fn push_bool(&mut self, _: bool) {
+        self.array.push(None)
+    }
+
+    #[inline]
+    fn push_str(&mut self, value: &str) {
+        self.array.push(parse_decimal(value, self.scale))
+    }
+
+    #[inline]
+    fn push_null(&mut self) {
+        self.array.push(None)
+    }
+
+    fn len(&self) -> usize {
+        self.array.len()
+    }
+
+    fn finish(self: Box<Self>) -> Arc<dyn Array> {
+        Arc::new(PrimitiveArray::from(self.array))
+    }
+}
+
+struct ListColumn<O: Offset> {
+    data_type: DataType,
+    offsets: MutableBuffer<O>,
+    validity: MutableBitmap,
+    values: Box<Column>,
+}
+
+impl<O: Offset> ListColumn<O> {
+    fn new(data_type: DataType, format: BinaryFormat) -> Result<Self> {
+        let values = Box::new(Column::new(
+            ListArray::<O>::get_child_type(&data_type).clone(),
+            format,
+        )?);
+        let mut offsets = MutableBuffer::new();
+        offsets.push(O::zero());
+        Ok(Self {
+            data_type,
+            offsets,
+            validity: MutableBitmap::new(),
+            values,
+        })
+    }
+
+    #[inline]
+    fn push_offset(&mut self) {
+        // the number of values is bounded by the size of the JSON document
+        self.offsets
+            .push(O::from_usize(self.values.len()).expect("List offset is too large :/"));
+    }
+
+    fn push_seq<'de, A: SeqAccess<'de>>(
+        &mut self,
+        mut seq: A,
+    ) -> std::result::Result<(), A::Error> {
+        while seq
+            .next_element_seed(ColumnSeed(&mut self.values))?
+            .is_some()
+        {}
+        self.push_offset();
+        self.validity.push(true);
+        Ok(())
+    }
+
+    fn push_null(&mut self) {
+        self.push_offset();
+        self.validity.push(false);
+    }
+
+    fn len(&self) -> usize {
+        self.validity.len()
+    }
+
+    fn finish(self) -> Arc<dyn Array> {
+        Arc::new(ListArray::<O>::from_data(
+            self.data_type,
+            self.offsets.into(),
+            self.values.finish(),
+            self.validity.into(),
+        ))
+    }
+}
+
+/// Lists of a different size are read as nulls, same as [`Reader`](super::Reader)
+struct FixedSizeListColumn {
+    data_type: DataType,
+    size: usize,
+    validity: MutableBitmap,
+    values: Box<Column>,
+}
+
+impl FixedSizeListColumn {
+    fn new(data_type: DataType, format: BinaryFormat) -> Result<Self> {
+        let (child, size) = FixedSizeListArray::get_child_and_size(&data_type);
+        let values = Box::new(Column::new(child.data_type().clone(), format)?);
+        Ok(Self {
+            data_type,
+            size,
+            validity: MutableBitmap::new(),
+            values,
+        })
+    }
+
+    fn push_seq<'de, A: SeqAccess<'de>>(
+        &mut self,
+        mut seq: A,
+    ) -> std::result::Result<(), A::Error> {
+        let mut length = 0;
+        while length < self.size {
+            if seq
+                .next_element_seed(ColumnSeed(&mut self.values))?
+                .is_none()
+            {
+                break;
+            }
+            length += 1;
+        }
+        let mut is_valid = length == self.size;
+        while seq.next_element::<IgnoredAny>()?.is_some() {
+            is_valid = false;
+        }
+        // the values of a null slot are not read: pad shorter lists to the size
+        (length..self.size).for_each(|_| self.values.push_null());
+        self.validity.push(is_valid);
+        Ok(())
+    }
+
+    fn push_null(&mut self) {
+        (0..self.size).for_each(|_| self.values.push_null());
+        self.validity.push(false);
+    }
+
+    fn len(&self) -> usize {
+        self.validity.len()
+    }
+
+    fn finish(self) -> Arc<dyn Array> {
+        Arc::new(FixedSizeListArray::from_data(
+            self.data_type,
+            self.values.finish(),
+            self.validity.into(),
+        ))
+    }
+}
+
+/// Values of a different size are read as nulls, same as [`Reader`](super::Reader)
+struct FixedSizeBinaryColumn {
+    data_type: DataType,
+    size: usize,
+    format: BinaryFormat,
+    values: MutableBuffer<u8>,
+    validity: MutableBitmap,
+}
+
+impl FixedSizeBinaryColumn {
+    fn new(data_type: DataType, format: BinaryFormat) -> Self {
+        Self {
+            size: FixedSizeBinaryArray::get_size(&data_type),
+            data_type,
+            format,
+            values: MutableBuffer::new(),
+            validity: MutableBitmap::new(),
+        }
+    }
+
+    fn push_str(&mut self, value: &str) {
+        match decode_str(value, self.format).filter(|x| x.len() == self.size) {
+            Some(value) => {
+                self.values.extend_from_slice(&value);
+                self.validity.push(true);
+            }
+            None => self.push_null(),
+        }
+    }
+
+    fn push_null(&mut self) {
+        self.values.extend_constant(self.size, 0);
+        self.validity.push(false);
+    }
+
+    fn len(&self) -> usize {
+        self.validity.len()
+    }
+
+    fn finish(self) -> Arc<dyn Array> {
+        Arc::new(FixedSizeBinaryArray::from_data(
+            self.data_type,
+            self.values.into(),
+            self.validity.into(),
+        ))
+    }
+}
+
+/// Dictionary-encoded values, deduplicated by their JSON value, same as [`Reader`](super::Reader).
+/// Only strings, numbers and booleans of string and signed integer values are read.
+struct DictionaryColumn {
+    key_type: IntegerType,
+    /// Whether strings are read
+    is_utf8: bool,
+    /// Whether numbers and booleans are read
+    is_numeric: bool,
+    /// The keys of the values read so far, by the hash of their JSON value
+    hashes: HashedMap<u64, usize>,
+    keys: Vec<Option<usize>>,
+    values: Box<Column>,
+}
+
+fn finish_dictionary<K: DictionaryKey>(
+    keys: &[Option<usize>],
+    values: Arc<dyn Array>,
+) -> DictionaryArray<K> {
+    let keys = keys
+        .iter()
+        .map(|key| key.map(|key| K::from_usize(key).expect("Too many dictionary values :/")))
+        .collect::<PrimitiveArray<K>>()
+        .to(K::DATA_TYPE);
+    DictionaryArray::<K>::from_data(keys, values)
+}
+
+#[inline]
+fn hash(bytes: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+impl DictionaryColumn {
+    fn new(data_type: DataType, format: BinaryFormat) -> Result<Self> {
+        let (key_type, values) = match data_type.to_logical_type() {
+            DataType::Dictionary(key_type, values) => (*key_type, values.as_ref()),
+            _ => unreachable!(),
+        };
+        let is_utf8 = matches!(values, DataType::Utf8 | DataType::LargeUtf8);
+        let is_numeric = is_utf8
+            || matches!(
+                values,
+                DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64
+            );
+        Ok(Self {
+            key_type,
+            is_utf8,
+            is_numeric,
+            hashes: HashedMap::default(),
+            keys: vec![],
+            values: Box::new(Column::new(values.clone(), format)?),
+        })
+    }
+
+    /// Pushes the key of a value whose hash is `hash`, pushing the value with `push` when
+    /// it was not read before. Values without a hash are nulls.
+    #[inline]
+    fn push<F: FnOnce(&mut Column)>(&mut self, hash: Option<u64>, push: F) {
+        let (hashes, values) = (&mut self.hashes, &mut self.values);
+        let key = hash.map(|hash| {
+            *hashes.entry(hash).or_insert_with(|| {
+                let key = values.len();
+                push(values);
+                key
+            })
+        });
+        self.keys.push(key);
+    }
+
+    fn push_null(&mut self) {
+        self.keys.push(None)
+    }
+
+    fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    fn finish(self) -> Arc<dyn Array> {
+        let values = self.values.finish();
+        match_integer_type!(self.key_type, |$T| {
+            Arc::new(finish_dictionary::<$T>(&self.keys, values))
+        })
+    }
+}
+
+struct StructColumn {
+    /// The indices of the fields by name
+    names: HashMap<String, usize>,
+    fields: Vec<Column>,
+    length: usize,
+    /// Whether a row had a repeated key
+    has_duplicate_keys: bool,
+}
+
+impl StructColumn {
+    fn new(fields: &[Field], format: BinaryFormat) -> Result<Self> {
+        Ok(Self {
+            names: fields
+                .iter()
+                .enumerate()
+                .map(|(i, field)| (field.name().clone(), i))
+                .collect(),
+            fields: fields
+                .iter()
+                .map(|field| Column::new(field.data_type().clone(), format))
+                .collect::<Result<_>>()?,
+            length: 0,
+            has_duplicate_keys: false,
+        })
+    }
+
+    /// Deserializes the values of the known keys of `map`. Unknown keys are skipped without
+    /// being deserialized.
+    /// # Errors
+    /// Errors when a known key is repeated, since its value was already read.
+    fn push_map<'de, A: MapAccess<'de>>(
+        &mut self,
+        mut map: A,
+    ) -> std::result::Result<(), A::Error> {
+        let length = self.length;
+        while let Some(Key(key)) = map.next_key::<Key>()? {
+            match self.names.get(key.as_ref()).copied() {
+                // a field longer than the struct was already read in this row
+                Some(i) if self.fields[i].len() > length => {
+                    self.has_duplicate_keys = true;
+                    return Err(A::Error::custom(format!("duplicate key \"{}\"", key)));
+                }
+                Some(i) => map.next_value_seed(ColumnSeed(&mut self.fields[i]))?,
+                None => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        // keys missing in this row are nulls
+        self.fields
+            .iter_mut()
+            .filter(|field| field.len() == length)
+            .for_each(|field| field.push_null());
+        self.length += 1;
+        Ok(())
+    }
+
+    fn push_null(&mut self) {
+        self.fields.iter_mut().for_each(|field| field.push_null());
+        self.length += 1;
+    }
+
+    fn has_duplicate_keys(&self) -> bool {
+        self.has_duplicate_keys || self.fields.iter().any(|field| field.has_duplicate_keys())
+    }
+
+    fn finish(self) -> Vec<Arc<dyn Array>> {
+        self.fields
+            .into_iter()
+            .map(|field| field.finish())
+            .collect()
+    }
+}
+
+/// Objects are read as entries, same as [`Reader`](super::Reader)
+struct MapColumn {
+    data_type: DataType,
+    offsets: MutableBuffer<i32>,
+    validity: MutableBitmap,
+    /// Whether keys are read as strings or parsed from their JSON text
+    is_string_key: bool,
+    keys: Box<Column>,
+    values: Box<Column>,
+    /// Whether an entry had a repeated key
+    has_duplicate_keys: bool,
+}
+
+impl MapColumn {
+    fn new(data_type: DataType, format: BinaryFormat) -> Result<Self> {
+        let entries = MapArray::get_field(&data_type).data_type();
+        let fields = StructArray::get_fields(entries);
+        let (key_type, value_type) = (fields[0].data_type(), fields[1].data_type());
+        let is_string_key = matches!(key_type, DataType::Utf8 | DataType::LargeUtf8);
+        let keys = Box::new(Column::new(key_type.clone(), format)?);
+        let values = Box::new(Column::new(value_type.clone(), format)?);
+        let mut offsets = MutableBuffer::new();
+        offsets.push(0);
+        Ok(Self {
+            data_type,
+            offsets,
+            validity: MutableBitmap::new(),
+            is_string_key,
+            keys,
+            values,
+            has_duplicate_keys: false,
+        })
+    }
+
+    #[inline]
+    fn push_offset(&mut self) {
+        // the number of entries is bounded by the size of the JSON document
+        self.offsets.push(
+            num_traits::cast::<usize, i32>(self.keys.len()).expect("Map offset is too large :/"),
+        );
+    }
+
+    /// Deserializes the entries of `map`.
+    /// # Errors
+    /// Errors when a key is repeated, since its value was already read.
+    fn push_map<'de, A: MapAccess<'de>>(
+        &mut self,
+        mut map: A,
+    ) -> std::result::Result<(), A::Error> {
+        let mut keys = HashSet::new();
+        while let Some(Key(key)) = map.next_key::<Key>()? {
+            if !keys.insert(key.clone()) {
+                self.has_duplicate_keys = true;
+                return Err(A::Error::custom(format!("duplicate key \"{}\"", key)));
+            }
+            if self.is_string_key {
+                self.keys.push_str(&key)
+            } else {
+                // keys that are not strings are written as their JSON text
+                let key = serde_json::from_str(&key).unwrap_or(Value::Null);
+                ColumnSeed(&mut self.keys)
+                    .deserialize(key)
+                    .map_err(A::Error::custom)?;
+            }
+            map.next_value_seed(ColumnSeed(&mut self.values))?;
+        }
+        self.push_offset();
+        self.validity.push(true);
+        Ok(())
+    }
+
+    fn push_null(&mut self) {
+        self.push_offset();
+        self.validity.push(false);
+    }
+
+    fn len(&self) -> usize {
+        self.validity.len()
+    }
+
+    fn has_duplicate_keys(&self) -> bool {
+        self.has_duplicate_keys
+            || self.keys.has_duplicate_keys()
+            || self.values.has_duplicate_keys()
+    }
+
+    fn finish(self) -> Arc<dyn Array> {
+        let entries = MapArray::get_field(&self.data_type).data_type().clone();
+        let entries = StructArray::from_data(
+            entries,
+            vec![self.keys.finish(), self.values.finish()],
+            None,
+        );
+        Arc::new(MapArray::from_data(
+            self.data_type,
+            self.offsets.into(),
+            Arc::new(entries),
+            self.validity.into(),
+        ))
+    }
+}
+
+/// A key of a JSON object. Keys are borrowed from the input when they have no escape sequences.
+struct Key<'de>(Cow<'de, str>);
+
+impl<'de> Deserialize<'de> for Key<'de> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct KeyVisitor;
+
+        impl<'de> Visitor<'de> for KeyVisitor {
+            type Value = Key<'de>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a key")
+            }
+
+            fn visit_borrowed_str<E: serde::de::Error>(
+                self,
+                v: &'de str,
+            ) -> std::result::Result<Self::Value, E> {
+                Ok(Key(v.into()))
+            }
+
+            fn visit_str<E: serde::de::Error>(
+                self,
+                v: &str,
+            ) -> std::result::Result<Self::Value, E> {
+                Ok(Key(v.to_string().into()))
+            }
+
+            fn visit_string<E: serde::de::Error>(
+                self,
+                v: String,
+            ) -> std::result::Result<Self::Value, E> {
+                Ok(Key(v.into()))
+            }
+        }
+
+        deserializer.deserialize_str(KeyVisitor)
+    }
+}
+
+/// A mutable column of a given [`DataType`]
+enum Column {
+    Null(DataType, usize),
+    Boolean(MutableBooleanArray),
+    Primitive(Box<dyn PrimitiveColumn>),
+    Utf8(MutableUtf8Array<i32>),
+    LargeUtf8(MutableUtf8Array<i64>),
+    Binary(BinaryFormat, MutableBinaryArray<i32>),
+    LargeBinary(BinaryFormat, MutableBinaryArray<i64>),
+    FixedSizeBinary(FixedSizeBinaryColumn),
+    List(ListColumn<i32>),
+    LargeList(ListColumn<i64>),
+    FixedSizeList(FixedSizeListColumn),
+    Struct(DataType, StructColumn),
+    Map(MapColumn),
+    Dictionary(DictionaryColumn),
+}
+
+fn integer<T: NativeType + NumCast>(data_type: DataType) -> Column {
+    Column::Primitive(Box::new(IntegerColumn::<T>(MutablePrimitiveArray::from(
+        data_type,
+    ))))
+}
+
+fn float<T: NativeType + NumCast>(data_type: DataType) -> Column {
+    Column::Primitive(Box::new(FloatColumn::<T>(MutablePrimitiveArray::from(
+        data_type,
+    ))))
+}
+
+fn temporal<T: NativeType + NumCast, F: Fn(&str) -> Option<i64> + 'static>(
+    data_type: DataType,
+    parse: F,
+) -> Column {
+    Column::Primitive(Box::new(TemporalColumn::<T> {
+        array: MutablePrimitiveArray::from(data_type),
+        parse: Box::new(parse),
+    }))
+}
+
+impl Column {
+    fn new(data_type: DataType, format: BinaryFormat) -> Result<Self> {
+        use DataType::*;
+        Ok(match data_type.to_logical_type() {
+            Null => Column::Null(data_type, 0),
+            Boolean => Column::Boolean(MutableBooleanArray::new()),
+            Int8 => integer::<i8>(data_type),
+            Int16 => integer::<i16>(data_type),
+            Int32 | Interval(IntervalUnit::YearMonth) => integer::<i32>(data_type),
+            Int64 | Duration(_) => integer::<i64>(data_type),
+            UInt8 => integer::<u8>(data_type),
+            UInt16 => integer::<u16>(data_type),
+            UInt32 => integer::<u32>(data_type),
+            UInt64 => integer::<u64>(data_type),
+            Float32 => float::<f32>(data_type),
+            Float64 => float::<f64>(data_type),
+            Date32 => temporal::<i32, _>(data_type, |x| parse_date(x).map(|x| x as i64)),
+            Date64 => {
+                temporal::<i64, _>(data_type, |x| parse_date(x).map(|x| x as i64 * 86_400_000))
+            }
+            Time32(unit) => {
+                let unit = *unit;
+                temporal::<i32, _>(data_type, move |x| parse_time(x, unit))
+            }
+            Time64(unit) => {
+                let unit = *unit;
+                temporal::<i64, _>(data_type, move |x| parse_time(x, unit))
+            }
+            Timestamp(unit, _) => {
+                let unit = *unit;
+                temporal::<i64, _>(data_type, move |x| parse_timestamp(x, unit))
+            }
+            Decimal(_, scale) => Column::Primitive(Box::new(DecimalColumn {
+                scale: *scale,
+                array: MutablePrimitiveArray::from(data_type),
+            })),
+            Utf8 => Column::Utf8(MutableUtf8Array::new()),
+            LargeUtf8 => Column::LargeUtf8(MutableUtf8Array::new()),
+            Binary => Column::Binary(format, MutableBinaryArray::new()),
+            LargeBinary => Column::LargeBinary(format, MutableBinaryArray::new()),
+            FixedSizeBinary(_) => {
+                Column::FixedSizeBinary(FixedSizeBinaryColumn::new(data_type, format))
+            }
+            List(_) => Column::List(ListColumn::new(data_type, format)?),
+            LargeList(_) => Column::LargeList(ListColumn::new(data_type, format)?),
+            FixedSizeList(_, _) => {
+                Column::FixedSizeList(FixedSizeListColumn::new(data_type, format)?)
+            }
+            Struct(fields) => {
+                let column = StructColumn::new(fields, format)?;
+                Column::Struct(data_type, column)
+            }
+            Map(_, _) => Column::Map(MapColumn::new(data_type, format)?),
+            Dictionary(_, _) => Column::Dictionary(DictionaryColumn::new(data_type, format)?),
+            Float16 | Interval(_) | Union(_, _, _) | Extension(_, _, _) => {
+                return Err(ArrowError::NotYetImplemented(format!(
+                    "Deserializing JSON directly into {:?}; use `Reader` instead",
+                    data_type
+                )))
+            }
+        })
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Column::Null(_, length) => *length,
+            Column::Boolean(array) => array.len(),
+            Column::Primitive(array) => array.len(),
+            Column::Utf8(array) => array.len(),
+            Column::LargeUtf8(array) => array.len(),
+            Column::Binary(_, array) => array.len(),
+            Column::LargeBinary(_, array) => array.len(),
+            Column::FixedSizeBinary(array) => array.len(),
+            Column::List(array) => array.len(),
+            Column::LargeList(array) => array.len(),
+            Column::FixedSizeList(array) => array.len(),
+            Column::Struct(_, array) => array.length,
+            Column::Map(array) => array.len(),
+            Column::Dictionary(array) => array.len(),
+        }
+    }
+
+    fn push_null(&mut self) {
+        match self {
+            Column::Null(_, length) => *length += 1,
+            Column::Boolean(array) => array.push(None),
+            Column::Primitive(array) => array.push_null(),
+            Column::Utf8(array) => array.push::<&str>(None),
+            Column::LargeUtf8(array) => array.push::<&str>(None),
+            Column::Binary(_, array) => array.push::<&[u8]>(None),
+            Column::LargeBinary(_, array) => array.push::<&[u8]>(None),
+            Column::FixedSizeBinary(array) => array.push_null(),
+            Column::List(array) => array.push_null(),
+            Column::LargeList(array) => array.push_null(),
+            Column::FixedSizeList(array) => array.push_null(),
+            Column::Struct(_, array) => array.push_null(),
+            Column::Map(array) => array.push_null(),
+            Column::Dictionary(array) => array.push_null(),
+        }
+    }
+
+    fn push_bool(&mut self, value: bool) {
+        match self {
+            Column::Boolean(array) => array.push(Some(value)),
+            Column::Primitive(array) => array.push_bool(value),
+            Column::Utf8(array) => array.push(Some(value.to_string())),
+            Column::LargeUtf8(array) => array.push(Some(value.to_string())),
+            Column::Dictionary(array) => {
+                let hash = Some(hash(&[value as u8])).filter(|_| array.is_numeric);
+                array.push(hash, |values| values.push_bool(value))
+            }
+            _ => self.push_null(),
+        }
+    }
+
+    /// Pushes a number, whose value as `f64` is `value` and whose (JSON) text is `text`
+    fn push_number<F: FnOnce(&mut dyn PrimitiveColumn), T: FnOnce() -> String>(
+        &mut self,
+        value: f64,
+        f: F,
+        text: T,
+    ) {
+        match self {
+            Column::Primitive(array) => f(array.as_mut()),
+            Column::Utf8(array) => array.push(Some(text())),
+            Column::LargeUtf8(array) => array.push(Some(text())),
+            Column::Dictionary(array) => {
+                let hash = Some(hash(&value.to_le_bytes())).filter(|_| array.is_numeric);
+                array.push(hash, |values| values.push_number(value, f, text))
+            }
+            _ => self.push_null(),
+        }
+    }
+
+    fn push_str(&mut self, value: &str) {
+        match self {
+            Column::Primitive(array) => array.push_str(value),
+            Column::Utf8(array) => array.push(Some(value)),
+            Column::LargeUtf8(array) => array.push(Some(value)),
+            Column::Binary(format, array) => array.push(decode_str(value, *format)),
+            Column::LargeBinary(format, array) => array.push(decode_str(value, *format)),
+            Column::FixedSizeBinary(array) => array.push_str(value),
+            Column::Dictionary(array) => {
+                let hash = Some(hash(value.as_bytes())).filter(|_| array.is_utf8);
+                array.push(hash, |values| values.push_str(value))
+            }
+            _ => self.push_null(),
+        }
+    }
+
+    /// Whether a row of this column, or of one of its children, had a repeated key
+    fn has_duplicate_keys(&self) -> bool {
+        match self {
+            Column::List(array) => array.values.has_duplicate_keys(),
+            Column::LargeList(array) => array.values.has_duplicate_keys(),
+            Column::FixedSizeList(array) => array.values.has_duplicate_keys(),
+            Column::Struct(_, array) => array.has_duplicate_keys(),
+            Column::Map(array) => array.has_duplicate_keys(),
+            _ => false,
+        }
+    }
+
+    fn finish(self) -> Arc<dyn Array> {
+        match self {
+            Column::Null(data_type, length) => Arc::new(NullArray::from_data(data_type, length)),
+            Column::Boolean(array) => Arc::new(BooleanArray::from(array)),
+            Column::Primitive(array) => array.finish(),
+            Column::Utf8(array) => array.into_arc(),
+            Column::LargeUtf8(array) => array.into_arc(),
+            Column::Binary(_, array) => array.into_arc(),
+            Column::LargeBinary(_, array) => array.into_arc(),
+            Column::FixedSizeBinary(array) => array.finish(),
+            Column::List(array) => array.finish(),
+            Column::LargeList(array) => array.finish(),
+            Column::FixedSizeList(array) => array.finish(),
+            Column::Struct(data_type, array) => {
+                Arc::new(StructArray::from_data(data_type, array.finish(), None))
+            }
+            Column::Map(array) => array.finish(),
+            Column::Dictionary(array) => array.finish(),
+        }
+    }
+}
+
+/// Deserializes a JSON value into a [`Column`]
+struct ColumnSeed<'a>(&'a mut Column);
+
+impl<'de, 'a> DeserializeSeed<'de> for ColumnSeed<'a> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<(), D::Error> {
+        deserializer.deserialize_any(ColumnVisitor(self.0))
+    }
+}
+
+struct ColumnVisitor<'a>(&'a mut Column);
+
+impl<'de, 'a> Visitor<'de> for ColumnVisitor<'a> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a JSON value")
+    }
+
+    fn visit_bool<E: serde::de::Error>(self, v: bool) -> std::result::Result<(), E> {
+        self.0.push_bool(v);
+        Ok(())
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, v: i64) -> std::result::Result<(), E> {
+        self.0
+            .push_number(v as f64, |array| array.push_i64(v), || v.to_string());
+        Ok(())
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, v: u64) -> std::result::Result<(), E> {
+        self.0
+            .push_number(v as f64, |array| array.push_u64(v), || v.to_string());
+        Ok(())
+    }
+
+    fn visit_f64<E: serde::de::Error>(self, v: f64) -> std::result::Result<(), E> {
+        self.0
+            .push_number(v, |array| array.push_f64(v), || Value::from(v).to_string());
+        Ok(())
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> std::result::Result<(), E> {
+        self.0.push_str(v);
+        Ok(())
+    }
+
+    fn visit_unit<E: serde::de::Error>(self) -> std::result::Result<(), E> {
+        self.0.push_null();
+        Ok(())
+    }
+
+    fn visit_none<E: serde::de::Error>(self) -> std::result::Result<(), E> {
+        self.0.push_null();
+        Ok(())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<(), A::Error> {
+        match self.0 {
+            Column::List(array) => array.push_seq(seq),
+            Column::LargeList(array) => array.push_seq(seq),
+            Column::FixedSizeList(array) => array.push_seq(seq),
+            column => {
+                while seq.next_element::<IgnoredAny>()?.is_some() {}
+                column.push_null();
+                Ok(())
+            }
+        }
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> std::result::Result<(), A::Error> {
+        match self.0 {
+            Column::Struct(_, array) => array.push_map(map),
+            Column::Map(array) => array.push_map(map),
+            column => {
+                while map.next_entry::<IgnoredAny, IgnoredAny>()?.is_some() {}
+                column.push_null();
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Deserializes a row (a JSON object) into a [`StructColumn`]
+struct RowSeed<'a>(&'a mut StructColumn);
+
+impl<'de, 'a> DeserializeSeed<'de> for RowSeed<'a> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, 'a> Visitor<'de> for RowSeed<'a> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a row of type object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> std::result::Result<(), A::Error> {
+        self.0.push_map(map)
+    }
+}
+
+/// Deserializes `rows` (each a JSON object) into a [`RecordBatch`] of `schema`, without
+/// building intermediate [`serde_json::Value`]s. Keys of `rows` that are not
+/// fields of `schema` are skipped. The result is the same as the one of [`Reader`](super::Reader),
+/// including when a key is repeated in an object (its last value is read).
+/// # Errors
+/// This function errors iff a row is not valid JSON or is not a JSON object, or when `schema`
+/// has fields of [`DataType::Float16`], [`DataType::Union`], or
+/// [`DataType::Interval`] of days and milliseconds or months, days and nanoseconds, which can
+/// only be read by [`Reader`](super::Reader).
+pub fn deserialize<A: AsRef<str>>(
+    rows: &[A],
+    schema: Arc<Schema>,
+    binary_format: BinaryFormat,
+) -> Result<RecordBatch> {
+    let rows = rows.iter().map(|row| row.as_ref());
+    let columns = deserialize_rows(rows, schema.fields(), binary_format)?;
+    RecordBatch::try_new(schema, columns.finish())
+}
+
+/// Deserializes `rows` into a [`StructColumn`] of `fields`.
+fn deserialize_rows<'a, I: Iterator<Item = &'a str> + Clone>(
+    rows: I,
+    fields: &[Field],
+    format: BinaryFormat,
+) -> Result<StructColumn> {
+    let mut columns = StructColumn::new(fields, format)?;
+    match rows
+        .clone()
+        .try_for_each(|row| deserialize_row(row, &mut columns))
+    {
+        Err(_) if columns.has_duplicate_keys() => {
+            // the value of a repeated key was already read and can't be replaced. `Value`s keep
+            // the last value of each key: read the rows through them instead
+            let mut columns = StructColumn::new(fields, format)?;
+            rows.into_iter().try_for_each(|row| {
+                let value: Value = serde_json::from_str(row)?;
+                RowSeed(&mut columns).deserialize(value)?;
+                Result::Ok(())
+            })?;
+            Ok(columns)
+        }
+        result => result.map(|_| columns),
+    }
+}
+
+#[inline]
+fn deserialize_row(row: &str, columns: &mut StructColumn) -> Result<()> {
+    let mut deserializer = serde_json::Deserializer::from_str(row);
+    RowSeed(columns).deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(())
+}
+
+/// JSON line-delimited reader that deserializes each line directly into the arrays of its
+/// [`Schema`], without building intermediate [`serde_json::Value`]s.
+///
+/// It returns the same [`RecordBatch`]es as [`Reader`](super::Reader), but is faster,
+/// particularly when only some of the keys are projected. It does not support fields of
+/// [`DataType::Float16`], [`DataType::Union`], or [`DataType::Interval`]
+/// of days and milliseconds or months, days and nanoseconds: reading them errors.
+/// Use [`ReaderBuilder::build_direct`](super::ReaderBuilder::build_direct) to infer its schema.
+///
+/// Example:
+///
+/// ```
+/// use std::sync::Arc;
+/// use arrow2::datatypes::{DataType, Field, Schema};
+/// use arrow2::io::json;
+/// use std::io::Cursor;
+///
+/// let schema = Arc::new(Schema::new(vec![
+///     Field::new("a", DataType::Int64, true),
+///     Field::new("b", DataType::Float32, true),
+///     Field::new("c", DataType::Boolean, true),
+///     Field::new("d", DataType::Utf8, true),
+/// ]));
+///
+/// let data = r#"{"a":1, "b":2.0, "c":false, "d":"4"}
+/// {"a":-10, "b":-3.5, "c":true, "d":null}
+/// {"a":100000000, "b":0.6, "d":"text"}"#;
+/// let mut reader = json::DirectReader::new(Cursor::new(data), schema, 1024, None);
+/// let batch = reader.next().unwrap().unwrap();
+/// assert_eq!(batch.num_rows(), 3);
+/// ```
+#[derive(Debug)]
+pub struct DirectReader<R: Read> {
+    reader: BufReader<R>,
+    schema: Arc<Schema>,
+    batch_size: usize,
+    binary_format: BinaryFormat,
+    // the lines of a batch, whose buffers are reused to avoid allocations on each record
+    rows: Vec<String>,
+}
+
+impl<R: Read> DirectReader<R> {
+    /// Create a new [`DirectReader`] from any value that implements the `Read` trait.
+    /// When `projection` is provided, only its columns are read, in its order.
+    pub fn new(
+        reader: R,
+        schema: Arc<Schema>,
+        batch_size: usize,
+        projection: Option<Vec<String>>,
+    ) -> Self {
+        Self::from_buf_reader(BufReader::new(reader), schema, batch_size, projection)
+    }
+
+    /// Create a new [`DirectReader`] from a `BufReader<R: Read>`
+    pub fn from_buf_reader(
+        reader: BufReader<R>,
+        schema: Arc<Schema>,
+        batch_size: usize,
+        projection: Option<Vec<String>>,
+    ) -> Self {
+        let schema = match projection {
+            Some(projection) if !projection.is_empty() => {
+                let fields = projection
+                    .iter()
+                    .filter_map(|name| schema.column_with_name(name).map(|x| x.1.clone()))
+                    .collect();
+                Arc::new(Schema::new(fields))
+            }
+            _ => schema,
+        };
+        Self {
+            reader,
+            schema,
+            batch_size,
+            binary_format: BinaryFormat::default(),
+            rows: vec![],
+        }
+    }
+
+    /// Sets how binary values are encoded in the JSON file
+    pub fn with_binary_format(mut self, binary_format: BinaryFormat) -> Self {
+        self.binary_format = binary_format;
+        self
+    }
+
+    /// Returns the (projected) schema of the reader
+    pub fn schema(&self) -> &Arc<Schema> {
+        &self.schema
+    }
+
+    /// Read the next batch of records
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Result<Option<RecordBatch>> {
+        let mut length = 0;
+        while length < self.batch_size {
+            if length == self.rows.len() {
+                self.rows.push(String::new());
+            }
+            let row = &mut self.rows[length];
+            row.clear();
+            if self.reader.read_line(row)? == 0 {
+                // reached end of file
+                break;
+            }
+            // ignore empty lines
+            if !row.trim().is_empty() {
+                length += 1;
+            }
+        }
+
+        if length == 0 {
+            return Ok(None);
+        }
+        let rows = self.rows[..length].iter().map(|row| row.trim());
+        let columns = deserialize_rows(rows, self.schema.fields(), self.binary_format)?;
+        RecordBatch::try_new(self.schema.clone(), columns.finish()).map(Some)
+    }
+}
@@ -26,40 +26,126 @@ use super::util::ValueIter;
 use crate::datatypes::*;
 use crate::error::{ArrowError, Result};
 
-/// Coerce data type during inference
+/// Coerce data type during inference. The coercion does not depend on the order of `datatypes`.
 ///
-/// * `Int64` and `Float64` should be `Float64`
-/// * Lists and scalars are coerced to a list of a compatible scalar
+/// * `Null` (JSON nulls and empty lists) is compatible with any type and is ignored
+/// * `Int64` and `Float64` are coerced to `Float64`
+/// * `Int64` and `Boolean` are coerced to `Int64`
+/// * Lists and any other type (including lists) are coerced to a list whose item is
+///   the coercion of the items of the lists and of the other types
+/// * Structs are coerced to a struct with the union of their fields, in the order they are
+///   first seen, each field being the coercion of the fields of the same name
 /// * All other types are coerced to `Utf8`
-fn coerce_data_type(dt: &[&DataType]) -> DataType {
+fn coerce_data_type(datatypes: &[&DataType]) -> DataType {
     use DataType::*;
-    if dt.len() == 1 {
-        return dt[0].clone();
-    } else if dt.len() > 2 {
-        return List(Box::new(Field::new("item", Utf8, true)));
+    let mut datatypes = datatypes
+        .iter()
+        .copied()
+        .filter(|dt| **dt != Null)
+        .collect::<Vec<_>>();
+    datatypes.dedup();
+
+    if datatypes.is_empty() {
+        return Null;
+    } else if datatypes.len() == 1 {
+        return datatypes[0].clone();
+    }
+
+    if datatypes.iter().any(|dt| matches!(dt, List(_))) {
+        let inner = datatypes
+            .iter()
+            .map(|dt| match dt {
+                List(field) => field.data_type(),
+                dt => dt,
+            })
+            .collect::<Vec<_>>();
+        return List(Box::new(Field::new("item", coerce_data_type(&inner), true)));
     }
-    let (lhs, rhs) = (dt[0], dt[1]);
 
-    return match (lhs, rhs) {
-        (lhs, rhs) if lhs == rhs => lhs.clone(),
-        (List(lhs), List(rhs)) => {
-            let inner = coerce_data_type(&[lhs.data_type(), rhs.data_type()]);
-            List(Box::new(Field::new("item", inner, true)))
+    if datatypes.iter().all(|dt| matches!(dt, Struct(_))) {
+        // the fields, in the order they are first seen, and their position in `fields`
+        let mut fields = Vec::<(&str, Vec<&DataType>)>::new();
+        let mut positions = HashMap::<&str, usize>::new();
+        for dt in datatypes {
+            if let Struct(inner) = dt {
+                for field in inner {
+                    let name = field.name().as_str();
+                    let position = *positions.entry(name).or_insert_with(|| {
+                        fields.push((name, vec![]));
+                        fields.len() - 1
+                    });
+                    fields[position].1.push(field.data_type());
+                }
+            }
         }
-        (scalar, List(list)) => {
-            let inner = coerce_data_type(&[scalar, list.data_type()]);
-            List(Box::new(Field::new("item", inner, true)))
+        return Struct(
+            fields
+                .into_iter()
+                .map(|(name, datatypes)| Field::new(name, coerce_data_type(&datatypes), true))
+                .collect(),
+        );
+    }
+
+    let datatypes = datatypes.into_iter().collect::<HashSet<_>>();
+    if datatypes.iter().all(|dt| matches!(dt, Int64 | Float64)) {
+        Float64
+    } else if datatypes.iter().all(|dt| matches!(dt, Int64 | Boolean)) {
+        Int64
+    } else {
+        Utf8
+    }
+}
+
+/// Infers the [`DataType`] of a JSON value. JSON nulls and lists of nulls are inferred as
+/// [`DataType::Null`], whose type is not yet known.
+fn infer_value(value: &Value) -> DataType {
+    match value {
+        Value::Null => DataType::Null,
+        Value::Bool(_) => DataType::Boolean,
+        Value::Number(n) => {
+            if n.is_f64() {
+                DataType::Float64
+            } else {
+                DataType::Int64
+            }
         }
-        (List(list), scalar) => {
-            let inner = coerce_data_type(&[scalar, list.data_type()]);
-            List(Box::new(Field::new("item", inner, true)))
+        Value::String(_) => DataType::Utf8,
+        Value::Array(values) => {
+            let types = values.iter().map(infer_value).collect::<HashSet<_>>();
+            let types = types.iter().collect::<Vec<_>>();
+            match coerce_data_type(&types) {
+                DataType::Null => DataType::Null,
+                dt => DataType::List(Box::new(Field::new("item", dt, true))),
+            }
         }
-        (Float64, Int64) => Float64,
-        (Int64, Float64) => Float64,
-        (Int64, Boolean) => Int64,
-        (Boolean, Int64) => Int64,
-        (_, _) => Utf8,
-    };
+        Value::Object(map) => DataType::Struct(
+            map.iter()
+                .map(|(k, v)| Field::new(k, infer_value(v), true))
+                .collect(),
+        ),
+    }
+}
+
+/// Replaces the [`DataType::Null`] (e.g. of struct fields that were always null) of nested
+/// types by [`DataType::Utf8`].
+fn resolve_nulls(data_type: DataType) -> DataType {
+    match data_type {
+        DataType::Null => DataType::Utf8,
+        DataType::List(field) => DataType::List(Box::new(Field::new(
+            field.name(),
+            resolve_nulls(field.data_type().clone()),
+            true,
+        ))),
+        DataType::Struct(fields) => DataType::Struct(
+            fields
+                .into_iter()
+                .map(|field| {
+                    Field::new(field.name(), resolve_nulls(field.data_type().clone()), true)
+                })
+                .collect(),
+        ),
+        other => other,
+    }
 }
 
 /// Generate schema from JSON field names and inferred data types
@@ -68,7 +154,7 @@ fn generate_schema(spec: HashMap<String, HashSet<DataType>>) -> Schema {
         .iter()
         .map(|(k, hs)| {
             let v: Vec<&DataType> = hs.iter().collect();
-            Field::new(k, coerce_data_type(&v), true)
+            Field::new(k, resolve_nulls(coerce_data_type(&v)), true)
         })
         .collect();
     Schema::new(fields)
@@ -106,6 +192,17 @@ pub fn infer_json_schema<R: Read>(
 }
 
 /// Infer the fields of a JSON file by reading all items from the JSON Value Iterator.
+///
+/// Nested lists and objects are inferred as [`DataType::List`] and [`DataType::Struct`].
+/// Conflicting types of the same field are coerced as follows:
+/// * `Int64` and `Float64` are coerced to `Float64`
+/// * `Int64` and `Boolean` are coerced to `Int64`
+/// * a list and any other type are coerced to a list of the coercion of the items and the other type
+/// * structs are coerced to a struct with the union of their fields
+/// * all other combinations are coerced to `Utf8`
+///
+/// Fields that are always null are not part of the schema, and nested fields that are always
+/// null are inferred as `Utf8`.
 pub fn infer_json_schema_from_iterator<I>(value_iter: I) -> Result<Schema>
 where
     I: Iterator<Item = Result<Value>>,
@@ -115,125 +212,17 @@ where
     for record in value_iter {
         match record? {
             Value::Object(map) => {
-                let res = map.iter().try_for_each(|(k, v)| {
-                    match v {
-                        Value::Array(a) => {
-                            // collect the data types in array
-                            let types: Result<Vec<Option<&DataType>>> = a
-                                .iter()
-                                .map(|a| match a {
-                                    Value::Null => Ok(None),
-                                    Value::Number(n) => {
-                                        if n.is_i64() {
-                                            Ok(Some(&DataType::Int64))
-                                        } else {
-                                            Ok(Some(&DataType::Float64))
-                                        }
-                                    }
-                                    Value::Bool(_) => Ok(Some(&DataType::Boolean)),
-                                    Value::String(_) => Ok(Some(&DataType::Utf8)),
-                                    Value::Array(_) | Value::Object(_) => {
-                                        Err(ArrowError::NotYetImplemented(
-                                            "Nested lists and structs not supported".to_string(),
-                                        ))
-                                    }
-                                })
-                                .collect();
-                            match types {
-                                Ok(types) => {
-                                    // unwrap the Option and discard None values (from
-                                    // JSON nulls)
-                                    let mut types: Vec<&DataType> =
-                                        types.into_iter().flatten().collect();
-                                    types.dedup();
-                                    // if a record contains only nulls, it is not
-                                    // added to values
-                                    if !types.is_empty() {
-                                        let dt = coerce_data_type(&types);
-
-                                        if values.contains_key(k) {
-                                            let x = values.get_mut(k).unwrap();
-                                            x.insert(DataType::List(Box::new(Field::new(
-                                                "item", dt, true,
-                                            ))));
-                                        } else {
-                                            // create hashset and add value type
-                                            let mut hs = HashSet::new();
-                                            hs.insert(DataType::List(Box::new(Field::new(
-                                                "item", dt, true,
-                                            ))));
-                                            values.insert(k.to_string(), hs);
-                                        }
-                                    }
-                                    Ok(())
-                                }
-                                Err(e) => Err(e),
-                            }
-                        }
-                        Value::Bool(_) => {
-                            if values.contains_key(k) {
-                                let x = values.get_mut(k).unwrap();
-                                x.insert(DataType::Boolean);
-                            } else {
-                                // create hashset and add value type
-                                let mut hs = HashSet::new();
-                                hs.insert(DataType::Boolean);
-                                values.insert(k.to_string(), hs);
-                            }
-                            Ok(())
-                        }
-                        Value::Null => {
-                            // do nothing, we treat json as nullable by default when
-                            // inferring
-                            Ok(())
-                        }
-                        Value::Number(n) => {
-                            if n.is_f64() {
-                                if values.contains_key(k) {
-                                    let x = values.get_mut(k).unwrap();
-                                    x.insert(DataType::Float64);
-                                } else {
-                                    // create hashset and add value type
-                                    let mut hs = HashSet::new();
-                                    hs.insert(DataType::Float64);
-                                    values.insert(k.to_string(), hs);
-                                }
-                            } else {
-                                // default to i64
-                                if values.contains_key(k) {
-                                    let x = values.get_mut(k).unwrap();
-                                    x.insert(DataType::Int64);
-                                } else {
-                                    // create hashset and add value type
-                                    let mut hs = HashSet::new();
-                                    hs.insert(DataType::Int64);
-                                    values.insert(k.to_string(), hs);
-                                }
-                            }
-                            Ok(())
-                        }
-                        Value::String(_) => {
-                            if values.contains_key(k) {
-                                let x = values.get_mut(k).unwrap();
-                                x.insert(DataType::Utf8);
-                            } else {
-                                // create hashset and add value type
-                                let mut hs = HashSet::new();
-                                hs.insert(DataType::Utf8);
-                                values.insert(k.to_string(), hs);
-                            }
-                            Ok(())
-                        }
-                        Value::Object(_) => Err(ArrowError::NotYetImplemented(
-                            "Inferring schema from nested JSON structs currently not supported"
-                                .to_string(),
-                        )),
+                map.iter().for_each(|(k, v)| {
+                    // values whose type is not known (e.g. nulls) are not added, since
+                    // json is treated as nullable by default when inferring
+                    let data_type = infer_value(v);
+                    if data_type != DataType::Null {
+                        values
+                            .entry(k.to_string())
+                            .or_insert_with(HashSet::new)
+                            .insert(data_type);
                     }
                 });
-                match res {
-                    Ok(()) => {}
-                    Err(e) => return Err(e),
-                }
             }
             value => {
                 return Err(ArrowError::Other(format!(
@@ -307,4 +296,31 @@ mod test {
             coerce_data_type(&[&Boolean, &List(Box::new(Field::new("item", Float64, true)))])
         );
     }
+
+    #[test]
+    fn test_coersion_structs() {
+        use crate::datatypes::DataType::*;
+
+        let lhs = Struct(vec![
+            Field::new("a", Int64, true),
+            Field::new("b", Boolean, true),
+        ]);
+        let rhs = Struct(vec![
+            Field::new("a", Float64, true),
+            Field::new("c", Utf8, true),
+        ]);
+        assert_eq!(
+            Struct(vec![
+                Field::new("a", Float64, true),
+                Field::new("b", Boolean, true),
+                Field::new("c", Utf8, true),
+            ]),
+            coerce_data_type(&[&lhs, &rhs])
+        );
+        // the order of the types does not matter
+        assert_eq!(
+            coerce_data_type(&[&Boolean, &Int64, &Float64]),
+            coerce_data_type(&[&Float64, &Boolean, &Int64])
+        );
+    }
 }
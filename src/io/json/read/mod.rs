@@ -16,9 +16,11 @@
 // under the License.
 
 mod deserialize;
+mod deserializer;
 mod infer_schema;
 mod reader;
 mod util;
 
+pub use deserializer::{deserialize, DirectReader};
 pub use infer_schema::*;
 pub use reader::*;
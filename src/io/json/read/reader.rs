@@ -25,7 +25,8 @@ use crate::datatypes::*;
 use crate::error::{ArrowError, Result};
 use crate::record_batch::RecordBatch;
 
-use super::{deserialize::read, infer_json_schema_from_seekable, util::ValueIter};
+use super::super::BinaryFormat;
+use super::{deserialize::read, infer_json_schema_from_seekable, util::ValueIter, DirectReader};
 
 #[derive(Debug)]
 struct Decoder {
@@ -35,6 +36,8 @@ struct Decoder {
     projection: Option<Vec<String>>,
     /// Batch size (number of records to load each time)
     batch_size: usize,
+    /// How binary values are encoded in the JSON file
+    binary_format: BinaryFormat,
 }
 
 impl Decoder {
@@ -64,6 +67,7 @@ impl Decoder {
             schema,
             projection,
             batch_size,
+            binary_format: BinaryFormat::default(),
         }
     }
 
@@ -111,7 +115,7 @@ impl Decoder {
         };
 
         let data_type = DataType::Struct(projected_fields.clone());
-        let array = read(&rows, data_type);
+        let array = read(&rows, data_type, self.binary_format);
         let array = array.as_any().downcast_ref::<StructArray>().unwrap();
         let arrays = array.values().to_vec();
 
@@ -217,6 +221,8 @@ pub struct ReaderBuilder {
     batch_size: usize,
     /// Optional projection for which columns to load (zero-based column indices)
     projection: Option<Vec<String>>,
+    /// How binary values are encoded in the JSON file
+    binary_format: BinaryFormat,
 }
 
 impl Default for ReaderBuilder {
@@ -226,6 +232,7 @@ impl Default for ReaderBuilder {
             max_records: None,
             batch_size: 1024,
             projection: None,
+            binary_format: BinaryFormat::default(),
         }
     }
 }
@@ -262,6 +269,13 @@ impl ReaderBuilder {
         self
     }
 
+    /// Set how binary values are encoded in the JSON file. It should match the
+    /// [`BinaryFormat`] used to write it.
+    pub fn with_binary_format(mut self, binary_format: BinaryFormat) -> Self {
+        self.binary_format = binary_format;
+        self
+    }
+
     /// Create a new `Reader` from the `ReaderBuilder`
     pub fn build<R>(self, source: R) -> Result<Reader<R>>
     where
@@ -278,11 +292,30 @@ impl ReaderBuilder {
             )?),
         };
 
-        Ok(Reader::from_buf_reader(
-            buf_reader,
-            schema,
-            self.batch_size,
-            self.projection,
-        ))
+        let mut reader =
+            Reader::from_buf_reader(buf_reader, schema, self.batch_size, self.projection);
+        reader.decoder.binary_format = self.binary_format;
+        Ok(reader)
+    }
+
+    /// Create a new [`DirectReader`] from the `ReaderBuilder`
+    pub fn build_direct<R>(self, source: R) -> Result<DirectReader<R>>
+    where
+        R: Read + Seek,
+    {
+        let mut buf_reader = BufReader::new(source);
+
+        let schema = match self.schema {
+            Some(schema) => schema,
+            None => Arc::new(infer_json_schema_from_seekable(
+                &mut buf_reader,
+                self.max_records,
+            )?),
+        };
+
+        Ok(
+            DirectReader::from_buf_reader(buf_reader, schema, self.batch_size, self.projection)
+                .with_binary_format(self.binary_format),
+        )
     }
 }
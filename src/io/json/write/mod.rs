@@ -16,6 +16,9 @@
 // under the License.
 
 mod serialize;
+mod serializer;
 mod writer;
-pub use serialize::write_record_batches;
+pub use serialize::{write_record_batches, write_record_batches_with_options, SerializeOptions};
+pub(crate) use serialize::{TIMESTAMP_FORMAT, TIMESTAMP_TZ_FORMAT};
+pub use serializer::{new_serializer, serialize};
 pub use writer::*;
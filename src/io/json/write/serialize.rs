@@ -19,8 +19,31 @@ use serde_json::map::Map;
 use serde_json::{Number, Value};
 
 use crate::bitmap::utils::zip_validity;
+use crate::temporal_conversions;
+use crate::types::{days_ms, months_days_ns};
 use crate::{array::*, datatypes::*, record_batch::RecordBatch, types::NativeType};
 
+use super::super::BinaryFormat;
+
+/// Options to serialize arrays to JSON
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SerializeOptions {
+    /// Whether [`DataType::Decimal`] values are written as JSON strings (exact) or as
+    /// JSON numbers (which may lose precision when read by other readers). Defaults to `true`.
+    pub decimal_as_string: bool,
+    /// How binary values are written. Defaults to [`BinaryFormat::Utf8`].
+    pub binary_format: BinaryFormat,
+}
+
+impl Default for SerializeOptions {
+    fn default() -> Self {
+        Self {
+            decimal_as_string: true,
+            binary_format: BinaryFormat::Utf8,
+        }
+    }
+}
+
 trait JsonSerializable {
     fn into_json_value(self) -> Option<Value>;
 }
@@ -103,7 +126,297 @@ where
     array.iter().map(to_json).collect()
 }
 
-fn struct_array_to_jsonmap_array(array: &StructArray, row_count: usize) -> Vec<Map<String, Value>> {
+/// Maps each slot of a [`PrimitiveArray`] to a [`Value`] via `f`; nulls are mapped to [`Value::Null`]
+fn primitive_map<T: NativeType, F: Fn(T) -> Value>(array: &dyn Array, f: F) -> Vec<Value> {
+    let array = array.as_any().downcast_ref::<PrimitiveArray<T>>().unwrap();
+    array
+        .iter()
+        .map(|x| x.map(|x| f(*x)).unwrap_or(Value::Null))
+        .collect()
+}
+
+fn utf8_array_to_json<O: Offset>(array: &dyn Array) -> Vec<Value> {
+    let array = array.as_any().downcast_ref::<Utf8Array<O>>().unwrap();
+    array
+        .iter()
+        .map(|x| x.map(|x| x.into()).unwrap_or(Value::Null))
+        .collect()
+}
+
+fn encode_binary(bytes: &[u8], format: BinaryFormat) -> Value {
+    Value::String(match format {
+        BinaryFormat::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+        BinaryFormat::Base64 => base64::encode(bytes),
+        BinaryFormat::Hex => hex::encode(bytes),
+    })
+}
+
+fn binary_array_to_json<O: Offset>(array: &dyn Array, format: BinaryFormat) -> Vec<Value> {
+    let array = array.as_any().downcast_ref::<BinaryArray<O>>().unwrap();
+    array
+        .iter()
+        .map(|x| x.map(|x| encode_binary(x, format)).unwrap_or(Value::Null))
+        .collect()
+}
+
+fn list_array_to_json<O: Offset>(array: &dyn Array, options: &SerializeOptions) -> Vec<Value> {
+    let array = array.as_any().downcast_ref::<ListArray<O>>().unwrap();
+    array
+        .iter()
+        .map(|x| {
+            x.map(|x| Value::Array(array_to_json(x.as_ref(), options)))
+                .unwrap_or(Value::Null)
+        })
+        .collect()
+}
+
+/// Returns the exact decimal representation of `value` with `scale`
+pub(super) fn decimal_to_string(value: i128, scale: usize) -> String {
+    let factor = 10i128.pow(scale as u32);
+    let (integer, fraction) = (value / factor, (value % factor).abs());
+    let sign = if value < 0 && integer == 0 { "-" } else { "" };
+    if scale == 0 {
+        value.to_string()
+    } else {
+        format!("{}{}.{:0width$}", sign, integer, fraction, width = scale)
+    }
+}
+
+fn decimal_to_json(value: i128, scale: usize, as_string: bool) -> Value {
+    let string = decimal_to_string(value, scale);
+    if as_string {
+        Value::String(string)
+    } else {
+        string
+            .parse::<f64>()
+            .ok()
+            .and_then(Number::from_f64)
+            .map(Value::Number)
+            .unwrap_or(Value::Null)
+    }
+}
+
+fn timestamp_tz_to_json<T>(array: &dyn Array, time_unit: TimeUnit, timezone: &T) -> Vec<Value>
+where
+    T: chrono::TimeZone,
+    T::Offset: std::fmt::Display,
+{
+    primitive_map(array, |x: i64| {
+        temporal_conversions::timestamp_to_datetime(x, time_unit, timezone)
+            .format(TIMESTAMP_TZ_FORMAT)
+            .to_string()
+            .into()
+    })
+}
+
+#[cfg(feature = "chrono-tz")]
+fn timestamp_named_tz_to_json(
+    array: &dyn Array,
+    time_unit: TimeUnit,
+    timezone: &str,
+) -> Vec<Value> {
+    let timezone = temporal_conversions::parse_offset_tz(timezone).unwrap();
+    timestamp_tz_to_json(array, time_unit, &timezone)
+}
+
+#[cfg(not(feature = "chrono-tz"))]
+fn timestamp_named_tz_to_json(_: &dyn Array, _: TimeUnit, timezone: &str) -> Vec<Value> {
+    panic!(
+        "Invalid Offset format \"{}\" (must be [-]00:00) or chrono-tz feature not active",
+        timezone
+    )
+}
+
+/// The format of timestamps without timezone
+pub(crate) const TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f";
+/// The format of timestamps with timezone
+pub(crate) const TIMESTAMP_TZ_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f%:z";
+
+/// Serializes each slot of `array` into a [`Value`].
+/// # Implementation
+/// * dates, times and timestamps are written as ISO 8601 strings. Timestamps with
+///   a timezone are written in that timezone, with its offset.
+/// * durations and [`IntervalUnit::YearMonth`] intervals are written as integers
+/// * [`IntervalUnit::DayTime`] intervals are written as `{"days": _, "milliseconds": _}`
+/// * [`IntervalUnit::MonthDayNano`] intervals are written as
+///   `{"months": _, "days": _, "nanoseconds": _}`
+/// * decimals are written as strings or numbers (see [`SerializeOptions`])
+/// * binary values are written according to [`SerializeOptions::binary_format`]
+/// * dictionaries are written as their values
+/// * maps are written as objects whose keys are the (JSON text of the) keys
+/// * unions are written as the value of the selected field
+/// # Panics
+/// This function panics for [`DataType::Float16`] and for timestamps with an invalid timezone.
+pub(super) fn array_to_json(array: &dyn Array, options: &SerializeOptions) -> Vec<Value> {
+    use DataType::*;
+    match array.data_type().to_logical_type() {
+        Null => std::iter::repeat(Value::Null).take(array.len()).collect(),
+        Boolean => array
+            .as_any()
+            .downcast_ref::<BooleanArray>()
+            .unwrap()
+            .iter()
+            .map(|maybe_value| match maybe_value {
+                Some(v) => v.into(),
+                None => Value::Null,
+            })
+            .collect(),
+        Utf8 => utf8_array_to_json::<i32>(array),
+        LargeUtf8 => utf8_array_to_json::<i64>(array),
+        Int8 => primitive_array_to_json::<i8>(array),
+        Int16 => primitive_array_to_json::<i16>(array),
+        Int32 | Interval(IntervalUnit::YearMonth) => primitive_array_to_json::<i32>(array),
+        Int64 | Duration(_) => primitive_array_to_json::<i64>(array),
+        UInt8 => primitive_array_to_json::<u8>(array),
+        UInt16 => primitive_array_to_json::<u16>(array),
+        UInt32 => primitive_array_to_json::<u32>(array),
+        UInt64 => primitive_array_to_json::<u64>(array),
+        Float16 => unreachable!(),
+        Float32 => primitive_array_to_json::<f32>(array),
+        Float64 => primitive_array_to_json::<f64>(array),
+        Date32 => primitive_map(array, |x: i32| {
+            temporal_conversions::date32_to_date(x).to_string().into()
+        }),
+        Date64 => primitive_map(array, |x: i64| {
+            temporal_conversions::date64_to_date(x).to_string().into()
+        }),
+        Time32(TimeUnit::Second) => primitive_map(array, |x: i32| {
+            temporal_conversions::time32s_to_time(x).to_string().into()
+        }),
+        Time32(_) => primitive_map(array, |x: i32| {
+            temporal_conversions::time32ms_to_time(x).to_string().into()
+        }),
+        Time64(TimeUnit::Microsecond) => primitive_map(array, |x: i64| {
+            temporal_conversions::time64us_to_time(x).to_string().into()
+        }),
+        Time64(_) => primitive_map(array, |x: i64| {
+            temporal_conversions::time64ns_to_time(x).to_string().into()
+        }),
+        Timestamp(time_unit, None) => primitive_map(array, |x: i64| {
+            temporal_conversions::timestamp_to_naive_datetime(x, *time_unit)
+                .format(TIMESTAMP_FORMAT)
+                .to_string()
+                .into()
+        }),
+        Timestamp(time_unit, Some(timezone)) => {
+            match temporal_conversions::parse_offset(timezone) {
+                Ok(offset) => timestamp_tz_to_json(array, *time_unit, &offset),
+                Err(_) => timestamp_named_tz_to_json(array, *time_unit, timezone),
+            }
+        }
+        Interval(IntervalUnit::DayTime) => primitive_map(
+            array,
+            |x: days_ms| serde_json::json!({"days": x.days(), "milliseconds": x.milliseconds()}),
+        ),
+        Interval(IntervalUnit::MonthDayNano) => primitive_map(
+            array,
+            |x: months_days_ns| serde_json::json!({"months": x.months(), "days": x.days(), "nanoseconds": x.ns()}),
+        ),
+        Decimal(_, scale) => primitive_map(array, |x: i128| {
+            decimal_to_json(x, *scale, options.decimal_as_string)
+        }),
+        Binary => binary_array_to_json::<i32>(array, options.binary_format),
+        LargeBinary => binary_array_to_json::<i64>(array, options.binary_format),
+        FixedSizeBinary(_) => {
+            let array = array
+                .as_any()
+                .downcast_ref::<FixedSizeBinaryArray>()
+                .unwrap();
+            array
+                .iter()
+                .map(|x| {
+                    x.map(|x| encode_binary(x, options.binary_format))
+                        .unwrap_or(Value::Null)
+                })
+                .collect()
+        }
+        List(_) => list_array_to_json::<i32>(array, options),
+        LargeList(_) => list_array_to_json::<i64>(array, options),
+        FixedSizeList(_, _) => {
+            let array = array.as_any().downcast_ref::<FixedSizeListArray>().unwrap();
+            array
+                .iter()
+                .map(|x| {
+                    x.map(|x| Value::Array(array_to_json(x.as_ref(), options)))
+                        .unwrap_or(Value::Null)
+                })
+                .collect()
+        }
+        Struct(_) => {
+            let array = array.as_any().downcast_ref::<StructArray>().unwrap();
+            let jsonmaps = struct_array_to_jsonmap_array(array, array.len(), options);
+            zip_validity(jsonmaps.into_iter(), array.validity().map(|v| v.iter()))
+                .map(|m| m.map(Value::Object).unwrap_or(Value::Null))
+                .collect()
+        }
+        Map(_, _) => {
+            let array = array.as_any().downcast_ref::<MapArray>().unwrap();
+            let entries = array
+                .field()
+                .as_any()
+                .downcast_ref::<StructArray>()
+                .unwrap();
+            let keys = array_to_json(entries.values()[0].as_ref(), options);
+            let values = array_to_json(entries.values()[1].as_ref(), options);
+            let offsets = array.offsets();
+            (0..array.len())
+                .map(|i| {
+                    if array.is_null(i) {
+                        return Value::Null;
+                    }
+                    let (start, end) = (offsets[i] as usize, offsets[i + 1] as usize);
+                    Value::Object(
+                        (start..end)
+                            .map(|j| {
+                                let key = match &keys[j] {
+                                    Value::String(key) => key.clone(),
+                                    key => key.to_string(),
+                                };
+                                (key, values[j].clone())
+                            })
+                            .collect(),
+                    )
+                })
+                .collect()
+        }
+        Union(_, _, _) => {
+            let array = array.as_any().downcast_ref::<UnionArray>().unwrap();
+            let fields = array
+                .fields()
+                .iter()
+                .map(|field| array_to_json(field.as_ref(), options))
+                .collect::<Vec<_>>();
+            (0..array.len())
+                .map(|i| {
+                    let (field, index) = array.index(i);
+                    fields[field][index].clone()
+                })
+                .collect()
+        }
+        Dictionary(key_type, _) => match_integer_type!(key_type, |$T| {
+            let array = array
+                .as_any()
+                .downcast_ref::<DictionaryArray<$T>>()
+                .unwrap();
+            let values = array_to_json(array.values().as_ref(), options);
+            array
+                .keys()
+                .iter()
+                .map(|key| {
+                    key.map(|key| values[*key as usize].clone())
+                        .unwrap_or(Value::Null)
+                })
+                .collect()
+        }),
+        Extension(_, _, _) => unreachable!(),
+    }
+}
+
+fn struct_array_to_jsonmap_array(
+    array: &StructArray,
+    row_count: usize,
+    options: &SerializeOptions,
+) -> Vec<Map<String, Value>> {
     // {"a": [1, 2, 3], "b": [a, b, c], "c": {"a": [1, 2, 3]}}
     // [
     //  {"a": 1, "b": a, "c": {"a": 1}},
@@ -128,220 +441,32 @@ fn struct_array_to_jsonmap_array(array: &StructArray, row_count: usize) -> Vec<M
                 row_count,
                 struct_col.as_ref(),
                 fields[j].name(),
+                options,
             );
         });
 
     inner_objs
 }
 
-fn write_array(array: &dyn Array) -> Value {
-    Value::Array(match array.data_type() {
-        DataType::Null => std::iter::repeat(Value::Null).take(array.len()).collect(),
-        DataType::Boolean => array
-            .as_any()
-            .downcast_ref::<BooleanArray>()
-            .unwrap()
-            .iter()
-            .map(|maybe_value| match maybe_value {
-                Some(v) => v.into(),
-                None => Value::Null,
-            })
-            .collect(),
-        DataType::Utf8 => array
-            .as_any()
-            .downcast_ref::<Utf8Array<i32>>()
-            .unwrap()
-            .iter()
-            .map(|maybe_value| match maybe_value {
-                Some(v) => v.into(),
-                None => Value::Null,
-            })
-            .collect(),
-        DataType::LargeUtf8 => array
-            .as_any()
-            .downcast_ref::<Utf8Array<i32>>()
-            .unwrap()
-            .iter()
-            .map(|maybe_value| match maybe_value {
-                Some(v) => v.into(),
-                None => Value::Null,
-            })
-            .collect(),
-        DataType::Int8 => primitive_array_to_json::<i8>(array),
-        DataType::Int16 => primitive_array_to_json::<i16>(array),
-        DataType::Int32 => primitive_array_to_json::<i32>(array),
-        DataType::Int64 => primitive_array_to_json::<i64>(array),
-        DataType::UInt8 => primitive_array_to_json::<u8>(array),
-        DataType::UInt16 => primitive_array_to_json::<u16>(array),
-        DataType::UInt32 => primitive_array_to_json::<u32>(array),
-        DataType::UInt64 => primitive_array_to_json::<u64>(array),
-        DataType::Float32 => primitive_array_to_json::<f32>(array),
-        DataType::Float64 => primitive_array_to_json::<f64>(array),
-        DataType::List(_) => array
-            .as_any()
-            .downcast_ref::<ListArray<i32>>()
-            .unwrap()
-            .iter()
-            .map(|maybe_value| match maybe_value {
-                Some(v) => write_array(v.as_ref()),
-                None => Value::Null,
-            })
-            .collect(),
-        DataType::LargeList(_) => array
-            .as_any()
-            .downcast_ref::<ListArray<i64>>()
-            .unwrap()
-            .iter()
-            .map(|maybe_value| match maybe_value {
-                Some(v) => write_array(v.as_ref()),
-                None => Value::Null,
-            })
-            .collect(),
-        DataType::Struct(_) => {
-            let jsonmaps = struct_array_to_jsonmap_array(
-                array.as_any().downcast_ref::<StructArray>().unwrap(),
-                array.len(),
-            );
-            zip_validity(jsonmaps.into_iter(), array.validity().map(|v| v.iter()))
-                .map(|m| m.map(Value::Object).unwrap_or(Value::Null))
-                .collect()
-        }
-        _ => {
-            panic!(
-                "Unsupported datatype for array conversion: {:#?}",
-                array.data_type()
-            );
-        }
-    })
-}
-
-fn set_column_by_primitive_type<T: NativeType + JsonSerializable>(
+fn set_column_for_json_rows(
     rows: &mut [Map<String, Value>],
     row_count: usize,
     array: &dyn Array,
     col_name: &str,
+    options: &SerializeOptions,
 ) {
-    let primitive_arr = array.as_any().downcast_ref::<PrimitiveArray<T>>().unwrap();
-
+    if array.data_type() == &DataType::Null {
+        // when value is null, we simply skip setting the key
+        return;
+    }
     rows.iter_mut()
-        .zip(primitive_arr.iter())
+        .zip(array_to_json(array, options))
         .take(row_count)
         .for_each(|(row, value)| {
-            let value = to_json::<T>(value);
             row.insert(col_name.to_string(), value);
         });
 }
 
-fn set_column_for_json_rows(
-    rows: &mut [Map<String, Value>],
-    row_count: usize,
-    array: &dyn Array,
-    col_name: &str,
-) {
-    match array.data_type() {
-        DataType::Null => {
-            // when value is null, we simply skip setting the key
-        }
-        DataType::Boolean => {
-            let array = array.as_any().downcast_ref::<BooleanArray>().unwrap();
-            rows.iter_mut()
-                .zip(array.iter())
-                .take(row_count)
-                .for_each(|(row, value)| {
-                    row.insert(
-                        col_name.to_string(),
-                        value.map(Value::Bool).unwrap_or(Value::Null),
-                    );
-                });
-        }
-        DataType::Int8 => set_column_by_primitive_type::<i8>(rows, row_count, array, col_name),
-        DataType::Int16 => set_column_by_primitive_type::<i16>(rows, row_count, array, col_name),
-        DataType::Int32 => set_column_by_primitive_type::<i32>(rows, row_count, array, col_name),
-        DataType::Int64 => set_column_by_primitive_type::<i64>(rows, row_count, array, col_name),
-        DataType::UInt8 => set_column_by_primitive_type::<u8>(rows, row_count, array, col_name),
-        DataType::UInt16 => set_column_by_primitive_type::<u16>(rows, row_count, array, col_name),
-        DataType::UInt32 => set_column_by_primitive_type::<u32>(rows, row_count, array, col_name),
-        DataType::UInt64 => set_column_by_primitive_type::<u64>(rows, row_count, array, col_name),
-        DataType::Float32 => set_column_by_primitive_type::<f32>(rows, row_count, array, col_name),
-        DataType::Float64 => set_column_by_primitive_type::<f64>(rows, row_count, array, col_name),
-        DataType::Utf8 => {
-            let array = array.as_any().downcast_ref::<Utf8Array<i32>>().unwrap();
-            rows.iter_mut()
-                .zip(array.iter())
-                .take(row_count)
-                .for_each(|(row, value)| {
-                    row.insert(
-                        col_name.to_string(),
-                        value
-                            .map(|x| Value::String(x.to_string()))
-                            .unwrap_or(Value::Null),
-                    );
-                });
-        }
-        DataType::LargeUtf8 => {
-            let array = array.as_any().downcast_ref::<Utf8Array<i64>>().unwrap();
-            rows.iter_mut()
-                .zip(array.iter())
-                .take(row_count)
-                .for_each(|(row, value)| {
-                    row.insert(
-                        col_name.to_string(),
-                        value
-                            .map(|x| Value::String(x.to_string()))
-                            .unwrap_or(Value::Null),
-                    );
-                });
-        }
-        DataType::Struct(_) => {
-            let array = array.as_any().downcast_ref::<StructArray>().unwrap();
-            let inner_objs = struct_array_to_jsonmap_array(array, row_count);
-            rows.iter_mut()
-                .take(row_count)
-                .zip(zip_validity(
-                    inner_objs.into_iter(),
-                    array.validity().map(|v| v.iter()),
-                ))
-                .for_each(|(row, obj)| {
-                    row.insert(
-                        col_name.to_string(),
-                        obj.map(Value::Object).unwrap_or(Value::Null),
-                    );
-                });
-        }
-        DataType::List(_) => {
-            let array = array.as_any().downcast_ref::<ListArray<i32>>().unwrap();
-            rows.iter_mut()
-                .zip(array.iter())
-                .take(row_count)
-                .for_each(|(row, value)| {
-                    row.insert(
-                        col_name.to_string(),
-                        value
-                            .map(|x| write_array(x.as_ref()))
-                            .unwrap_or(Value::Null),
-                    );
-                });
-        }
-        DataType::LargeList(_) => {
-            let array = array.as_any().downcast_ref::<ListArray<i64>>().unwrap();
-            rows.iter_mut()
-                .zip(array.iter())
-                .take(row_count)
-                .for_each(|(row, value)| {
-                    row.insert(
-                        col_name.to_string(),
-                        value
-                            .map(|x| write_array(x.as_ref()))
-                            .unwrap_or(Value::Null),
-                    );
-                });
-        }
-        _ => {
-            panic!("Unsupported datatype: {:#?}", array.data_type());
-        }
-    }
-}
-
 /// Serializes a [`RecordBatch`] into Json
 /// # Example
 /// ```
@@ -362,6 +487,15 @@ fn set_column_for_json_rows(
 /// );
 /// ```
 pub fn write_record_batches(batches: &[RecordBatch]) -> Vec<Map<String, Value>> {
+    write_record_batches_with_options(batches, &SerializeOptions::default())
+}
+
+/// Serializes a [`RecordBatch`] into Json according to `options`.
+/// See [`write_record_batches`] for an example.
+pub fn write_record_batches_with_options(
+    batches: &[RecordBatch],
+    options: &SerializeOptions,
+) -> Vec<Map<String, Value>> {
     let mut rows: Vec<Map<String, Value>> = std::iter::repeat(Map::new())
         .take(batches.iter().map(|b| b.num_rows()).sum())
         .collect();
@@ -373,7 +507,13 @@ pub fn write_record_batches(batches: &[RecordBatch]) -> Vec<Map<String, Value>>
             let row_count = batch.num_rows();
             batch.columns().iter().enumerate().for_each(|(j, col)| {
                 let col_name = schema.field(j).name();
-                set_column_for_json_rows(&mut rows[base..], row_count, col.as_ref(), col_name);
+                set_column_for_json_rows(
+                    &mut rows[base..],
+                    row_count,
+                    col.as_ref(),
+                    col_name,
+                    options,
+                );
             });
             base += row_count;
         });
@@ -0,0 +1,490 @@
+//! Serializes arrays directly into JSON bytes, without intermediate [`serde_json::Value`]s.
+use std::io::Write;
+
+use crate::error::Result;
+use crate::io::iterator::{BufStreamingIterator, StreamingIterator};
+use crate::temporal_conversions;
+use crate::types::{days_ms, months_days_ns, NativeType};
+use crate::{array::*, datatypes::*, record_batch::RecordBatch};
+
+use super::super::BinaryFormat;
+use super::serialize::decimal_to_string;
+use super::{JsonFormat, SerializeOptions, TIMESTAMP_FORMAT, TIMESTAMP_TZ_FORMAT};
+
+type Serializer<'a> = Box<dyn StreamingIterator<Item = [u8]> + 'a>;
+
+fn new_iterator<'a, I, T, F>(iter: I, mut f: F) -> Serializer<'a>
+where
+    I: Iterator<Item = Option<T>> + 'a,
+    T: 'a,
+    F: FnMut(T, &mut Vec<u8>) + 'a,
+{
+    Box::new(BufStreamingIterator::new(
+        iter,
+        move |x, buf| match x {
+            Some(x) => f(x, buf),
+            None => buf.extend_from_slice(b"null"),
+        },
+        vec![],
+    ))
+}
+
+/// Writes `value` as JSON. Writing to a `Vec<u8>` only fails for maps with non-string keys,
+/// which are never written by this module.
+#[inline]
+fn write_value<T: serde::Serialize + ?Sized>(value: &T, buf: &mut Vec<u8>) {
+    serde_json::to_writer(buf, value).unwrap()
+}
+
+/// Writes each slot of `array` via `f`
+fn primitive_serializer<'a, T, F>(array: &'a dyn Array, f: F) -> Serializer<'a>
+where
+    T: NativeType,
+    F: Fn(T, &mut Vec<u8>) + 'a,
+{
+    let array = array.as_any().downcast_ref::<PrimitiveArray<T>>().unwrap();
+    new_iterator(array.iter(), move |x, buf| f(*x, buf))
+}
+
+fn integer_serializer<'a, T: NativeType + std::fmt::Display>(
+    array: &'a dyn Array,
+) -> Serializer<'a> {
+    primitive_serializer(array, |x: T, buf| {
+        let _ = write!(buf, "{}", x);
+    })
+}
+
+fn utf8_serializer<'a, O: Offset>(array: &'a dyn Array) -> Serializer<'a> {
+    let array = array.as_any().downcast_ref::<Utf8Array<O>>().unwrap();
+    new_iterator(array.iter(), write_value)
+}
+
+fn write_binary(bytes: &[u8], format: BinaryFormat, buf: &mut Vec<u8>) {
+    match format {
+        BinaryFormat::Utf8 => write_value(String::from_utf8_lossy(bytes).as_ref(), buf),
+        BinaryFormat::Base64 => {
+            buf.push(b'"');
+            buf.extend_from_slice(base64::encode(bytes).as_bytes());
+            buf.push(b'"');
+        }
+        BinaryFormat::Hex => {
+            buf.push(b'"');
+            buf.extend_from_slice(hex::encode(bytes).as_bytes());
+            buf.push(b'"');
+        }
+    }
+}
+
+fn binary_serializer<'a, O: Offset>(array: &'a dyn Array, format: BinaryFormat) -> Serializer<'a> {
+    let array = array.as_any().downcast_ref::<BinaryArray<O>>().unwrap();
+    new_iterator(array.iter(), move |x, buf| write_binary(x, format, buf))
+}
+
+/// Writes `item` (whose [`std::fmt::Display`] never needs escaping) as a JSON string
+#[inline]
+fn write_quoted<T: std::fmt::Display>(item: T, buf: &mut Vec<u8>) {
+    let _ = write!(buf, "\"{}\"", item);
+}
+
+fn timestamp_tz_serializer<'a, T>(
+    array: &'a dyn Array,
+    time_unit: TimeUnit,
+    timezone: T,
+) -> Serializer<'a>
+where
+    T: chrono::TimeZone + 'a,
+    T::Offset: std::fmt::Display,
+{
+    primitive_serializer(array, move |x: i64, buf| {
+        let datetime = temporal_conversions::timestamp_to_datetime(x, time_unit, &timezone);
+        write_quoted(datetime.format(TIMESTAMP_TZ_FORMAT), buf)
+    })
+}
+
+#[cfg(feature = "chrono-tz")]
+fn timestamp_named_tz_serializer<'a>(
+    array: &'a dyn Array,
+    time_unit: TimeUnit,
+    timezone: &str,
+) -> Serializer<'a> {
+    let timezone = temporal_conversions::parse_offset_tz(timezone).unwrap();
+    timestamp_tz_serializer(array, time_unit, timezone)
+}
+
+#[cfg(not(feature = "chrono-tz"))]
+fn timestamp_named_tz_serializer<'a>(
+    _: &'a dyn Array,
+    _: TimeUnit,
+    timezone: &str,
+) -> Serializer<'a> {
+    panic!(
+        "Invalid Offset format \"{}\" (must be [-]00:00) or chrono-tz feature not active",
+        timezone
+    )
+}
+
+/// Writes `length` items of `values` as a JSON array
+#[inline]
+fn write_list(values: &mut Serializer, length: usize, buf: &mut Vec<u8>) {
+    buf.push(b'[');
+    (0..length).for_each(|i| {
+        if i > 0 {
+            buf.push(b',');
+        }
+        values.advance();
+        buf.extend_from_slice(values.get().unwrap());
+    });
+    buf.push(b']');
+}
+
+#[inline]
+fn skip(values: &mut Serializer, length: usize) {
+    (0..length).for_each(|_| values.advance());
+}
+
+fn list_serializer<'a, O: Offset>(
+    array: &'a dyn Array,
+    options: &SerializeOptions,
+) -> Serializer<'a> {
+    let array = array.as_any().downcast_ref::<ListArray<O>>().unwrap();
+    let offsets = array.offsets();
+    let mut values = new_serializer(array.values().as_ref(), options);
+    // the values before the first offset do not belong to this array
+    skip(&mut values, offsets[0].to_usize());
+
+    Box::new(BufStreamingIterator::new(
+        offsets.windows(2).enumerate(),
+        move |(i, window), buf| {
+            let length = (window[1] - window[0]).to_usize();
+            if array.is_valid(i) {
+                write_list(&mut values, length, buf)
+            } else {
+                skip(&mut values, length);
+                buf.extend_from_slice(b"null")
+            }
+        },
+        vec![],
+    ))
+}
+
+fn fixed_size_list_serializer<'a>(
+    array: &'a dyn Array,
+    options: &SerializeOptions,
+) -> Serializer<'a> {
+    let array = array.as_any().downcast_ref::<FixedSizeListArray>().unwrap();
+    let size = FixedSizeListArray::get_child_and_size(array.data_type()).1;
+    let mut values = new_serializer(array.values().as_ref(), options);
+
+    Box::new(BufStreamingIterator::new(
+        0..array.len(),
+        move |i, buf| {
+            if array.is_valid(i) {
+                write_list(&mut values, size, buf)
+            } else {
+                skip(&mut values, size);
+                buf.extend_from_slice(b"null")
+            }
+        },
+        vec![],
+    ))
+}
+
+/// Serializers of each field and their (JSON-encoded) names. Fields of type
+/// [`DataType::Null`] are not written.
+fn field_serializers<'a>(
+    fields: &'a [Field],
+    values: &'a [std::sync::Arc<dyn Array>],
+    options: &SerializeOptions,
+) -> Vec<(Vec<u8>, Serializer<'a>)> {
+    fields
+        .iter()
+        .zip(values.iter())
+        .filter(|(field, _)| field.data_type() != &DataType::Null)
+        .map(|(field, values)| {
+            let mut name = vec![];
+            write_value(field.name(), &mut name);
+            name.push(b':');
+            (name, new_serializer(values.as_ref(), options))
+        })
+        .collect()
+}
+
+/// Writes the next item of each of `fields` as a JSON object
+#[inline]
+fn write_object(fields: &mut [(Vec<u8>, Serializer)], buf: &mut Vec<u8>) {
+    buf.push(b'{');
+    fields
+        .iter_mut()
+        .enumerate()
+        .for_each(|(i, (name, values))| {
+            if i > 0 {
+                buf.push(b',');
+            }
+            buf.extend_from_slice(name);
+            values.advance();
+            buf.extend_from_slice(values.get().unwrap());
+        });
+    buf.push(b'}');
+}
+
+fn struct_serializer<'a>(array: &'a dyn Array, options: &SerializeOptions) -> Serializer<'a> {
+    let array = array.as_any().downcast_ref::<StructArray>().unwrap();
+    let mut fields = field_serializers(array.fields(), array.values(), options);
+
+    Box::new(BufStreamingIterator::new(
+        0..array.len(),
+        move |i, buf| {
+            if array.is_valid(i) {
+                write_object(&mut fields, buf)
+            } else {
+                fields.iter_mut().for_each(|(_, values)| values.advance());
+                buf.extend_from_slice(b"null")
+            }
+        },
+        vec![],
+    ))
+}
+
+/// Writes the next `length` entries of `keys` and `values` as a JSON object. JSON keys are
+/// strings: keys that are not strings are written as strings of their JSON text.
+#[inline]
+fn write_map(keys: &mut Serializer, values: &mut Serializer, length: usize, buf: &mut Vec<u8>) {
+    buf.push(b'{');
+    (0..length).for_each(|i| {
+        if i > 0 {
+            buf.push(b',');
+        }
+        keys.advance();
+        let key = keys.get().unwrap();
+        if key.first() == Some(&b'"') {
+            buf.extend_from_slice(key)
+        } else {
+            // the JSON text of a value is valid utf8
+            write_value(std::str::from_utf8(key).unwrap(), buf)
+        }
+        buf.push(b':');
+        values.advance();
+        buf.extend_from_slice(values.get().unwrap());
+    });
+    buf.push(b'}');
+}
+
+fn map_serializer<'a>(array: &'a dyn Array, options: &SerializeOptions) -> Serializer<'a> {
+    let array = array.as_any().downcast_ref::<MapArray>().unwrap();
+    let entries = array
+        .field()
+        .as_any()
+        .downcast_ref::<StructArray>()
+        .unwrap();
+    let mut keys = new_serializer(entries.values()[0].as_ref(), options);
+    let mut values = new_serializer(entries.values()[1].as_ref(), options);
+    let offsets = array.offsets();
+    // the entries before the first offset do not belong to this array
+    skip(&mut keys, offsets[0] as usize);
+    skip(&mut values, offsets[0] as usize);
+
+    Box::new(BufStreamingIterator::new(
+        offsets.windows(2).enumerate(),
+        move |(i, window), buf| {
+            let length = (window[1] - window[0]) as usize;
+            if array.is_valid(i) {
+                write_map(&mut keys, &mut values, length, buf)
+            } else {
+                skip(&mut keys, length);
+                skip(&mut values, length);
+                buf.extend_from_slice(b"null")
+            }
+        },
+        vec![],
+    ))
+}
+
+fn union_serializer<'a>(array: &'a dyn Array, options: &SerializeOptions) -> Serializer<'a> {
+    let array = array.as_any().downcast_ref::<UnionArray>().unwrap();
+    // each field is serialized once: its slots are selected in increasing order (the
+    // offsets of each field of a dense union are increasing)
+    let mut fields = array
+        .fields()
+        .iter()
+        .map(|field| (new_serializer(field.as_ref(), options), 0))
+        .collect::<Vec<_>>();
+
+    Box::new(BufStreamingIterator::new(
+        0..array.len(),
+        move |i, buf| {
+            let (field, index) = array.index(i);
+            // `advanced` is the number of slots of `values` that were advanced
+            let (values, advanced) = &mut fields[field];
+            while *advanced <= index {
+                values.advance();
+                *advanced += 1;
+            }
+            buf.extend_from_slice(values.get().unwrap())
+        },
+        vec![],
+    ))
+}
+
+fn dictionary_serializer<'a, K: DictionaryKey>(
+    array: &'a dyn Array,
+    options: &SerializeOptions,
+) -> Serializer<'a> {
+    let array = array.as_any().downcast_ref::<DictionaryArray<K>>().unwrap();
+
+    // the values are serialized once and copied for every key that references them
+    let mut values = new_serializer(array.values().as_ref(), options);
+    let mut serialized = Vec::with_capacity(array.values().len());
+    while let Some(value) = values.next() {
+        serialized.push(value.to_vec());
+    }
+
+    new_iterator(array.keys().iter(), move |key, buf| {
+        buf.extend_from_slice(&serialized[key.to_usize().unwrap()])
+    })
+}
+
+/// Returns a [`StreamingIterator`] that yields the JSON representation of each slot of `array`.
+/// The representation of each type is the same as [`write_record_batches_with_options`]'s
+/// (see [`SerializeOptions`]).
+/// # Panics
+/// This function panics for [`DataType::Float16`] and for timestamps with an invalid timezone.
+///
+/// [`write_record_batches_with_options`]: super::write_record_batches_with_options
+pub fn new_serializer<'a>(
+    array: &'a dyn Array,
+    options: &SerializeOptions,
+) -> Box<dyn StreamingIterator<Item = [u8]> + 'a> {
+    use DataType::*;
+    match array.data_type().to_logical_type() {
+        Null => new_iterator(std::iter::repeat(None::<()>).take(array.len()), |_, _| {}),
+        Boolean => {
+            let array = array.as_any().downcast_ref::<BooleanArray>().unwrap();
+            new_iterator(array.iter(), |x, buf| {
+                buf.extend_from_slice(if x { b"true" } else { b"false" })
+            })
+        }
+        Int8 => integer_serializer::<i8>(array),
+        Int16 => integer_serializer::<i16>(array),
+        Int32 | Interval(IntervalUnit::YearMonth) => integer_serializer::<i32>(array),
+        Int64 | Duration(_) => integer_serializer::<i64>(array),
+        UInt8 => integer_serializer::<u8>(array),
+        UInt16 => integer_serializer::<u16>(array),
+        UInt32 => integer_serializer::<u32>(array),
+        UInt64 => integer_serializer::<u64>(array),
+        Float16 => unreachable!(),
+        Float32 => primitive_serializer(array, |x: f32, buf| {
+            write_value(&(f64::round(x as f64 * 1000.0) / 1000.0), buf)
+        }),
+        Float64 => primitive_serializer(array, |x: f64, buf| write_value(&x, buf)),
+        Utf8 => utf8_serializer::<i32>(array),
+        LargeUtf8 => utf8_serializer::<i64>(array),
+        Date32 => primitive_serializer(array, |x: i32, buf| {
+            write_quoted(temporal_conversions::date32_to_date(x), buf)
+        }),
+        Date64 => primitive_serializer(array, |x: i64, buf| {
+            write_quoted(temporal_conversions::date64_to_date(x), buf)
+        }),
+        Time32(TimeUnit::Second) => primitive_serializer(array, |x: i32, buf| {
+            write_quoted(temporal_conversions::time32s_to_time(x), buf)
+        }),
+        Time32(_) => primitive_serializer(array, |x: i32, buf| {
+            write_quoted(temporal_conversions::time32ms_to_time(x), buf)
+        }),
+        Time64(TimeUnit::Microsecond) => primitive_serializer(array, |x: i64, buf| {
+            write_quoted(temporal_conversions::time64us_to_time(x), buf)
+        }),
+        Time64(_) => primitive_serializer(array, |x: i64, buf| {
+            write_quoted(temporal_conversions::time64ns_to_time(x), buf)
+        }),
+        Timestamp(time_unit, None) => {
+            let time_unit = *time_unit;
+            primitive_serializer(array, move |x: i64, buf| {
+                let datetime = temporal_conversions::timestamp_to_naive_datetime(x, time_unit);
+                write_quoted(datetime.format(TIMESTAMP_FORMAT), buf)
+            })
+        }
+        Timestamp(time_unit, Some(timezone)) => {
+            match temporal_conversions::parse_offset(timezone) {
+                Ok(offset) => timestamp_tz_serializer(array, *time_unit, offset),
+                Err(_) => timestamp_named_tz_serializer(array, *time_unit, timezone),
+            }
+        }
+        Interval(IntervalUnit::DayTime) => primitive_serializer(array, |x: days_ms, buf| {
+            let _ = write!(
+                buf,
+                "{{\"days\":{},\"milliseconds\":{}}}",
+                x.days(),
+                x.milliseconds()
+            );
+        }),
+        Interval(IntervalUnit::MonthDayNano) => {
+            primitive_serializer(array, |x: months_days_ns, buf| {
+                let _ = write!(
+                    buf,
+                    "{{\"months\":{},\"days\":{},\"nanoseconds\":{}}}",
+                    x.months(),
+                    x.days(),
+                    x.ns()
+                );
+            })
+        }
+        Decimal(_, scale) => {
+            let scale = *scale;
+            let as_string = options.decimal_as_string;
+            primitive_serializer(array, move |x: i128, buf| {
+                let string = decimal_to_string(x, scale);
+                if as_string {
+                    write_quoted(string, buf)
+                } else {
+                    // same as `f64`: non-finite values are written as `null`
+                    write_value(&string.parse::<f64>().unwrap_or(f64::NAN), buf)
+                }
+            })
+        }
+        Binary => binary_serializer::<i32>(array, options.binary_format),
+        LargeBinary => binary_serializer::<i64>(array, options.binary_format),
+        FixedSizeBinary(_) => {
+            let array = array
+                .as_any()
+                .downcast_ref::<FixedSizeBinaryArray>()
+                .unwrap();
+            let format = options.binary_format;
+            new_iterator(array.iter(), move |x, buf| write_binary(x, format, buf))
+        }
+        List(_) => list_serializer::<i32>(array, options),
+        LargeList(_) => list_serializer::<i64>(array, options),
+        FixedSizeList(_, _) => fixed_size_list_serializer(array, options),
+        Struct(_) => struct_serializer(array, options),
+        Dictionary(key_type, _) => match_integer_type!(key_type, |$T| {
+            dictionary_serializer::<$T>(array, options)
+        }),
+        Map(_, _) => map_serializer(array, options),
+        Union(_, _, _) => union_serializer(array, options),
+        Extension(_, _, _) => unreachable!(),
+    }
+}
+
+/// Serializes `batch` into `buffer` as JSON objects, one per row, formatted according to `format`.
+/// Columns of type [`DataType::Null`] are not written.
+///
+/// `buffer` is not cleared. The bytes written before the first row
+/// ([`JsonFormat::start_row`] of the first row) are left to the caller, so that blocks of
+/// rows serialized independently (e.g. in parallel) can be written in sequence
+/// via [`Writer::write_serialized`](super::Writer::write_serialized).
+pub fn serialize<F: JsonFormat>(
+    batch: &RecordBatch,
+    format: &F,
+    options: &SerializeOptions,
+    buffer: &mut Vec<u8>,
+) -> Result<()> {
+    let schema = batch.schema();
+    let mut fields = field_serializers(schema.fields(), batch.columns(), options);
+
+    (0..batch.num_rows()).try_for_each(|row| {
+        if row > 0 {
+            format.start_row(buffer, false)?;
+        }
+        write_object(&mut fields, buffer);
+        format.end_row(buffer)
+    })
+}
@@ -101,6 +101,39 @@
 //! let buf = writer.into_inner();
 //! assert_eq!(r#"[{"a":1},{"a":2},{"a":3}]"#, String::from_utf8(buf).unwrap())
 //! ```
+//!
+//! ## Serializing in parallel
+//!
+//! Writers serialize each [`RecordBatch`] directly into bytes via [`serialize`]. This function
+//! can also be called independently (e.g. one batch per thread), and its output written in order
+//! via [`Writer::write_serialized`]:
+//!
+//! ```
+//! use std::sync::Arc;
+//!
+//! use arrow2::array::Int32Array;
+//! use arrow2::datatypes::{DataType, Field, Schema};
+//! use arrow2::io::json;
+//! use arrow2::record_batch::RecordBatch;
+//!
+//! let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
+//! let a = Int32Array::from_slice(&[1, 2, 3]);
+//! let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(a)]).unwrap();
+//!
+//! // this can be performed on a different thread
+//! let options = json::SerializeOptions::default();
+//! let mut block = vec![];
+//! json::serialize(&batch, &json::JsonArray::default(), &options, &mut block).unwrap();
+//!
+//! let mut writer = json::ArrayWriter::new(vec![]);
+//! writer.write_serialized(&block).unwrap();
+//! writer.write_serialized(&block).unwrap();
+//! writer.finish().unwrap();
+//! assert_eq!(
+//!     r#"[{"a":1},{"a":2},{"a":3},{"a":1},{"a":2},{"a":3}]"#,
+//!     String::from_utf8(writer.into_inner()).unwrap()
+//! )
+//! ```
 
 use std::{fmt::Debug, io::Write};
 
@@ -109,7 +142,7 @@ use serde_json::Value;
 use crate::error::Result;
 use crate::record_batch::RecordBatch;
 
-use super::write_record_batches;
+use super::{serialize, SerializeOptions};
 
 /// This trait defines how to format a sequence of JSON objects to a
 /// byte stream.
@@ -209,6 +242,12 @@ where
 
     /// Determines how the byte stream is formatted
     format: F,
+
+    /// Determines how arrays are serialized
+    options: SerializeOptions,
+
+    /// Buffer re-used to serialize batches
+    buffer: Vec<u8>,
 }
 
 impl<W, F> Writer<W, F>
@@ -223,31 +262,61 @@ where
             started: false,
             finished: false,
             format: F::default(),
+            options: SerializeOptions::default(),
+            buffer: vec![],
         }
     }
 
-    /// Write a single JSON row to the output writer
-    pub fn write_row(&mut self, row: &Value) -> Result<()> {
+    /// Sets the [`SerializeOptions`] used to serialize [`RecordBatch`]es
+    pub fn with_options(mut self, options: SerializeOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Writes the start of the stream (if not yet written) and of a new row.
+    fn start_row(&mut self) -> Result<()> {
         let is_first_row = !self.started;
         if !self.started {
             self.format.start_stream(&mut self.writer)?;
             self.started = true;
         }
+        self.format.start_row(&mut self.writer, is_first_row)
+    }
 
-        self.format.start_row(&mut self.writer, is_first_row)?;
+    /// Write a single JSON row to the output writer
+    pub fn write_row(&mut self, row: &Value) -> Result<()> {
+        self.start_row()?;
         self.writer.write_all(&serde_json::to_vec(row)?)?;
         self.format.end_row(&mut self.writer)?;
         Ok(())
     }
 
-    /// Convert the [`RecordBatch`] into JSON rows, and write them to the output
-    pub fn write_batches(&mut self, batches: &[RecordBatch]) -> Result<()> {
-        for row in write_record_batches(batches) {
-            self.write_row(&Value::Object(row))?;
+    /// Writes a block of rows serialized by [`serialize`] with this writer's [`JsonFormat`].
+    /// Empty blocks are ignored.
+    ///
+    /// Blocks can be serialized independently (e.g. in parallel, one per batch), and written
+    /// in sequence with this function.
+    pub fn write_serialized(&mut self, block: &[u8]) -> Result<()> {
+        if block.is_empty() {
+            return Ok(());
         }
+        self.start_row()?;
+        self.writer.write_all(block)?;
         Ok(())
     }
 
+    /// Serialize the [`RecordBatch`]es into JSON rows, and write them to the output
+    pub fn write_batches(&mut self, batches: &[RecordBatch]) -> Result<()> {
+        let mut buffer = std::mem::take(&mut self.buffer);
+        let result = batches.iter().try_for_each(|batch| {
+            buffer.clear();
+            serialize(batch, &self.format, &self.options, &mut buffer)?;
+            self.write_serialized(&buffer)
+        });
+        self.buffer = buffer;
+        result
+    }
+
     /// Finishes the output stream. This function must be called after
     /// all record batches have been produced. (e.g. producing the final `']'` if writing
     /// arrays.
@@ -19,6 +19,9 @@ pub mod csv;
 #[cfg_attr(docsrs, doc(cfg(feature = "io_json")))]
 pub mod json;
 
+#[cfg(any(feature = "io_csv_write", feature = "io_json", feature = "io_avro"))]
+mod iterator;
+
 #[cfg(feature = "io_ipc")]
 #[cfg_attr(docsrs, doc(cfg(feature = "io_ipc")))]
 pub mod ipc;
@@ -45,9 +45,9 @@ pub fn date32_to_date(days: i32) -> NaiveDate {
 pub fn date64_to_datetime(v: i64) -> NaiveDateTime {
     NaiveDateTime::from_timestamp(
         // extract seconds from milliseconds
-        v / MILLISECONDS,
+        v.div_euclid(MILLISECONDS),
         // discard extracted seconds and convert milliseconds to nanoseconds
-        (v % MILLISECONDS * MICROSECONDS) as u32,
+        (v.rem_euclid(MILLISECONDS) * MICROSECONDS) as u32,
     )
 }
 
@@ -110,9 +110,9 @@ pub fn timestamp_s_to_datetime(seconds: i64) -> NaiveDateTime {
 pub fn timestamp_ms_to_datetime(v: i64) -> NaiveDateTime {
     NaiveDateTime::from_timestamp(
         // extract seconds from milliseconds
-        v / MILLISECONDS,
+        v.div_euclid(MILLISECONDS),
         // discard extracted seconds and convert milliseconds to nanoseconds
-        (v % MILLISECONDS * MICROSECONDS) as u32,
+        (v.rem_euclid(MILLISECONDS) * MICROSECONDS) as u32,
     )
 }
 
@@ -121,9 +121,9 @@ pub fn timestamp_ms_to_datetime(v: i64) -> NaiveDateTime {
 pub fn timestamp_us_to_datetime(v: i64) -> NaiveDateTime {
     NaiveDateTime::from_timestamp(
         // extract seconds from microseconds
-        v / MICROSECONDS,
+        v.div_euclid(MICROSECONDS),
         // discard extracted seconds and convert microseconds to nanoseconds
-        (v % MICROSECONDS * MILLISECONDS) as u32,
+        (v.rem_euclid(MICROSECONDS) * MILLISECONDS) as u32,
     )
 }
 
@@ -132,9 +132,9 @@ pub fn timestamp_us_to_datetime(v: i64) -> NaiveDateTime {
 pub fn timestamp_ns_to_datetime(v: i64) -> NaiveDateTime {
     NaiveDateTime::from_timestamp(
         // extract seconds from nanoseconds
-        v / NANOSECONDS,
+        v.div_euclid(NANOSECONDS),
         // discard extracted seconds
-        (v % NANOSECONDS) as u32,
+        v.rem_euclid(NANOSECONDS) as u32,
     )
 }
 
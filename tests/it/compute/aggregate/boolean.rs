@@ -0,0 +1,59 @@
+use arrow2::array::*;
+use arrow2::compute::aggregate::{all, all_boolean, any, any_boolean};
+use arrow2::datatypes::DataType;
+use arrow2::scalar::{BooleanScalar, Scalar};
+
+#[test]
+fn any_all() {
+    let a = BooleanArray::from(&[Some(true), None, Some(true)]);
+    assert_eq!(Some(true), any_boolean(&a));
+    assert_eq!(Some(true), all_boolean(&a));
+
+    let a = BooleanArray::from(&[Some(false), None, Some(true)]);
+    assert_eq!(Some(true), any_boolean(&a));
+    assert_eq!(Some(false), all_boolean(&a));
+
+    let a = BooleanArray::from_slice(&[false, false]);
+    assert_eq!(Some(false), any_boolean(&a));
+    assert_eq!(Some(false), all_boolean(&a));
+
+    let a = BooleanArray::from(&[None, None]);
+    assert_eq!(None, any_boolean(&a));
+    assert_eq!(None, all_boolean(&a));
+}
+
+#[test]
+fn any_all_null_values() {
+    // the values of null slots are ignored
+    let a = BooleanArray::from_data(
+        DataType::Boolean,
+        vec![true, false, true].into(),
+        Some(vec![false, true, false].into()),
+    );
+    assert_eq!(Some(false), any_boolean(&a));
+
+    let a = BooleanArray::from_data(
+        DataType::Boolean,
+        vec![false, true, false].into(),
+        Some(vec![false, true, false].into()),
+    );
+    assert_eq!(Some(true), all_boolean(&a));
+    assert_eq!(Some(true), all_boolean(&a.slice(1, 2)));
+}
+
+#[test]
+fn any_all_dyn() {
+    let a = BooleanArray::from(&[Some(false), None]);
+    assert_eq!(
+        &BooleanScalar::new(Some(false)) as &dyn Scalar,
+        any(&a).unwrap().as_ref()
+    );
+    assert_eq!(
+        &BooleanScalar::new(Some(false)) as &dyn Scalar,
+        all(&a).unwrap().as_ref()
+    );
+
+    let a = Int32Array::from_slice(&[1]);
+    assert!(any(&a).is_err());
+    assert!(all(&a).is_err());
+}
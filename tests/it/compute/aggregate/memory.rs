@@ -17,3 +17,15 @@ fn utf8() {
     let a = Utf8Array::<i32>::from_slice(&["aaa"]);
     assert_eq!(3 + 2 * std::mem::size_of::<i32>(), estimated_bytes_size(&a));
 }
+
+#[test]
+fn fixed_size_list() {
+    use std::sync::Arc;
+
+    use arrow2::datatypes::DataType;
+
+    let values = Arc::new(Int32Array::from_slice(&[1, 2, 3, 4, 5, 6]));
+    let data_type = FixedSizeListArray::default_datatype(DataType::Int32, 3);
+    let a = FixedSizeListArray::from_data(data_type, values, None);
+    assert_eq!(6 * std::mem::size_of::<i32>(), estimated_bytes_size(&a));
+}
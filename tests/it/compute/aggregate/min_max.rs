@@ -1,7 +1,8 @@
 use arrow2::compute::aggregate::{
-    max_binary, max_boolean, max_primitive, max_string, min_binary, min_boolean, min_primitive,
-    min_string,
+    max_binary, max_boolean, max_primitive, max_string, min_binary, min_boolean, min_max,
+    min_max_binary, min_max_boolean, min_max_primitive, min_max_string, min_primitive, min_string,
 };
+use arrow2::scalar::{PrimitiveScalar, Scalar, Utf8Scalar};
 use arrow2::{array::*, datatypes::DataType};
 
 #[test]
@@ -186,3 +187,67 @@ fn test_binary_min_max_1() {
     assert_eq!(Some("a".as_bytes()), min_binary(&a));
     assert_eq!(Some("b".as_bytes()), max_binary(&a));
 }
+
+#[test]
+fn min_max_primitive_single_pass() {
+    let a = Int32Array::from(&[Some(5), None, None, Some(8), Some(-1)]);
+    assert_eq!(Some((-1, 8)), min_max_primitive(&a));
+
+    // larger than a SIMD chunk, with and without an offset
+    let a = (0..200i64)
+        .map(|x| (x % 3 != 0).then(|| x - 100))
+        .collect::<Int64Array>();
+    assert_eq!(Some((-99, 99)), min_max_primitive(&a));
+    let a = a.slice(2, 150);
+    assert_eq!(Some((-98, 51)), min_max_primitive(&a));
+
+    let a = Float64Array::from_slice(&[1.0, f64::NAN, -2.0]);
+    assert_eq!(Some((-2.0, 1.0)), min_max_primitive(&a));
+
+    let a = Int32Array::from(&[None, None]);
+    assert_eq!(None, min_max_primitive(&a));
+}
+
+#[test]
+fn min_max_non_primitive() {
+    let a = Utf8Array::<i32>::from(&[Some("b"), None, Some("a"), Some("c")]);
+    assert_eq!(Some(("a", "c")), min_max_string(&a));
+
+    let a = BinaryArray::<i32>::from(&[None, Some(b"b"), Some(b"a")]);
+    assert_eq!(Some(("a".as_bytes(), "b".as_bytes())), min_max_binary(&a));
+
+    let a = BooleanArray::from(&[Some(true), None, Some(true)]);
+    assert_eq!(Some((true, true)), min_max_boolean(&a));
+    let a = BooleanArray::from(&[None, Some(false), Some(true)]);
+    assert_eq!(Some((false, true)), min_max_boolean(&a));
+    let a = BooleanArray::from(&[None, None]);
+    assert_eq!(None, min_max_boolean(&a));
+}
+
+#[test]
+fn min_max_dyn() {
+    let a = Int32Array::from(&[Some(5), None, Some(2)]).to(DataType::Date32);
+    let (min, max) = min_max(&a).unwrap();
+    assert_eq!(
+        &PrimitiveScalar::<i32>::new(DataType::Date32, Some(2)) as &dyn Scalar,
+        min.as_ref()
+    );
+    assert_eq!(
+        &PrimitiveScalar::<i32>::new(DataType::Date32, Some(5)) as &dyn Scalar,
+        max.as_ref()
+    );
+
+    let a = Utf8Array::<i32>::from(&[None::<&str>, None]);
+    let (min, max) = min_max(&a).unwrap();
+    assert_eq!(
+        &Utf8Scalar::<i32>::new(None::<&str>) as &dyn Scalar,
+        min.as_ref()
+    );
+    assert_eq!(
+        &Utf8Scalar::<i32>::new(None::<&str>) as &dyn Scalar,
+        max.as_ref()
+    );
+
+    let a = NullArray::from_data(DataType::Null, 2);
+    assert!(min_max(&a).is_err());
+}
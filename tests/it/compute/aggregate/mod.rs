@@ -1,3 +1,7 @@
+mod boolean;
 mod memory;
 mod min_max;
+mod mode;
+mod product;
+mod stats;
 mod sum;
@@ -0,0 +1,35 @@
+use arrow2::array::*;
+use arrow2::compute::aggregate::{can_mode, mode, mode_primitive};
+use arrow2::datatypes::DataType;
+use arrow2::scalar::{PrimitiveScalar, Scalar, Utf8Scalar};
+
+#[test]
+fn mode_primitive_values() {
+    let a = Int32Array::from(&[Some(1), None, Some(2), None, Some(2), None]);
+    assert_eq!(Some(2), mode_primitive(&a).unwrap());
+
+    // ties resolve to the value that appears first
+    let a = Float64Array::from_slice(&[3.0, 1.0, 1.0, 3.0]);
+    assert_eq!(Some(3.0), mode_primitive(&a).unwrap());
+
+    let a = Int32Array::from(&[None, None]);
+    assert_eq!(None, mode_primitive(&a).unwrap());
+}
+
+#[test]
+fn mode_dyn() {
+    let a = Utf8Array::<i32>::from(&[Some("a"), Some("b"), None, Some("b")]);
+    assert_eq!(
+        &Utf8Scalar::<i32>::new(Some("b")) as &dyn Scalar,
+        mode(&a).unwrap().as_ref()
+    );
+
+    let a = Int64Array::from(&[None, None]).to(DataType::Date64);
+    assert_eq!(
+        &PrimitiveScalar::<i64>::new(DataType::Date64, None) as &dyn Scalar,
+        mode(&a).unwrap().as_ref()
+    );
+
+    assert!(!can_mode(&DataType::Null));
+    assert!(mode(&NullArray::from_data(DataType::Null, 1)).is_err());
+}
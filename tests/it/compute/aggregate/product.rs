@@ -0,0 +1,60 @@
+use arrow2::array::*;
+use arrow2::compute::aggregate::{can_product, product, product_primitive};
+use arrow2::datatypes::DataType;
+use arrow2::scalar::{PrimitiveScalar, Scalar};
+
+#[test]
+fn product_int() {
+    let a = Int32Array::from(&[Some(2), None, Some(3), Some(-4)]);
+    assert_eq!(Some(-24), product_primitive(&a));
+
+    let a = Int32Array::from(&[None, None]);
+    assert_eq!(None, product_primitive(&a));
+}
+
+#[test]
+fn product_large() {
+    // larger than a SIMD chunk, with and without an offset
+    let a = (0..100)
+        .map(|x| Some(if x % 10 == 0 { 2u64 } else { 1 }))
+        .collect::<UInt64Array>();
+    assert_eq!(Some(1024), product_primitive(&a));
+    assert_eq!(Some(512), product_primitive(&a.slice(1, 99)));
+
+    let a = (0..100)
+        .map(|x| if x % 10 == 0 { Some(2u64) } else { None })
+        .collect::<UInt64Array>();
+    assert_eq!(Some(1024), product_primitive(&a));
+    assert_eq!(Some(512), product_primitive(&a.slice(1, 99)));
+}
+
+#[test]
+fn product_wraps() {
+    // 2^10 wraps to 0 in `u8`, both in the SIMD chunks and in the head and tail
+    let a = UInt8Array::from_slice(&[2; 10]);
+    assert_eq!(Some(0), product_primitive(&a));
+    let a = (0..100).map(|_| Some(2u8)).collect::<UInt8Array>();
+    assert_eq!(Some(0), product_primitive(&a.slice(1, 99)));
+
+    let a = Int8Array::from(&[Some(i8::MAX), None, Some(2)]);
+    assert_eq!(Some(-2), product_primitive(&a));
+}
+
+#[test]
+fn product_float() {
+    let a = Float64Array::from_slice(&[1.5, 2.0, -2.0]);
+    assert_eq!(Some(-6.0), product_primitive(&a));
+}
+
+#[test]
+fn product_dyn() {
+    let a = Int8Array::from_slice(&[2, 3]);
+    assert_eq!(
+        &PrimitiveScalar::<i8>::from(Some(6)) as &dyn Scalar,
+        product(&a).unwrap().as_ref()
+    );
+
+    assert!(!can_product(&DataType::Date32));
+    let a = Int32Array::from_slice(&[1]).to(DataType::Date32);
+    assert!(product(&a).is_err());
+}
@@ -0,0 +1,130 @@
+use arrow2::array::*;
+use arrow2::compute::aggregate::*;
+use arrow2::datatypes::DataType;
+use arrow2::scalar::{PrimitiveScalar, Scalar};
+
+#[test]
+fn count_non_null() {
+    let a = Int32Array::from(&[Some(1), None, Some(3)]);
+    assert_eq!(2, count(&a));
+    assert_eq!(0, count(&NullArray::from_data(DataType::Null, 3)));
+}
+
+#[test]
+fn mean_with_nulls() {
+    let a = Int8Array::from(&[Some(100), None, Some(100), Some(101)]);
+    assert_eq!(Some(301.0 / 3.0), mean_primitive(&a));
+
+    let a = Int8Array::from(&[None, None]);
+    assert_eq!(None, mean_primitive(&a));
+}
+
+#[test]
+fn variance_ddof() {
+    let a = Float64Array::from(&[Some(1.0), None, Some(2.0), Some(3.0), Some(4.0)]);
+    assert_eq!(Some(1.25), variance_primitive(&a, 0));
+    assert_eq!(Some(5.0 / 3.0), variance_primitive(&a, 1));
+    assert_eq!(Some(1.25f64.sqrt()), stddev_primitive(&a, 0));
+    assert_eq!(None, variance_primitive(&a, 4));
+
+    let a = Int32Array::from_slice(&[7]);
+    assert_eq!(Some(0.0), variance_primitive(&a, 0));
+    assert_eq!(None, variance_primitive(&a, 1));
+}
+
+#[test]
+fn quantile_interpolation() {
+    let a = Int32Array::from(&[Some(4), None, Some(1), Some(3), Some(2)]);
+
+    // index 0.4 * 3 = 1.2, between 2 and 3
+    let cases = [
+        (QuantileInterpolation::Linear, 2.2),
+        (QuantileInterpolation::Lower, 2.0),
+        (QuantileInterpolation::Higher, 3.0),
+        (QuantileInterpolation::Midpoint, 2.5),
+        (QuantileInterpolation::Nearest, 2.0),
+    ];
+    for (interpolation, expected) in cases.iter() {
+        let result = quantile_primitive(&a, 0.4, *interpolation)
+            .unwrap()
+            .unwrap();
+        assert!((result - expected).abs() < 1e-9, "{:?}", interpolation);
+    }
+
+    assert_eq!(
+        Some(1.0),
+        quantile_primitive(&a, 0.0, QuantileInterpolation::Linear).unwrap()
+    );
+    assert_eq!(
+        Some(4.0),
+        quantile_primitive(&a, 1.0, QuantileInterpolation::Linear).unwrap()
+    );
+    assert!(quantile_primitive(&a, 1.5, QuantileInterpolation::Linear).is_err());
+}
+
+#[test]
+fn quantile_nearest_ties() {
+    // index 0.5 * 1 = 0.5, resolved to the even index 0
+    let a = Int32Array::from_slice(&[1, 2]);
+    assert_eq!(
+        Some(1.0),
+        quantile_primitive(&a, 0.5, QuantileInterpolation::Nearest).unwrap()
+    );
+    // index 0.5 * 3 = 1.5, resolved to the even index 2
+    let a = Int32Array::from_slice(&[1, 2, 3, 4]);
+    assert_eq!(
+        Some(3.0),
+        quantile_primitive(&a, 0.5, QuantileInterpolation::Nearest).unwrap()
+    );
+}
+
+#[test]
+fn median_values() {
+    let a = Float32Array::from(&[Some(3.0), None, Some(1.0), Some(2.0)]);
+    assert_eq!(Some(2.0), median_primitive(&a));
+
+    let a = UInt64Array::from_slice(&[4, 1, 3, 2]);
+    assert_eq!(Some(2.5), median_primitive(&a));
+
+    let a = UInt64Array::from(&[None, None]);
+    assert_eq!(None, median_primitive(&a));
+}
+
+#[test]
+fn stats_dyn() {
+    let a = Int32Array::from(&[Some(1), None, Some(3)]);
+    let expected = |x: Option<f64>| PrimitiveScalar::<f64>::new(DataType::Float64, x);
+
+    assert_eq!(
+        &expected(Some(2.0)) as &dyn Scalar,
+        mean(&a).unwrap().as_ref()
+    );
+    assert_eq!(
+        &expected(Some(2.0)) as &dyn Scalar,
+        variance(&a, 1).unwrap().as_ref()
+    );
+    assert_eq!(
+        &expected(Some(2.0f64.sqrt())) as &dyn Scalar,
+        stddev(&a, 1).unwrap().as_ref()
+    );
+    assert_eq!(
+        &expected(Some(2.0)) as &dyn Scalar,
+        median(&a).unwrap().as_ref()
+    );
+    assert_eq!(
+        &expected(Some(3.0)) as &dyn Scalar,
+        quantile(&a, 0.75, QuantileInterpolation::Higher)
+            .unwrap()
+            .as_ref()
+    );
+    assert_eq!(
+        &expected(None) as &dyn Scalar,
+        mean(&Int32Array::from(&[None, None])).unwrap().as_ref()
+    );
+
+    let a = a.to(DataType::Date32);
+    assert!(!can_mean(a.data_type()));
+    assert!(mean(&a).is_err());
+    assert!(variance(&a, 0).is_err());
+    assert!(quantile(&a, 0.5, QuantileInterpolation::Linear).is_err());
+}
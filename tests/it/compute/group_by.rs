@@ -0,0 +1,146 @@
+use std::sync::Arc;
+
+use arrow2::array::*;
+use arrow2::compute::group_by::*;
+use arrow2::datatypes::DataType;
+use arrow2::error::Result;
+
+#[test]
+fn groups_of_keys() -> Result<()> {
+    let a = Int32Array::from(&[Some(1), Some(1), None, Some(2), Some(1), None]);
+    let b = Utf8Array::<i32>::from(&[Some("x"), Some("y"), None, Some("x"), Some("x"), None]);
+
+    let result = groups(&[&a, &b])?;
+    assert_eq!(result.ids, vec![0, 1, 2, 3, 0, 2]);
+    assert_eq!(result.first, vec![0, 1, 2, 3]);
+    assert_eq!(result.last(), vec![4, 1, 5, 3]);
+    assert_eq!(result.len(), 4);
+    Ok(())
+}
+
+#[test]
+fn groups_of_dictionary() -> Result<()> {
+    let mut array = MutableDictionaryArray::<i32, MutableUtf8Array<i32>>::new();
+    array.try_extend(vec![Some("a"), Some("b"), None, Some("a"), None])?;
+    let array: DictionaryArray<i32> = array.into();
+
+    let result = groups(&[&array])?;
+    assert_eq!(result.ids, vec![0, 1, 2, 0, 2]);
+    Ok(())
+}
+
+#[test]
+fn groups_of_floats() -> Result<()> {
+    let array = Float64Array::from(&[Some(1.5), Some(f64::NAN), None, Some(1.5), Some(f64::NAN)]);
+
+    let result = groups(&[&array])?;
+    assert_eq!(result.ids, vec![0, 1, 2, 0, 1]);
+    Ok(())
+}
+
+#[test]
+fn aggregations() -> Result<()> {
+    let keys = Utf8Array::<i32>::from_slice(["a", "b", "a", "c", "a", "b"]);
+    let values = Int64Array::from(&[Some(1), Some(2), Some(3), None, None, Some(-4)]);
+    let strings = Utf8Array::<i32>::from(&[Some("z"), None, Some("y"), Some("x"), None, None]);
+
+    let (unique, result) = group_by(
+        &[&keys],
+        &[
+            (&values, Aggregation::Count),
+            (&values, Aggregation::Sum),
+            (&values, Aggregation::Min),
+            (&values, Aggregation::Max),
+            (&values, Aggregation::Mean),
+            (&values, Aggregation::First),
+            (&values, Aggregation::Last),
+            (&strings, Aggregation::Min),
+            (&strings, Aggregation::Max),
+        ],
+    )?;
+
+    assert_eq!(
+        Utf8Array::<i32>::from_slice(["a", "b", "c"]),
+        unique[0].as_ref()
+    );
+    assert_eq!(UInt64Array::from_slice([2, 2, 0]), result[0].as_ref());
+    assert_eq!(
+        Int64Array::from(&[Some(4), Some(-2), None]),
+        result[1].as_ref()
+    );
+    assert_eq!(
+        Int64Array::from(&[Some(1), Some(-4), None]),
+        result[2].as_ref()
+    );
+    assert_eq!(
+        Int64Array::from(&[Some(3), Some(2), None]),
+        result[3].as_ref()
+    );
+    assert_eq!(
+        Float64Array::from(&[Some(2.0), Some(-1.0), None]),
+        result[4].as_ref()
+    );
+    assert_eq!(
+        Int64Array::from(&[Some(1), Some(2), None]),
+        result[5].as_ref()
+    );
+    assert_eq!(
+        Int64Array::from(&[None, Some(-4), None]),
+        result[6].as_ref()
+    );
+    assert_eq!(
+        Utf8Array::<i32>::from(&[Some("y"), None, Some("x")]),
+        result[7].as_ref()
+    );
+    assert_eq!(
+        Utf8Array::<i32>::from(&[Some("z"), None, Some("x")]),
+        result[8].as_ref()
+    );
+    Ok(())
+}
+
+#[test]
+fn sum_wraps() -> Result<()> {
+    let keys = Int32Array::from_slice([1, 1, 2]);
+    let values = Int8Array::from_slice([i8::MAX, 1, i8::MIN]);
+    let decimals = Int128Array::from_slice([i128::MAX, 2, 1]).to(DataType::Decimal(38, 0));
+
+    let (_, result) = group_by(
+        &[&keys],
+        &[(&values, Aggregation::Sum), (&decimals, Aggregation::Sum)],
+    )?;
+    assert_eq!(
+        Int8Array::from_slice([i8::MIN, i8::MIN]),
+        result[0].as_ref()
+    );
+    assert_eq!(
+        Int128Array::from_slice([i128::MIN + 1, 1]).to(DataType::Decimal(38, 0)),
+        result[1].as_ref()
+    );
+    Ok(())
+}
+
+#[test]
+fn sliced() -> Result<()> {
+    let keys = Int32Array::from_slice([1, 2, 1, 2]);
+    let values: Arc<dyn Array> = Arc::new(Int32Array::from(&[Some(1), None, Some(3), Some(4)]));
+    let values = values.slice(1, 3);
+    let keys = keys.slice(1, 3);
+
+    let (unique, result) = group_by(&[&keys], &[(values.as_ref(), Aggregation::Count)])?;
+    assert_eq!(Int32Array::from_slice([2, 1]), unique[0].as_ref());
+    assert_eq!(UInt64Array::from_slice([1, 1]), result[0].as_ref());
+    Ok(())
+}
+
+#[test]
+fn invalid() {
+    let keys = Int32Array::from_slice([1, 2]);
+    let values = Int32Array::from_slice([1, 2, 3]);
+    let strings = Utf8Array::<i32>::from_slice(["a", "b"]);
+
+    assert!(groups(&[]).is_err());
+    assert!(group_by(&[&keys], &[(&values, Aggregation::Sum)]).is_err());
+    assert!(group_by(&[&keys], &[(&strings, Aggregation::Sum)]).is_err());
+    assert!(group_by(&[&keys, &values], &[]).is_err());
+}
@@ -0,0 +1,168 @@
+use std::sync::Arc;
+
+use arrow2::array::*;
+use arrow2::compute::join::*;
+use arrow2::datatypes::{DataType, Field, Schema};
+use arrow2::error::Result;
+use arrow2::record_batch::RecordBatch;
+
+fn keys() -> (Int32Array, Int32Array) {
+    let left = Int32Array::from(&[Some(1), Some(2), None, Some(4), Some(1)]);
+    let right = Int32Array::from(&[Some(1), None, Some(3), Some(2)]);
+    (left, right)
+}
+
+fn test_join(
+    join_type: JoinType,
+    null_equals_null: bool,
+    expected_left: &[Option<u32>],
+    expected_right: &[Option<u32>],
+) -> Result<()> {
+    let (left, right) = keys();
+    let (left, right) = hash_join::<u32>(&[&left], &[&right], join_type, null_equals_null)?;
+    assert_eq!(left, UInt32Array::from(expected_left));
+    assert_eq!(right, UInt32Array::from(expected_right));
+    Ok(())
+}
+
+#[test]
+fn inner() -> Result<()> {
+    test_join(
+        JoinType::Inner,
+        false,
+        &[Some(0), Some(4), Some(1)],
+        &[Some(0), Some(0), Some(3)],
+    )
+}
+
+#[test]
+fn inner_null_equals_null() -> Result<()> {
+    test_join(
+        JoinType::Inner,
+        true,
+        &[Some(0), Some(4), Some(2), Some(1)],
+        &[Some(0), Some(0), Some(1), Some(3)],
+    )
+}
+
+#[test]
+fn left() -> Result<()> {
+    test_join(
+        JoinType::Left,
+        false,
+        &[Some(0), Some(4), Some(1), Some(2), Some(3)],
+        &[Some(0), Some(0), Some(3), None, None],
+    )
+}
+
+#[test]
+fn right() -> Result<()> {
+    test_join(
+        JoinType::Right,
+        false,
+        &[Some(0), Some(4), None, None, Some(1)],
+        &[Some(0), Some(0), Some(1), Some(2), Some(3)],
+    )
+}
+
+#[test]
+fn full() -> Result<()> {
+    test_join(
+        JoinType::Full,
+        false,
+        &[Some(0), Some(4), None, None, Some(1), Some(2), Some(3)],
+        &[Some(0), Some(0), Some(1), Some(2), Some(3), None, None],
+    )
+}
+
+#[test]
+fn semi() -> Result<()> {
+    test_join(
+        JoinType::Semi,
+        false,
+        &[Some(0), Some(1), Some(4)],
+        &[None, None, None],
+    )
+}
+
+#[test]
+fn anti() -> Result<()> {
+    test_join(JoinType::Anti, false, &[Some(2), Some(3)], &[None, None])
+}
+
+#[test]
+fn multiple_keys() -> Result<()> {
+    let left_a = Utf8Array::<i32>::from_slice(["a", "a", "b"]);
+    let left_b = Int64Array::from_slice([1, 2, 1]);
+    let right_a = Utf8Array::<i32>::from_slice(["b", "a", "a"]);
+    let right_b = Int64Array::from_slice([1, 1, 3]);
+
+    let (left, right) = hash_join::<u64>(
+        &[&left_a, &left_b],
+        &[&right_a, &right_b],
+        JoinType::Inner,
+        false,
+    )?;
+    assert_eq!(left, UInt64Array::from_slice([2, 0]));
+    assert_eq!(right, UInt64Array::from_slice([0, 1]));
+    Ok(())
+}
+
+#[test]
+fn invalid() {
+    let a = Int32Array::from_slice([1]);
+    let b = Utf8Array::<i32>::from_slice(["a"]);
+
+    assert!(hash_join::<u32>(&[], &[], JoinType::Inner, false).is_err());
+    assert!(hash_join::<u32>(&[&a], &[&a, &a], JoinType::Inner, false).is_err());
+    assert!(hash_join::<u32>(&[&a], &[&b], JoinType::Inner, false).is_err());
+}
+
+#[test]
+fn join_batches() -> Result<()> {
+    let left = RecordBatch::try_from_iter(vec![
+        (
+            "id",
+            Arc::new(Int32Array::from_slice([1, 2, 3])) as Arc<dyn Array>,
+        ),
+        (
+            "name",
+            Arc::new(Utf8Array::<i32>::from_slice(["a", "b", "c"])) as Arc<dyn Array>,
+        ),
+    ])?;
+    let right = RecordBatch::try_from_iter(vec![
+        (
+            "key",
+            Arc::new(Int32Array::from_slice([3, 1])) as Arc<dyn Array>,
+        ),
+        (
+            "value",
+            Arc::new(Float64Array::from_slice([0.5, 1.5])) as Arc<dyn Array>,
+        ),
+    ])?;
+
+    let result = join(&left, &right, &["id"], &["key"], JoinType::Left, false)?;
+
+    let expected = RecordBatch::try_new(
+        Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, false),
+            Field::new("key", DataType::Int32, true),
+            Field::new("value", DataType::Float64, true),
+        ])),
+        vec![
+            Arc::new(Int32Array::from_slice([3, 1, 2])),
+            Arc::new(Utf8Array::<i32>::from_slice(["c", "a", "b"])),
+            Arc::new(Int32Array::from(&[Some(3), Some(1), None])),
+            Arc::new(Float64Array::from(&[Some(0.5), Some(1.5), None])),
+        ],
+    )?;
+    assert_eq!(result, expected);
+
+    let result = join(&left, &right, &["id"], &["key"], JoinType::Anti, false)?;
+    assert_eq!(result.num_columns(), 2);
+    assert_eq!(result.num_rows(), 1);
+
+    assert!(join(&left, &right, &["id"], &["missing"], JoinType::Inner, false).is_err());
+    Ok(())
+}
@@ -8,8 +8,10 @@ mod comparison;
 mod concat;
 mod contains;
 mod filter;
+mod group_by;
 mod hash;
 mod if_then_else;
+mod join;
 mod length;
 #[cfg(feature = "regex")]
 mod like;
@@ -23,4 +25,5 @@ mod sort;
 mod substring;
 mod take;
 mod temporal;
+mod unique;
 mod window;
@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use arrow2::array::*;
+use arrow2::compute::unique::*;
+use arrow2::datatypes::{DataType, Field};
+use arrow2::error::Result;
+use arrow2::record_batch::RecordBatch;
+
+#[test]
+fn primitive() -> Result<()> {
+    let array = Float64Array::from(&[Some(1.0), Some(f64::NAN), None, Some(1.0), Some(f64::NAN)]);
+
+    let result = unique(&array)?;
+    assert_eq!(result.len(), 3);
+    assert_eq!(count_distinct(&array)?, 3);
+    assert_eq!(
+        is_unique(&array)?,
+        BooleanArray::from_slice([false, false, true, false, false])
+    );
+    assert_eq!(
+        is_duplicated(&array)?,
+        BooleanArray::from_slice([true, true, false, true, true])
+    );
+    Ok(())
+}
+
+#[test]
+fn boolean() -> Result<()> {
+    let array = BooleanArray::from(&[Some(true), Some(true), None, Some(false)]);
+
+    let result = unique(&array)?;
+    assert_eq!(
+        BooleanArray::from(&[Some(true), None, Some(false)]),
+        result.as_ref()
+    );
+    Ok(())
+}
+
+#[test]
+fn binary_value_counts() -> Result<()> {
+    let array = BinaryArray::<i64>::from(&[Some(b"a"), Some(b"b"), Some(b"a"), None]);
+
+    let result = value_counts(&array)?;
+
+    let fields = vec![
+        Field::new("values", DataType::LargeBinary, true),
+        Field::new("counts", DataType::UInt64, false),
+    ];
+    let expected = StructArray::from_data(
+        DataType::Struct(fields),
+        vec![
+            Arc::new(BinaryArray::<i64>::from(&[Some(b"a"), Some(b"b"), None])),
+            Arc::new(UInt64Array::from_slice([2, 1, 1])),
+        ],
+        None,
+    );
+    assert_eq!(result, expected);
+    Ok(())
+}
+
+#[test]
+fn dictionary() -> Result<()> {
+    let mut array = MutableDictionaryArray::<i32, MutableUtf8Array<i32>>::new();
+    array.try_extend(vec![Some("a"), Some("b"), Some("a"), None])?;
+    let array: DictionaryArray<i32> = array.into();
+
+    let result = unique(&array)?;
+    assert_eq!(result.data_type(), array.data_type());
+    assert_eq!(result.len(), 3);
+    assert_eq!(
+        is_duplicated(&array)?,
+        BooleanArray::from_slice([true, false, true, false])
+    );
+    Ok(())
+}
+
+#[test]
+fn batch() -> Result<()> {
+    let batch = RecordBatch::try_from_iter(vec![
+        (
+            "a",
+            Arc::new(Int32Array::from_slice([1, 1, 2, 1])) as Arc<dyn Array>,
+        ),
+        (
+            "b",
+            Arc::new(Utf8Array::<i32>::from_slice(["x", "y", "x", "x"])) as Arc<dyn Array>,
+        ),
+    ])?;
+
+    let result = unique_batch(&batch)?;
+    assert_eq!(result.schema(), batch.schema());
+    assert_eq!(Int32Array::from_slice([1, 1, 2]), result.column(0).as_ref());
+    assert_eq!(
+        Utf8Array::<i32>::from_slice(["x", "y", "x"]),
+        result.column(1).as_ref()
+    );
+
+    assert_eq!(count_distinct_batch(&batch)?, 3);
+    assert_eq!(
+        is_unique_batch(&batch)?,
+        BooleanArray::from_slice([false, true, true, false])
+    );
+    assert_eq!(
+        is_duplicated_batch(&batch)?,
+        BooleanArray::from_slice([true, false, false, true])
+    );
+
+    let result = value_counts_batch(&batch)?;
+    assert_eq!(result.schema().field(2).name(), "counts");
+    assert_eq!(
+        UInt64Array::from_slice([2, 1, 1]),
+        result.column(2).as_ref()
+    );
+    Ok(())
+}
@@ -1,13 +1,14 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use arrow2::types::months_days_ns;
 use avro_rs::types::{Record, Value};
-use avro_rs::{Codec, Writer};
+use avro_rs::{Codec, Decimal, Writer};
 use avro_rs::{Days, Duration, Millis, Months, Schema as AvroSchema};
 
 use arrow2::array::*;
 use arrow2::datatypes::*;
-use arrow2::error::Result;
+use arrow2::error::{ArrowError, Result};
 use arrow2::io::avro::read;
 use arrow2::record_batch::RecordBatch;
 
@@ -82,7 +83,7 @@ fn schema() -> (AvroSchema, Schema) {
     (AvroSchema::parse_str(raw_schema).unwrap(), schema)
 }
 
-fn write(has_codec: bool) -> Result<(Vec<u8>, RecordBatch)> {
+pub(super) fn write(has_codec: bool) -> Result<(Vec<u8>, RecordBatch)> {
     let (avro, schema) = schema();
     // a writer needs a schema and something to write to
     let mut writer: Writer<Vec<u8>>;
@@ -186,6 +187,7 @@ fn read_without_codec() -> Result<()> {
         read::Decompressor::new(read::BlockStreamIterator::new(file, file_marker), codec),
         avro_schema,
         Arc::new(schema),
+        None,
     );
 
     assert_eq!(reader.next().unwrap().unwrap(), expected);
@@ -204,8 +206,493 @@ fn read_with_codec() -> Result<()> {
         read::Decompressor::new(read::BlockStreamIterator::new(file, file_marker), codec),
         avro_schema,
         Arc::new(schema),
+        None,
     );
 
     assert_eq!(reader.next().unwrap().unwrap(), expected);
     Ok(())
 }
+
+fn nested_schema() -> (AvroSchema, Schema) {
+    let raw_schema = r#"
+    {
+        "type": "record",
+        "name": "test",
+        "fields": [
+            {"name": "map", "type": {"type": "map", "values": "long"}},
+            {"name": "union", "type": ["int", "string", "null"]},
+            {"name": "nullable_enum", "type": ["null", {
+                "type": "enum",
+                "name": "suit",
+                "symbols" : ["SPADES", "HEARTS", "DIAMONDS", "CLUBS"]
+            }]},
+            {"name": "decimal", "type": {
+                "type": "bytes",
+                "logicalType": "decimal",
+                "precision": 10,
+                "scale": 2
+            }},
+            {"name": "record", "type": {
+                "type": "record",
+                "name": "inner",
+                "fields": [
+                    {"name": "x", "type": "int"},
+                    {"name": "y", "type": ["string", "null"]}
+                ]
+            }},
+            {"name": "time", "type": {"type": "long", "logicalType": "time-micros"}},
+            {"name": "timestamp", "type": {"type": "long", "logicalType": "timestamp-millis"}}
+        ]
+    }
+"#;
+
+    let entries = DataType::Struct(vec![
+        Field::new("key", DataType::Utf8, false),
+        Field::new("value", DataType::Int64, false),
+    ]);
+    let schema = Schema::new(vec![
+        Field::new(
+            "map",
+            DataType::Map(Box::new(Field::new("entries", entries, false)), false),
+            false,
+        ),
+        Field::new(
+            "union",
+            DataType::Union(
+                vec![
+                    Field::new("", DataType::Int32, true),
+                    Field::new("", DataType::Utf8, true),
+                    Field::new("", DataType::Null, true),
+                ],
+                None,
+                UnionMode::Dense,
+            ),
+            false,
+        ),
+        Field::new(
+            "nullable_enum",
+            DataType::Dictionary(i32::KEY_TYPE, Box::new(DataType::Utf8)),
+            true,
+        ),
+        Field::new("decimal", DataType::Decimal(10, 2), false),
+        Field::new(
+            "record",
+            DataType::Struct(vec![
+                Field::new("inner.x", DataType::Int32, false),
+                Field::new("inner.y", DataType::Utf8, true),
+            ]),
+            false,
+        ),
+        Field::new("time", DataType::Time64(TimeUnit::Microsecond), false),
+        Field::new(
+            "timestamp",
+            DataType::Timestamp(TimeUnit::Millisecond, None),
+            false,
+        ),
+    ]);
+
+    (AvroSchema::parse_str(raw_schema).unwrap(), schema)
+}
+
+fn write_nested() -> Result<(Vec<u8>, RecordBatch)> {
+    let (avro, schema) = nested_schema();
+    let mut writer = Writer::new(&avro, Vec::new());
+
+    let rows = vec![
+        (
+            vec![("a", 1i64)],
+            Value::Int(1),
+            Value::Enum(1, "HEARTS".to_string()),
+            vec![0x04u8, 0xD2],
+            Value::String("a".to_string()),
+        ),
+        (
+            vec![],
+            Value::String("b".to_string()),
+            Value::Null,
+            vec![0xFF],
+            Value::Null,
+        ),
+        (
+            vec![("c", 3)],
+            Value::Null,
+            Value::Enum(0, "SPADES".to_string()),
+            vec![0x00],
+            Value::String("c".to_string()),
+        ),
+    ];
+    for (i, (map, union, enum_, decimal, y)) in rows.into_iter().enumerate() {
+        let mut record = Record::new(writer.schema()).unwrap();
+        let map = map
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), Value::Long(v)))
+            .collect::<HashMap<_, _>>();
+        record.put("map", Value::Map(map));
+        record.put("union", Value::Union(Box::new(union)));
+        record.put("nullable_enum", Value::Union(Box::new(enum_)));
+        record.put("decimal", Value::Decimal(Decimal::from(decimal)));
+        record.put(
+            "record",
+            Value::Record(vec![
+                ("x".to_string(), Value::Int(i as i32 + 1)),
+                ("y".to_string(), Value::Union(Box::new(y))),
+            ]),
+        );
+        record.put("time", Value::TimeMicros(i as i64 + 1));
+        record.put("timestamp", Value::TimestampMillis(i as i64 + 1));
+        writer.append(record)?;
+    }
+
+    let fields = schema.fields();
+    let entries_type = DataType::Struct(vec![
+        Field::new("key", DataType::Utf8, false),
+        Field::new("value", DataType::Int64, false),
+    ]);
+    let entries = StructArray::from_data(
+        entries_type,
+        vec![
+            Arc::new(Utf8Array::<i32>::from_slice(["a", "c"])) as Arc<dyn Array>,
+            Arc::new(Int64Array::from_slice([1, 3])) as Arc<dyn Array>,
+        ],
+        None,
+    );
+    let union = UnionArray::from_data(
+        fields[1].data_type().clone(),
+        vec![0i8, 1, 2].into(),
+        vec![
+            Arc::new(Int32Array::from_slice([1])) as Arc<dyn Array>,
+            Arc::new(Utf8Array::<i32>::from_slice(["b"])) as Arc<dyn Array>,
+            Arc::new(NullArray::from_data(DataType::Null, 1)) as Arc<dyn Array>,
+        ],
+        Some(vec![0, 0, 0].into()),
+    );
+    let record = StructArray::from_data(
+        fields[4].data_type().clone(),
+        vec![
+            Arc::new(Int32Array::from_slice([1, 2, 3])) as Arc<dyn Array>,
+            Arc::new(Utf8Array::<i32>::from([Some("a"), None, Some("c")])) as Arc<dyn Array>,
+        ],
+        None,
+    );
+
+    let columns = vec![
+        Arc::new(MapArray::from_data(
+            fields[0].data_type().clone(),
+            vec![0, 1, 1, 2].into(),
+            Arc::new(entries),
+            None,
+        )) as Arc<dyn Array>,
+        Arc::new(union) as Arc<dyn Array>,
+        Arc::new(DictionaryArray::<i32>::from_data(
+            Int32Array::from([Some(1), None, Some(0)]),
+            Arc::new(Utf8Array::<i32>::from_slice([
+                "SPADES", "HEARTS", "DIAMONDS", "CLUBS",
+            ])),
+        )) as Arc<dyn Array>,
+        Arc::new(Int128Array::from_slice([1234, -1, 0]).to(DataType::Decimal(10, 2)))
+            as Arc<dyn Array>,
+        Arc::new(record) as Arc<dyn Array>,
+        Arc::new(Int64Array::from_slice([1, 2, 3]).to(DataType::Time64(TimeUnit::Microsecond)))
+            as Arc<dyn Array>,
+        Arc::new(
+            Int64Array::from_slice([1, 2, 3]).to(DataType::Timestamp(TimeUnit::Millisecond, None)),
+        ) as Arc<dyn Array>,
+    ];
+
+    let expected = RecordBatch::try_new(Arc::new(schema), columns).unwrap();
+
+    Ok((writer.into_inner().unwrap(), expected))
+}
+
+#[test]
+fn read_truncated_block() {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("a", DataType::Utf8, false),
+        Field::new("b", DataType::Float64, false),
+    ]));
+    let avro_schemas = [AvroSchema::String, AvroSchema::Double];
+
+    // a string of 10 bytes with only 3 of them
+    let block = [20u8, b'a', b'b', b'c'];
+    let result = read::deserialize(&block, 1, schema.clone(), &avro_schemas, &[true, true]);
+    assert!(matches!(result, Err(ArrowError::ExternalFormat(_))));
+
+    // a double with only 4 bytes, skipped or not
+    let block = [2u8, b'a', 0, 0, 0, 0];
+    let result = read::deserialize(&block, 1, schema, &avro_schemas, &[true, true]);
+    assert!(matches!(result, Err(ArrowError::ExternalFormat(_))));
+
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Utf8, false)]));
+    let result = read::deserialize(&block, 1, schema, &avro_schemas, &[true, false]);
+    assert!(matches!(result, Err(ArrowError::ExternalFormat(_))));
+}
+
+#[test]
+fn read_nested_and_logical_types() -> Result<()> {
+    let (data, expected) = write_nested().unwrap();
+
+    let file = &mut &data[..];
+
+    let (avro_schema, schema, codec, file_marker) = read::read_metadata(file)?;
+    assert_eq!(&schema, expected.schema().as_ref());
+
+    let mut reader = read::Reader::new(
+        read::Decompressor::new(read::BlockStreamIterator::new(file, file_marker), codec),
+        avro_schema,
+        Arc::new(schema),
+        None,
+    );
+
+    assert_eq!(reader.next().unwrap().unwrap(), expected);
+    Ok(())
+}
+
+fn project(batch: &RecordBatch, projection: &[usize]) -> RecordBatch {
+    let fields = projection
+        .iter()
+        .map(|i| batch.schema().field(*i).clone())
+        .collect();
+    let columns = projection
+        .iter()
+        .map(|i| batch.column(*i).clone())
+        .collect();
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).unwrap()
+}
+
+fn read_projected(data: &[u8], projection: Vec<usize>) -> Result<RecordBatch> {
+    let file = &mut &data[..];
+
+    let (avro_schema, schema, codec, file_marker) = read::read_metadata(file)?;
+
+    let mut reader = read::Reader::new(
+        read::Decompressor::new(read::BlockStreamIterator::new(file, file_marker), codec),
+        avro_schema,
+        Arc::new(schema),
+        Some(projection),
+    );
+
+    reader.next().unwrap()
+}
+
+#[test]
+fn read_projected_fields() -> Result<()> {
+    let (data, expected) = write(true).unwrap();
+
+    for projection in vec![vec![0], vec![1, 8, 10], vec![3, 6, 7, 9]] {
+        let result = read_projected(&data, projection.clone())?;
+        assert_eq!(result, project(&expected, &projection));
+    }
+    Ok(())
+}
+
+#[test]
+fn read_projected_nested_fields() -> Result<()> {
+    let (data, expected) = write_nested().unwrap();
+
+    for projection in vec![vec![3], vec![2, 6], vec![0, 4]] {
+        let result = read_projected(&data, projection.clone())?;
+        assert_eq!(result, project(&expected, &projection));
+    }
+    Ok(())
+}
+
+fn read_resolved(data: &[u8], reader_schema: &str) -> Result<RecordBatch> {
+    let file = &mut &data[..];
+
+    let (avro_schema, schema, codec, file_marker) = read::read_metadata(file)?;
+
+    let resolution = read::resolve(&schema, reader_schema)?;
+
+    let mut reader = read::Reader::with_resolution(
+        read::Decompressor::new(read::BlockStreamIterator::new(file, file_marker), codec),
+        avro_schema,
+        resolution,
+    );
+
+    reader.next().unwrap()
+}
+
+#[test]
+fn read_resolved_fields() -> Result<()> {
+    let (data, expected) = write(true).unwrap();
+
+    let reader_schema = r#"
+    {
+        "type": "record",
+        "name": "test",
+        "fields": [
+            {"name": "e", "type": "double"},
+            {"name": "c", "type": "double"},
+            {"name": "a", "type": "float"},
+            {"name": "b", "type": "bytes"},
+            {"name": "new", "type": "string", "default": "foo"},
+            {"name": "new_nullable", "type": ["null", "long"], "default": null}
+        ]
+    }
+"#;
+    let result = read_resolved(&data, reader_schema)?;
+
+    let schema = Schema::new(vec![
+        Field::new("e", DataType::Float64, false),
+        Field::new("c", DataType::Float64, false),
+        Field::new("a", DataType::Float32, false),
+        Field::new("b", DataType::Binary, false),
+        Field::new("new", DataType::Utf8, false),
+        Field::new("new_nullable", DataType::Int64, true),
+    ]);
+    let columns = vec![
+        expected.column(5).clone(),
+        Arc::new(Float64Array::from_slice([1.0, 1.0])) as Arc<dyn Array>,
+        Arc::new(Float32Array::from_slice([27.0, 47.0])) as Arc<dyn Array>,
+        Arc::new(BinaryArray::<i32>::from_slice([b"foo", b"bar"])) as Arc<dyn Array>,
+        Arc::new(Utf8Array::<i32>::from_slice(["foo", "foo"])) as Arc<dyn Array>,
+        Arc::new(Int64Array::from([None, None])) as Arc<dyn Array>,
+    ];
+
+    // the metadata of the reader schema is not compared
+    let fields = |schema: &Schema| {
+        schema
+            .fields()
+            .iter()
+            .map(|x| (x.name().clone(), x.data_type().clone(), x.is_nullable()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(fields(result.schema()), fields(&schema));
+    assert_eq!(result.columns(), columns.as_slice());
+    Ok(())
+}
+
+#[test]
+fn read_resolved_aliases() -> Result<()> {
+    let (data, expected) = write_nested().unwrap();
+
+    // field aliases are matched; `x` is promoted from `int` to `long` within the record
+    let reader_schema = r#"
+    {
+        "type": "record",
+        "name": "test",
+        "fields": [
+            {"name": "timestamp", "type": {"type": "long", "logicalType": "timestamp-millis"}},
+            {"name": "renamed", "aliases": ["record"], "type": {
+                "type": "record",
+                "name": "inner",
+                "fields": [
+                    {"name": "x", "type": "long"},
+                    {"name": "y", "type": ["string", "null"]}
+                ]
+            }}
+        ]
+    }
+"#;
+    let result = read_resolved(&data, reader_schema)?;
+
+    assert_eq!(result.schema().field(1).name(), "renamed");
+    let record = expected.column(4);
+    let record = record.as_any().downcast_ref::<StructArray>().unwrap();
+    let record = StructArray::from_data(
+        DataType::Struct(vec![
+            Field::new("inner.x", DataType::Int64, false),
+            Field::new("inner.y", DataType::Utf8, true),
+        ]),
+        vec![
+            Arc::new(Int64Array::from_slice([1, 2, 3])) as Arc<dyn Array>,
+            record.values()[1].clone(),
+        ],
+        None,
+    );
+    assert_eq!(result.column(0), expected.column(6));
+    assert_eq!(result.column(1).as_ref(), &record as &dyn Array);
+
+    // the aliases of the record's type are not aliases of the field
+    let reader_schema = r#"
+    {
+        "type": "record",
+        "name": "test",
+        "fields": [
+            {"name": "renamed", "type": {
+                "type": "record",
+                "name": "renamed",
+                "aliases": ["record"],
+                "fields": [
+                    {"name": "x", "type": "int"},
+                    {"name": "y", "type": ["string", "null"]}
+                ]
+            }}
+        ]
+    }
+"#;
+    assert!(read_resolved(&data, reader_schema).is_err());
+    Ok(())
+}
+
+#[test]
+fn read_resolved_promoted_items() -> Result<()> {
+    let (data, _) = write(true).unwrap();
+
+    let reader_schema = r#"
+    {
+        "type": "record",
+        "name": "test",
+        "fields": [
+            {"name": "h", "type": {"type": "array", "items": ["null", "long"]}}
+        ]
+    }
+"#;
+    let result = read_resolved(&data, reader_schema)?;
+
+    let data = vec![
+        Some(vec![Some(1i64), None, Some(3)]),
+        Some(vec![Some(1i64), None, Some(3)]),
+    ];
+    let mut expected = MutableListArray::<i32, MutablePrimitiveArray<i64>>::new();
+    expected.try_extend(data).unwrap();
+    let expected: ListArray<i32> = expected.into();
+    assert_eq!(result.column(0).as_ref(), &expected as &dyn Array);
+    Ok(())
+}
+
+#[test]
+fn read_resolved_invalid() -> Result<()> {
+    let (data, _) = write(true).unwrap();
+
+    // missing field without default
+    let reader_schema = r#"
+    {
+        "type": "record",
+        "name": "test",
+        "fields": [{"name": "new", "type": "string"}]
+    }
+"#;
+    assert!(read_resolved(&data, reader_schema).is_err());
+
+    // a string can't be promoted to a long
+    let reader_schema = r#"
+    {
+        "type": "record",
+        "name": "test",
+        "fields": [{"name": "b", "type": "long"}]
+    }
+"#;
+    assert!(read_resolved(&data, reader_schema).is_err());
+
+    // a nullable field can't be read as a non-nullable field
+    let reader_schema = r#"
+    {
+        "type": "record",
+        "name": "test",
+        "fields": [{"name": "g", "type": "string"}]
+    }
+"#;
+    assert!(read_resolved(&data, reader_schema).is_err());
+
+    // nor can nullable items
+    let reader_schema = r#"
+    {
+        "type": "record",
+        "name": "test",
+        "fields": [{"name": "h", "type": {"type": "array", "items": "long"}}]
+    }
+"#;
+    assert!(read_resolved(&data, reader_schema).is_err());
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use futures::pin_mut;
+use futures::StreamExt;
+
+use arrow2::error::Result;
+use arrow2::io::avro::read;
+use arrow2::io::avro::read_async::*;
+
+use super::read::write;
+
+async fn test(codec: bool) -> Result<()> {
+    let (data, expected) = write(codec).unwrap();
+
+    let mut reader = &data[..];
+
+    let (avro_schemas, schema, codec, marker) = read_metadata(&mut reader).await?;
+    let schema = Arc::new(schema);
+    let projection = vec![true; schema.fields().len()];
+
+    let blocks = block_stream(&mut reader, marker, codec);
+
+    pin_mut!(blocks);
+    while let Some((block, rows)) = blocks.next().await.transpose()? {
+        assert!(rows > 0);
+        let batch = read::deserialize(&block, rows, schema.clone(), &avro_schemas, &projection)?;
+        assert_eq!(batch, expected);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn read_without_codec() -> Result<()> {
+    test(false).await
+}
+
+#[tokio::test]
+async fn read_deflate() -> Result<()> {
+    test(true).await
+}
@@ -0,0 +1,369 @@
+use std::sync::Arc;
+
+use arrow2::array::*;
+use arrow2::datatypes::*;
+use arrow2::error::Result;
+use arrow2::io::avro::{read, write, Compression};
+use arrow2::record_batch::RecordBatch;
+use arrow2::types::months_days_ns;
+
+fn schema() -> Schema {
+    Schema::new(vec![
+        Field::new("a", DataType::Int64, false),
+        Field::new("b", DataType::Utf8, false),
+        Field::new("c", DataType::Int32, false),
+        Field::new("date", DataType::Date32, false),
+        Field::new("d", DataType::Binary, false),
+        Field::new("e", DataType::Float64, false),
+        Field::new("f", DataType::Boolean, false),
+        Field::new("g", DataType::Utf8, true),
+        Field::new(
+            "h",
+            DataType::List(Box::new(Field::new("item", DataType::Int32, true))),
+            true,
+        ),
+        Field::new("i", DataType::Float32, true),
+        Field::new("j", DataType::FixedSizeBinary(2), false),
+        Field::new(
+            "duration",
+            DataType::Interval(IntervalUnit::MonthDayNano),
+            false,
+        ),
+        Field::new("time", DataType::Time32(TimeUnit::Millisecond), false),
+        Field::new(
+            "timestamp",
+            DataType::Timestamp(TimeUnit::Microsecond, None),
+            true,
+        ),
+        Field::new("decimal", DataType::Decimal(18, 2), true),
+        Field::new("struct", struct_type(), true),
+        Field::new("map", map_type(), false),
+    ])
+}
+
+fn struct_type() -> DataType {
+    DataType::Struct(vec![
+        Field::new("x", DataType::Int32, false),
+        Field::new("y", DataType::Utf8, true),
+    ])
+}
+
+fn entries_type() -> DataType {
+    DataType::Struct(vec![
+        Field::new("key", DataType::Utf8, false),
+        Field::new("value", DataType::Float64, true),
+    ])
+}
+
+fn map_type() -> DataType {
+    DataType::Map(
+        Box::new(Field::new("entries", entries_type(), false)),
+        false,
+    )
+}
+
+fn data() -> RecordBatch {
+    let data = vec![Some(vec![Some(1i32), None, Some(3)]), None, Some(vec![])];
+
+    let mut list = MutableListArray::<i32, MutablePrimitiveArray<i32>>::new();
+    list.try_extend(data).unwrap();
+
+    let columns = vec![
+        Arc::new(Int64Array::from_slice([27, -47, 0])) as Arc<dyn Array>,
+        Arc::new(Utf8Array::<i32>::from_slice(["foo", "bar", ""])) as Arc<dyn Array>,
+        Arc::new(Int32Array::from_slice([1, i32::MIN, i32::MAX])) as Arc<dyn Array>,
+        Arc::new(Int32Array::from_slice([1, 2, 3]).to(DataType::Date32)) as Arc<dyn Array>,
+        Arc::new(BinaryArray::<i32>::from_slice(["foo", "bar", ""])) as Arc<dyn Array>,
+        Arc::new(PrimitiveArray::<f64>::from_slice([1.0, 2.0, -0.5])) as Arc<dyn Array>,
+        Arc::new(BooleanArray::from_slice([true, false, true])) as Arc<dyn Array>,
+        Arc::new(Utf8Array::<i32>::from([Some("foo"), None, Some("baz")])) as Arc<dyn Array>,
+        list.into_arc(),
+        Arc::new(PrimitiveArray::<f32>::from([Some(1.5), None, Some(-1.0)])) as Arc<dyn Array>,
+        Arc::new(FixedSizeBinaryArray::from_data(
+            DataType::FixedSizeBinary(2),
+            vec![1u8, 2, 3, 4, 5, 6].into(),
+            None,
+        )) as Arc<dyn Array>,
+        Arc::new(MonthsDaysNsArray::from_slice([
+            months_days_ns::new(1, 1, 1_000_000),
+            months_days_ns::new(-1, 2, 0),
+            months_days_ns::new(0, 0, 3_000_000),
+        ])) as Arc<dyn Array>,
+        Arc::new(
+            Int32Array::from_slice([0, 1_000, 86_399_999])
+                .to(DataType::Time32(TimeUnit::Millisecond)),
+        ) as Arc<dyn Array>,
+        Arc::new(
+            Int64Array::from([Some(1_600_000_000_000_000), None, Some(-1)])
+                .to(DataType::Timestamp(TimeUnit::Microsecond, None)),
+        ) as Arc<dyn Array>,
+        Arc::new(
+            Int128Array::from([Some(123_456), None, Some(-1_000_000_000_000_000)])
+                .to(DataType::Decimal(18, 2)),
+        ) as Arc<dyn Array>,
+        Arc::new(StructArray::from_data(
+            struct_type(),
+            vec![
+                Arc::new(Int32Array::from_slice([1, 2, 3])) as Arc<dyn Array>,
+                Arc::new(Utf8Array::<i32>::from([Some("a"), None, None])) as Arc<dyn Array>,
+            ],
+            Some([true, true, false].into()),
+        )) as Arc<dyn Array>,
+        Arc::new(MapArray::from_data(
+            map_type(),
+            vec![0, 2, 2, 3].into(),
+            Arc::new(StructArray::from_data(
+                entries_type(),
+                vec![
+                    Arc::new(Utf8Array::<i32>::from_slice(["a", "b", "c"])) as Arc<dyn Array>,
+                    Arc::new(Float64Array::from([Some(1.0), None, Some(3.0)])) as Arc<dyn Array>,
+                ],
+                None,
+            )),
+            None,
+        )) as Arc<dyn Array>,
+    ];
+
+    RecordBatch::try_new(Arc::new(schema()), columns).unwrap()
+}
+
+fn write_avro(batches: &[RecordBatch], compression: Option<Compression>) -> Result<Vec<u8>> {
+    let mut writer = write::Writer::try_new(vec![], batches[0].schema(), compression)?;
+    for batch in batches {
+        writer.write(batch)?;
+    }
+    Ok(writer.into_inner())
+}
+
+fn read_avro(mut data: &[u8]) -> Result<(Schema, Vec<RecordBatch>)> {
+    let file = &mut data;
+
+    let (avro_schema, schema, codec, file_marker) = read::read_metadata(file)?;
+
+    let reader = read::Reader::new(
+        read::Decompressor::new(read::BlockStreamIterator::new(file, file_marker), codec),
+        avro_schema,
+        Arc::new(schema.clone()),
+        None,
+    );
+
+    Ok((schema, reader.collect::<Result<Vec<_>>>()?))
+}
+
+/// Returns `batch` as read from Avro, whose reader names the fields of a record `"{record}.{field}"`.
+/// The records written from a field are named after it.
+fn as_read(batch: &RecordBatch) -> RecordBatch {
+    let (fields, columns) = batch
+        .schema()
+        .fields()
+        .iter()
+        .zip(batch.columns())
+        .map(|(field, column)| match field.data_type() {
+            DataType::Struct(children) => {
+                let data_type = DataType::Struct(
+                    children
+                        .iter()
+                        .map(|child| {
+                            let name = format!("{}.{}", field.name(), child.name());
+                            Field::new(&name, child.data_type().clone(), child.is_nullable())
+                        })
+                        .collect(),
+                );
+                let array = column.as_any().downcast_ref::<StructArray>().unwrap();
+                let array = StructArray::from_data(
+                    data_type.clone(),
+                    array.values().to_vec(),
+                    array.validity().cloned(),
+                );
+                (
+                    Field::new(field.name(), data_type, field.is_nullable()),
+                    Arc::new(array) as Arc<dyn Array>,
+                )
+            }
+            _ => (field.clone(), column.clone()),
+        })
+        .unzip();
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).unwrap()
+}
+
+fn round_trip(compression: Option<Compression>) -> Result<()> {
+    let expected = data();
+    let sliced = expected
+        .columns()
+        .iter()
+        .map(|array| Arc::from(array.slice(1, 2)))
+        .collect();
+    let sliced = RecordBatch::try_new(expected.schema().clone(), sliced)?;
+    let batches = vec![expected.clone(), sliced];
+
+    let data = write_avro(&batches, compression)?;
+    let (schema, result) = read_avro(&data)?;
+
+    let batches = batches.iter().map(as_read).collect::<Vec<_>>();
+    assert_eq!(&schema, batches[0].schema().as_ref());
+    assert_eq!(result, batches);
+    Ok(())
+}
+
+#[test]
+fn round_trip_without_codec() -> Result<()> {
+    round_trip(None)
+}
+
+#[test]
+fn round_trip_with_deflate() -> Result<()> {
+    round_trip(Some(Compression::Deflate))
+}
+
+#[cfg(feature = "io_avro_compression")]
+#[test]
+fn round_trip_with_snappy() -> Result<()> {
+    round_trip(Some(Compression::Snappy))
+}
+
+#[cfg(feature = "io_avro_compression")]
+#[test]
+fn round_trip_with_zstd() -> Result<()> {
+    round_trip(Some(Compression::Zstandard))
+}
+
+#[cfg(feature = "io_avro_compression")]
+#[test]
+fn round_trip_with_bzip2() -> Result<()> {
+    round_trip(Some(Compression::Bzip2))
+}
+
+#[cfg(feature = "io_avro_compression")]
+#[test]
+fn round_trip_with_xz() -> Result<()> {
+    round_trip(Some(Compression::Xz))
+}
+
+#[cfg(feature = "io_avro_compression")]
+#[test]
+fn snappy_invalid_checksum() -> Result<()> {
+    let mut data = write_avro(&[data()], Some(Compression::Snappy))?;
+    // the checksum is right before the block's marker
+    let checksum = data.len() - 16 - 1;
+    data[checksum] = data[checksum].wrapping_add(1);
+
+    assert!(read_avro(&data).is_err());
+    Ok(())
+}
+
+#[test]
+fn unknown_codec() -> Result<()> {
+    let mut data = write_avro(&[data()], Some(Compression::Deflate))?;
+    // replace the codec in the header by one with the same length
+    let position = data.windows(7).position(|x| x == b"deflate").unwrap();
+    data[position..position + 7].copy_from_slice(b"unknown");
+
+    assert!(read_avro(&data).is_err());
+    Ok(())
+}
+
+#[test]
+fn invalid_block_marker() -> Result<()> {
+    let mut data = write_avro(&[data()], None)?;
+    let last = data.len() - 1;
+    data[last] = data[last].wrapping_add(1);
+
+    assert!(read_avro(&data).is_err());
+    Ok(())
+}
+
+#[test]
+fn avro_schema() -> Result<()> {
+    let schema = Schema::new(vec![
+        Field::new("a", DataType::Int64, false),
+        Field::new("b", DataType::Utf8, true),
+        Field::new(
+            "c",
+            DataType::Struct(vec![Field::new("d", DataType::Boolean, false)]),
+            false,
+        ),
+        Field::new("e", DataType::Decimal(10, 2), true),
+    ]);
+
+    let result = write::to_avro_schema(&schema)?;
+
+    let expected = avro_rs::Schema::parse_str(
+        r#"{
+        "type": "record",
+        "name": "record",
+        "fields": [
+            {"name": "a", "type": "long"},
+            {"name": "b", "type": ["null", "string"]},
+            {"name": "c", "type": {
+                "type": "record",
+                "name": "c",
+                "fields": [{"name": "d", "type": "boolean"}]
+            }},
+            {"name": "e", "type": ["null", {
+                "type": "bytes",
+                "logicalType": "decimal",
+                "precision": 10,
+                "scale": 2
+            }]}
+        ]
+    }"#,
+    )
+    .unwrap();
+    assert_eq!(result, expected);
+    Ok(())
+}
+
+#[test]
+fn non_nullable_with_nulls() {
+    let schema = Schema::new(vec![Field::new("a", DataType::Int32, false)]);
+    let batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![Arc::new(Int32Array::from([Some(1), None]))],
+    )
+    .unwrap();
+
+    let mut writer = write::Writer::try_new(vec![], batch.schema(), None).unwrap();
+    assert!(writer.write(&batch).is_err());
+}
+
+fn read_avro_parallel(mut data: &[u8], max_tasks: usize) -> Result<Vec<RecordBatch>> {
+    let file = &mut data;
+
+    let (avro_schema, schema, codec, file_marker) = read::read_metadata(file)?;
+
+    let reader = read::ParallelReader::new(
+        read::BlockStreamIterator::new(file, file_marker),
+        codec,
+        avro_schema,
+        Arc::new(schema),
+        None,
+        |task| {
+            std::thread::spawn(task);
+        },
+        max_tasks,
+    );
+
+    reader.collect()
+}
+
+#[test]
+fn parallel_round_trip() -> Result<()> {
+    let expected = data();
+    let batches = (0..expected.num_rows())
+        .map(|i| {
+            let columns = expected
+                .columns()
+                .iter()
+                .map(|array| Arc::from(array.slice(i, 1)))
+                .collect();
+            RecordBatch::try_new(expected.schema().clone(), columns)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let data = write_avro(&batches, Some(Compression::Deflate))?;
+
+    let batches = batches.iter().map(as_read).collect::<Vec<_>>();
+    for max_tasks in [1, 2, 5] {
+        assert_eq!(read_avro_parallel(&data, max_tasks)?, batches);
+    }
+    Ok(())
+}
@@ -7,6 +7,8 @@ use arrow2::array::*;
 use arrow2::datatypes::*;
 use arrow2::error::Result;
 use arrow2::io::csv::read::*;
+use arrow2::record_batch::{RecordBatch, RecordBatchReader};
+use arrow2::types::{days_ms, months_days_ns};
 
 #[test]
 fn read() -> Result<()> {
@@ -27,17 +29,19 @@ fn read() -> Result<()> {
 "Aberdeen, Aberdeen City, UK",57.149651,-2.099075"#;
     let mut reader = ReaderBuilder::new().from_reader(Cursor::new(data));
 
-    let schema = Arc::new(infer_schema(&mut reader, None, true, &infer)?);
+    let options = InferSchemaOptions {
+        has_header: true,
+        ..Default::default()
+    };
+    let (schema, rows) = infer_schema(&mut reader, &options, &infer)?;
+    let schema = Arc::new(schema);
 
-    let mut rows = vec![ByteRecord::default(); 100];
-    let rows_read = read_rows(&mut reader, 0, &mut rows)?;
-
-    let batch = deserialize_batch(
-        &rows[..rows_read],
+    let (batch, _) = deserialize_batch(
+        &rows,
         schema.fields(),
         None,
         0,
-        deserialize_column,
+        &DeserializeOptions::default(),
     )?;
 
     let batch_schema = batch.schema();
@@ -69,7 +73,11 @@ fn infer_basics() -> Result<()> {
     let file = Cursor::new("1,2,3\na,b,c\na,,c");
     let mut reader = ReaderBuilder::new().from_reader(file);
 
-    let schema = infer_schema(&mut reader, Some(10), false, &infer)?;
+    let options = InferSchemaOptions {
+        max_rows: Some(10),
+        ..Default::default()
+    };
+    let (schema, _) = infer_schema(&mut reader, &options, &infer)?;
 
     assert_eq!(
         schema,
@@ -87,7 +95,11 @@ fn infer_ints() -> Result<()> {
     let file = Cursor::new("1,2,3\n1,a,5\n2,,4");
     let mut reader = ReaderBuilder::new().from_reader(file);
 
-    let schema = infer_schema(&mut reader, Some(10), false, &infer)?;
+    let options = InferSchemaOptions {
+        max_rows: Some(10),
+        ..Default::default()
+    };
+    let (schema, _) = infer_schema(&mut reader, &options, &infer)?;
 
     assert_eq!(
         schema,
@@ -100,13 +112,87 @@ fn infer_ints() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn infer_empty_values() -> Result<()> {
+    let data = "1,,a\n,,\n3,,b";
+    let mut reader = ReaderBuilder::new()
+        .has_headers(false)
+        .from_reader(Cursor::new(data));
+
+    let (schema, _) = infer_schema(&mut reader, &InferSchemaOptions::default(), &infer)?;
+
+    // empty values are strings
+    assert_eq!(
+        schema,
+        Schema::new(vec![
+            Field::new("column_1", DataType::Utf8, true),
+            Field::new("column_2", DataType::Utf8, true),
+            Field::new("column_3", DataType::Utf8, true),
+        ])
+    );
+
+    // unless they are null values
+    let options = DeserializeOptions {
+        null_values: vec!["".to_string()],
+        ..Default::default()
+    };
+    let mut reader = ReaderBuilder::new()
+        .has_headers(false)
+        .from_reader(Cursor::new(data));
+    let (schema, _) = infer_schema(&mut reader, &InferSchemaOptions::default(), &|bytes| {
+        infer_with_options(bytes, &options)
+    })?;
+    assert_eq!(
+        schema,
+        Schema::new(vec![
+            Field::new("column_1", DataType::Int64, true),
+            Field::new("column_2", DataType::Utf8, true),
+            Field::new("column_3", DataType::Utf8, true),
+        ])
+    );
+    Ok(())
+}
+
+#[test]
+fn null_values_are_case_sensitive() {
+    let options = DeserializeOptions {
+        null_values: vec!["NA".to_string()],
+        ..Default::default()
+    };
+    assert_eq!(infer_with_options(b"NA", &options), DataType::Null);
+    assert_eq!(infer_with_options(b"na", &options), DataType::Utf8);
+}
+
 fn test_deserialize(input: &str, data_type: DataType) -> Result<Arc<dyn Array>> {
     let reader = std::io::Cursor::new(input);
     let mut reader = ReaderBuilder::new().has_headers(false).from_reader(reader);
 
     let mut rows = vec![ByteRecord::default(); 10];
     let rows_read = read_rows(&mut reader, 0, &mut rows)?;
-    deserialize_column(&rows[..rows_read], 0, data_type, 0)
+    deserialize_column(
+        &rows[..rows_read],
+        0,
+        data_type,
+        0,
+        &DeserializeOptions::default(),
+    )
+    .map(|(array, _)| array)
+}
+
+fn test_deserialize_permissive(
+    input: &str,
+    data_type: DataType,
+) -> Result<(Arc<dyn Array>, Vec<RejectedCell>)> {
+    let reader = std::io::Cursor::new(input);
+    let mut reader = ReaderBuilder::new().has_headers(false).from_reader(reader);
+
+    let mut rows = vec![ByteRecord::default(); 10];
+    let rows_read = read_rows(&mut reader, 0, &mut rows)?;
+    let options = DeserializeOptions {
+        mode: ParseMode::Permissive,
+        ..Default::default()
+    };
+    deserialize_column(&rows[..rows_read], 0, data_type, 0, &options)
 }
 
 #[test]
@@ -229,6 +315,253 @@ fn deserialize_timestamp() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn strict() -> Result<()> {
+    let data = "a,b\n1,x\n2,2021-01-01\nc,2021-01-02";
+    let mut reader = ReaderBuilder::new().from_reader(Cursor::new(data));
+    let mut rows = vec![ByteRecord::default(); 10];
+    let rows_read = read_rows(&mut reader, 0, &mut rows)?;
+    let rows = &rows[..rows_read];
+
+    let fields = vec![
+        Field::new("a", DataType::Int32, true),
+        Field::new("b", DataType::Date32, true),
+    ];
+
+    let options = DeserializeOptions {
+        mode: ParseMode::Strict,
+        ..Default::default()
+    };
+    let error = deserialize_column(rows, 0, DataType::Int32, 0, &options).unwrap_err();
+    let message = error.to_string();
+    assert!(message.contains("line 4"));
+    assert!(message.contains("\"c\""));
+
+    let error = deserialize_batch(rows, &fields, Some(&[1]), 0, &options)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("line 2"));
+    assert!(error.contains("\"x\""));
+    assert!(error.contains("column \"b\""));
+
+    // lenient mode is the default
+    let (result, _) =
+        deserialize_column(rows, 0, DataType::Int32, 0, &DeserializeOptions::default())?;
+    assert_eq!(Int32Array::from([Some(1), Some(2), None]), result.as_ref());
+    Ok(())
+}
+
+#[test]
+fn permissive() -> Result<()> {
+    let data = "a,b\n1,x\n2,2021-01-01\nc,2021-01-02";
+    let mut reader = ReaderBuilder::new().from_reader(Cursor::new(data));
+    let mut rows = vec![ByteRecord::default(); 10];
+    let rows_read = read_rows(&mut reader, 0, &mut rows)?;
+
+    let fields = vec![
+        Field::new("a", DataType::Int32, true),
+        Field::new("b", DataType::Date32, true),
+    ];
+
+    let options = DeserializeOptions {
+        mode: ParseMode::Permissive,
+        ..Default::default()
+    };
+    let (batch, rejected) = deserialize_batch(&rows[..rows_read], &fields, None, 0, &options)?;
+    assert_eq!(batch.num_rows(), 3);
+    assert_eq!(batch.column(0).null_count(), 1);
+    assert_eq!(batch.column(1).null_count(), 1);
+    assert_eq!(
+        rejected,
+        vec![
+            RejectedCell {
+                line_number: 4,
+                column: 0,
+                bytes: b"c".to_vec()
+            },
+            RejectedCell {
+                line_number: 2,
+                column: 1,
+                bytes: b"x".to_vec()
+            },
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn options() -> Result<()> {
+    let data = "a;b;c;d\n1,5;Y;01/02/2021 10:30;x\nNA;N;\\N;NA\n2;y;02/02/2021 11:00;y";
+    let mut reader = ReaderBuilder::new()
+        .delimiter(b';')
+        .from_reader(Cursor::new(data));
+
+    let options = DeserializeOptions {
+        null_values: vec!["NA".to_string(), "\\N".to_string()],
+        true_values: vec!["Y".to_string()],
+        false_values: vec!["N".to_string()],
+        datetime_format: Some("%d/%m/%Y %H:%M".to_string()),
+        decimal_separator: b',',
+        ..Default::default()
+    };
+
+    let infer_options = InferSchemaOptions {
+        has_header: true,
+        ..Default::default()
+    };
+    let (schema, rows) = infer_schema(&mut reader, &infer_options, &|bytes| {
+        infer_with_options(bytes, &options)
+    })?;
+    assert_eq!(
+        schema,
+        Schema::new(vec![
+            Field::new("a", DataType::Float64, true),
+            Field::new("b", DataType::Boolean, true),
+            Field::new("c", DataType::Timestamp(TimeUnit::Millisecond, None), true),
+            Field::new("d", DataType::Utf8, true),
+        ])
+    );
+
+    let (batch, rejected) = deserialize_batch(&rows, schema.fields(), None, 0, &options)?;
+    assert!(rejected.is_empty());
+
+    assert_eq!(
+        batch.column(0).as_ref(),
+        &Float64Array::from([Some(1.5), None, Some(2.0)]) as &dyn Array
+    );
+    assert_eq!(
+        batch.column(1).as_ref(),
+        &BooleanArray::from([Some(true), Some(false), Some(true)]) as &dyn Array
+    );
+    assert_eq!(
+        batch.column(2).as_ref(),
+        &Int64Array::from([Some(1612175400000), None, Some(1612263600000)])
+            .to(DataType::Timestamp(TimeUnit::Millisecond, None)) as &dyn Array
+    );
+    assert_eq!(
+        batch.column(3).as_ref(),
+        &Utf8Array::<i32>::from([Some("x"), None, Some("y")]) as &dyn Array
+    );
+    Ok(())
+}
+
+#[test]
+fn column_formats() -> Result<()> {
+    let input = "01/02/2021\n";
+    let reader = std::io::Cursor::new(input);
+    let mut reader = ReaderBuilder::new().has_headers(false).from_reader(reader);
+    let mut rows = vec![ByteRecord::default(); 10];
+    let rows_read = read_rows(&mut reader, 0, &mut rows)?;
+
+    let mut options = DeserializeOptions::default();
+    options.column_formats.insert(0, "%m/%d/%Y".to_string());
+    let (result, _) = deserialize_column(&rows[..rows_read], 0, DataType::Date32, 0, &options)?;
+    assert_eq!(
+        result.as_ref(),
+        &Int32Array::from([Some(18629)]).to(DataType::Date32) as &dyn Array
+    );
+    Ok(())
+}
+
+#[test]
+fn dictionary() -> Result<()> {
+    let input = "a\nb\na";
+    let data_type = DataType::Dictionary(IntegerType::UInt32, Box::new(DataType::Utf8));
+
+    let result = test_deserialize(input, data_type)?;
+
+    let mut expected = MutableDictionaryArray::<u32, MutableUtf8Array<i32>>::new();
+    expected.try_extend(vec![Some("a"), Some("b"), Some("a")])?;
+    let expected: DictionaryArray<u32> = expected.into();
+    assert_eq!(expected, result.as_ref());
+    Ok(())
+}
+
+#[test]
+fn fixed_size_binary() -> Result<()> {
+    let input = "ab\nabc";
+    let (result, rejected) = test_deserialize_permissive(input, DataType::FixedSizeBinary(2))?;
+
+    let expected = FixedSizeBinaryArray::from_iter(vec![Some(b"ab"), None], 2);
+    assert_eq!(expected, result.as_ref());
+    assert_eq!(rejected.len(), 1);
+    assert_eq!(rejected[0].bytes, b"abc");
+    Ok(())
+}
+
+#[test]
+fn duration() -> Result<()> {
+    let input = "PT1.5S\n-P1DT1H\n10\nP1M";
+    let data_type = DataType::Duration(TimeUnit::Millisecond);
+    let (result, rejected) = test_deserialize_permissive(input, data_type.clone())?;
+
+    let expected = Int64Array::from([Some(1500), Some(-90_000_000), Some(10), None]).to(data_type);
+    assert_eq!(expected, result.as_ref());
+    // months have no fixed duration
+    assert_eq!(rejected.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn interval() -> Result<()> {
+    let input = "P1Y2M3DT4H5M6.000000007S\nP2W";
+    let data_type = DataType::Interval(IntervalUnit::MonthDayNano);
+    let result = test_deserialize(input, data_type)?;
+
+    let expected = PrimitiveArray::<months_days_ns>::from([
+        Some(months_days_ns::new(14, 3, 14_706_000_000_007)),
+        Some(months_days_ns::new(0, 14, 0)),
+    ]);
+    assert_eq!(expected, result.as_ref());
+
+    let result = test_deserialize("P1DT1S\nP1M", DataType::Interval(IntervalUnit::DayTime))?;
+    let expected = PrimitiveArray::<days_ms>::from([Some(days_ms::new(1, 1000)), None]);
+    assert_eq!(expected, result.as_ref());
+
+    let data_type = DataType::Interval(IntervalUnit::YearMonth);
+    let result = test_deserialize("P1Y1M\nP1D", data_type.clone())?;
+    let expected = Int32Array::from([Some(13), None]).to(data_type);
+    assert_eq!(expected, result.as_ref());
+    Ok(())
+}
+
+#[test]
+fn infer_dictionaries() -> Result<()> {
+    let options = InferSchemaOptions {
+        has_header: true,
+        max_cardinality: Some(2),
+        ..Default::default()
+    };
+    let file = Cursor::new("a,b\nx,1\ny,2\nx,3\nx,4");
+    let mut reader = ReaderBuilder::new().from_reader(file);
+
+    let (schema, _) = infer_schema(&mut reader, &options, &infer)?;
+
+    assert_eq!(
+        schema,
+        Schema::new(vec![
+            Field::new(
+                "a",
+                DataType::Dictionary(IntegerType::UInt32, Box::new(DataType::Utf8)),
+                true
+            ),
+            Field::new("b", DataType::Int64, true),
+        ])
+    );
+
+    // distinct values are not dictionary-encoded
+    let file = Cursor::new("a\nx\ny");
+    let mut reader = ReaderBuilder::new().from_reader(file);
+
+    let (schema, _) = infer_schema(&mut reader, &options, &infer)?;
+
+    assert_eq!(
+        schema,
+        Schema::new(vec![Field::new("a", DataType::Utf8, true)])
+    );
+    Ok(())
+}
+
 proptest! {
     #[test]
     #[cfg_attr(miri, ignore)] // miri and proptest do not work well :(
@@ -252,3 +585,211 @@ proptest! {
         assert_eq!(infer(v.as_bytes()), DataType::Timestamp(TimeUnit::Millisecond, Some("-02:00".to_string())));
     }
 }
+
+#[test]
+fn infer_buffered() -> Result<()> {
+    // a `&[u8]` implements `Read` but not `Seek`
+    let data = "a,b\n1,x\n2,y\n3,z\n".as_bytes();
+    let mut reader = ReaderBuilder::new().from_reader(data);
+
+    let options = InferSchemaOptions {
+        max_rows: Some(2),
+        has_header: true,
+        ..Default::default()
+    };
+    let (schema, mut rows) = infer_schema(&mut reader, &options, &infer)?;
+    assert_eq!(
+        schema,
+        Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Utf8, true),
+        ])
+    );
+    assert_eq!(rows.len(), 2);
+
+    let mut remaining = vec![ByteRecord::default(); 10];
+    let rows_read = read_rows(&mut reader, 0, &mut remaining)?;
+    rows.extend(remaining.into_iter().take(rows_read));
+
+    let (batch, _) = deserialize_batch(
+        &rows,
+        schema.fields(),
+        None,
+        0,
+        &DeserializeOptions::default(),
+    )?;
+    assert_eq!(
+        batch.column(0).as_ref(),
+        &Int64Array::from_slice(&[1, 2, 3]) as &dyn Array
+    );
+    Ok(())
+}
+
+#[test]
+fn batch_reader() -> Result<()> {
+    let data = "a,b,c\n1,x,1.5\n2,y,2.5\n3,z,3.5\n";
+    let reader = BatchReaderBuilder::new()
+        .with_batch_size(2)
+        .with_projection(vec![2, 0])
+        .build(data.as_bytes())?;
+
+    assert_eq!(
+        reader.schema(),
+        &Schema::new(vec![
+            Field::new("c", DataType::Float64, true),
+            Field::new("a", DataType::Int64, true),
+        ])
+    );
+
+    let batches = reader.collect::<Result<Vec<_>>>()?;
+    assert_eq!(batches.len(), 2);
+    assert_eq!(batches[0].num_rows(), 2);
+    assert_eq!(batches[1].num_rows(), 1);
+    assert_eq!(
+        batches[1].column(1).as_ref(),
+        &Int64Array::from_slice(&[3]) as &dyn Array
+    );
+    Ok(())
+}
+
+#[test]
+fn parallel_batch_reader() -> Result<()> {
+    let mut data = "id,text\n".to_string();
+    for i in 0..100 {
+        // quoted fields with line breaks must not be split
+        data.push_str(&format!("{},\"line\n{}\"\n", i, i));
+    }
+    let builder = BatchReaderBuilder::new()
+        .with_batch_size(7)
+        .with_chunk_size(50);
+
+    let expected = builder
+        .build(data.as_bytes())?
+        .collect::<Result<Vec<_>>>()?;
+    let reader = builder.build_parallel(data.as_bytes(), 3)?;
+    assert_eq!(reader.schema(), expected[0].schema().as_ref());
+    let result = reader.collect::<Result<Vec<_>>>()?;
+
+    let ids = |batches: &[RecordBatch]| {
+        batches
+            .iter()
+            .flat_map(|batch| {
+                let array = batch
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<Int64Array>()
+                    .unwrap();
+                array.values().iter().copied().collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(ids(&result), (0..100).collect::<Vec<i64>>());
+    assert_eq!(ids(&result), ids(&expected));
+    Ok(())
+}
+
+#[test]
+fn parallel_batch_reader_row_numbers() -> Result<()> {
+    // deserializes each row into the row number passed to the deserializer
+    fn row_numbers(
+        rows: &[ByteRecord],
+        _: usize,
+        _: DataType,
+        row_number: usize,
+    ) -> Result<Arc<dyn Array>> {
+        let values = (row_number..row_number + rows.len()).map(|x| x as i64);
+        Ok(Arc::new(Int64Array::from_values(values)))
+    }
+
+    let mut data = "id,text\n".to_string();
+    for i in 0..100 {
+        data.push_str(&format!("{},\"line\n{}\"\n", i, i));
+    }
+    let builder = BatchReaderBuilder::new()
+        .with_schema(Arc::new(Schema::new(vec![Field::new(
+            "id",
+            DataType::Int64,
+            false,
+        )])))
+        .with_projection(vec![0])
+        .with_deserializer(row_numbers)
+        .with_batch_size(7)
+        .with_chunk_size(50);
+
+    let expected = builder
+        .build(data.as_bytes())?
+        .collect::<Result<Vec<_>>>()?;
+    let result = builder
+        .build_parallel(data.as_bytes(), 3)?
+        .collect::<Result<Vec<_>>>()?;
+
+    let values = |batches: &[RecordBatch]| {
+        batches
+            .iter()
+            .flat_map(|batch| {
+                let array = batch
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<Int64Array>()
+                    .unwrap();
+                array.values().iter().copied().collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(values(&result), (0..100).collect::<Vec<i64>>());
+    assert_eq!(values(&result), values(&expected));
+    Ok(())
+}
+
+#[test]
+fn parallel_batch_reader_error_line() -> Result<()> {
+    let data = "a\n1\n2\n3\nx\n";
+    let error = BatchReaderBuilder::new()
+        .with_schema(Arc::new(Schema::new(vec![Field::new(
+            "a",
+            DataType::Int32,
+            true,
+        )])))
+        .with_options(DeserializeOptions {
+            mode: ParseMode::Strict,
+            ..Default::default()
+        })
+        .with_chunk_size(4)
+        .build_parallel(data.as_bytes(), 2)?
+        .collect::<Result<Vec<_>>>()
+        .unwrap_err();
+    assert!(error.to_string().contains("line 5"), "{}", error);
+    Ok(())
+}
+
+#[test]
+fn batch_reader_options() -> Result<()> {
+    let data = "a;b\n1,5;Y\nNA;N\n";
+    let options = DeserializeOptions {
+        null_values: vec!["NA".to_string()],
+        true_values: vec!["Y".to_string()],
+        false_values: vec!["N".to_string()],
+        decimal_separator: b',',
+        ..Default::default()
+    };
+    let builder = BatchReaderBuilder::new()
+        .with_delimiter(b';')
+        .with_options(options);
+
+    for reader in [
+        Box::new(builder.build(data.as_bytes())?) as Box<dyn RecordBatchReader>,
+        Box::new(builder.build_parallel(data.as_bytes(), 2)?),
+    ] {
+        let batches = reader.collect::<Result<Vec<_>>>()?;
+        assert_eq!(batches.len(), 1);
+        assert_eq!(
+            batches[0].column(0).as_ref(),
+            &Float64Array::from([Some(1.5), None]) as &dyn Array
+        );
+        assert_eq!(
+            batches[0].column(1).as_ref(),
+            &BooleanArray::from([Some(true), Some(false)]) as &dyn Array
+        );
+    }
+    Ok(())
+}
@@ -24,17 +24,19 @@ async fn read() -> Result<()> {
 "Aberdeen, Aberdeen City, UK",57.149651,-2.099075"#;
     let mut reader = AsyncReaderBuilder::new().create_reader(Cursor::new(data.as_bytes()));
 
-    let schema = Arc::new(infer_schema(&mut reader, None, true, &infer).await?);
+    let options = InferSchemaOptions {
+        has_header: true,
+        ..Default::default()
+    };
+    let (schema, rows) = infer_schema(&mut reader, &options, &infer).await?;
+    let schema = Arc::new(schema);
 
-    let mut rows = vec![ByteRecord::default(); 100];
-    let rows_read = read_rows(&mut reader, 0, &mut rows).await?;
-
-    let batch = deserialize_batch(
-        &rows[..rows_read],
+    let (batch, _) = deserialize_batch(
+        &rows,
         schema.fields(),
         None,
         0,
-        deserialize_column,
+        &DeserializeOptions::default(),
     )?;
 
     let batch_schema = batch.schema();
@@ -60,3 +62,92 @@ async fn read() -> Result<()> {
     assert_eq!("Aberdeen, Aberdeen City, UK", city.value(13));
     Ok(())
 }
+
+#[tokio::test]
+async fn strict() -> Result<()> {
+    use arrow2::datatypes::{DataType, Field};
+
+    let data = "a\n1\nb";
+    let mut reader = AsyncReaderBuilder::new().create_reader(Cursor::new(data.as_bytes()));
+    let mut rows = vec![ByteRecord::default(); 10];
+    let rows_read = read_rows(&mut reader, 0, &mut rows).await?;
+    let rows = &rows[..rows_read];
+
+    let fields = vec![Field::new("a", DataType::Int32, true)];
+    let options = DeserializeOptions {
+        mode: ParseMode::Strict,
+        ..Default::default()
+    };
+    let error = deserialize_batch(rows, &fields, None, 0, &options)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("line 3"));
+    assert!(error.contains("column \"a\""));
+
+    let options = DeserializeOptions {
+        mode: ParseMode::Permissive,
+        ..Default::default()
+    };
+    let (_, rejected) = deserialize_batch(rows, &fields, None, 0, &options)?;
+    assert_eq!(rejected.len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn dictionary() -> Result<()> {
+    use arrow2::datatypes::{DataType, IntegerType};
+
+    let data = "a\nx\ny\nx";
+    let mut reader = AsyncReaderBuilder::new().create_reader(Cursor::new(data.as_bytes()));
+    let mut rows = vec![ByteRecord::default(); 10];
+    let rows_read = read_rows(&mut reader, 0, &mut rows).await?;
+
+    let data_type = DataType::Dictionary(IntegerType::UInt32, Box::new(DataType::Utf8));
+    let (result, _) = deserialize_column(
+        &rows[..rows_read],
+        0,
+        data_type,
+        0,
+        &DeserializeOptions::default(),
+    )?;
+
+    let mut expected = MutableDictionaryArray::<u32, MutableUtf8Array<i32>>::new();
+    expected.try_extend(vec![Some("x"), Some("y"), Some("x")])?;
+    let expected: DictionaryArray<u32> = expected.into();
+    assert_eq!(expected, result.as_ref());
+    Ok(())
+}
+
+#[tokio::test]
+async fn infer_buffered() -> Result<()> {
+    let data = "a,b\n1,x\n2,y\n3,z\n";
+    let mut reader = AsyncReaderBuilder::new().create_reader(data.as_bytes());
+
+    let options = InferSchemaOptions {
+        max_rows: Some(2),
+        has_header: true,
+        ..Default::default()
+    };
+    let (schema, mut rows) = infer_schema(&mut reader, &options, &infer).await?;
+    assert_eq!(rows.len(), 2);
+
+    let mut remaining = vec![ByteRecord::default(); 10];
+    let rows_read = read_rows(&mut reader, 0, &mut remaining).await?;
+    rows.extend(remaining.into_iter().take(rows_read));
+
+    let (batch, _) = deserialize_batch(
+        &rows,
+        schema.fields(),
+        None,
+        0,
+        &DeserializeOptions::default(),
+    )?;
+    assert_eq!(batch.num_rows(), 3);
+    let b = batch
+        .column(1)
+        .as_any()
+        .downcast_ref::<Utf8Array<i32>>()
+        .unwrap();
+    assert_eq!(b.value(2), "z");
+    Ok(())
+}
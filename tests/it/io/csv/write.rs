@@ -6,6 +6,7 @@ use arrow2::datatypes::*;
 use arrow2::error::Result;
 use arrow2::io::csv::write::*;
 use arrow2::record_batch::RecordBatch;
+use arrow2::types::months_days_ns;
 
 fn data() -> RecordBatch {
     let schema = Schema::new(vec![
@@ -56,18 +57,17 @@ fn data() -> RecordBatch {
 fn write_csv() -> Result<()> {
     let batch = data();
 
-    let write = Cursor::new(Vec::<u8>::new());
-    let mut writer = WriterBuilder::new().from_writer(write);
+    let mut writer = Cursor::new(Vec::<u8>::new());
+    let options = SerializeOptions::default();
 
-    write_header(&mut writer, batch.schema())?;
+    write_header(&mut writer, batch.schema(), &options)?;
     let batches = vec![&batch, &batch];
-    let options = SerializeOptions::default();
     batches
         .iter()
         .try_for_each(|batch| write_batch(&mut writer, batch, &options))?;
 
     // check
-    let buffer = writer.into_inner().unwrap().into_inner();
+    let buffer = writer.into_inner();
     assert_eq!(
         r#"c1,c2,c3,c4,c5,c6,c7
 Lorem ipsum dolor sit amet,123.564532,3,true,,00:20:34,sed do eiusmod tempor
@@ -87,10 +87,10 @@ sed do eiusmod tempor,-556132.25,1,,2019-04-18T02:45:55.555000000,23:46:03,conse
 fn write_csv_custom_options() -> Result<()> {
     let batch = data();
 
-    let write = Cursor::new(Vec::<u8>::new());
-    let mut writer = WriterBuilder::new().delimiter(b'|').from_writer(write);
+    let mut writer = Cursor::new(Vec::<u8>::new());
 
     let options = SerializeOptions {
+        delimiter: b'|',
         time32_format: "%r".to_string(),
         time64_format: "%r".to_string(),
         ..Default::default()
@@ -98,7 +98,7 @@ fn write_csv_custom_options() -> Result<()> {
     write_batch(&mut writer, &batch, &options)?;
 
     // check
-    let buffer = writer.into_inner().unwrap().into_inner();
+    let buffer = writer.into_inner();
     assert_eq!(
         r#"Lorem ipsum dolor sit amet|123.564532|3|true||12:20:34 AM|sed do eiusmod tempor
 consectetur adipiscing elit||2|false|2019-04-18T10:54:47.378000000|06:51:20 AM|Lorem ipsum dolor sit amet
@@ -109,3 +109,211 @@ sed do eiusmod tempor|-556132.25|1||2019-04-18T02:45:55.555000000|11:46:03 PM|co
     );
     Ok(())
 }
+
+fn write_single(array: Arc<dyn Array>, options: &SerializeOptions) -> Result<String> {
+    let schema = Schema::new(vec![Field::new("a", array.data_type().clone(), true)]);
+    let batch = RecordBatch::try_new(Arc::new(schema), vec![array])?;
+
+    Ok(String::from_utf8(serialize(&batch, options)?).unwrap())
+}
+
+#[test]
+fn write_null_and_float_precision() -> Result<()> {
+    let array = Float64Array::from([Some(1.0), None, Some(-2.126)]);
+    let options = SerializeOptions {
+        null: "NULL".to_string(),
+        float_precision: Some(2),
+        ..Default::default()
+    };
+    assert_eq!(
+        write_single(Arc::new(array), &options)?,
+        "1.00\nNULL\n-2.13\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn write_decimal() -> Result<()> {
+    let array = Int128Array::from([Some(12345), None, Some(-5)]).to(DataType::Decimal(10, 2));
+    assert_eq!(
+        write_single(Arc::new(array), &SerializeOptions::default())?,
+        "123.45\n\"\"\n-0.05\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn write_timestamp_tz() -> Result<()> {
+    let array = Int64Array::from_slice(&[1555584887378]).to(DataType::Timestamp(
+        TimeUnit::Millisecond,
+        Some("+01:00".to_string()),
+    ));
+    let array: Arc<dyn Array> = Arc::new(array);
+    assert_eq!(
+        write_single(array.clone(), &SerializeOptions::default())?,
+        "2019-04-18T11:54:47.378000000+01:00\n"
+    );
+
+    let options = SerializeOptions {
+        timezone: Some("UTC".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        write_single(array, &options)?,
+        "2019-04-18T10:54:47.378000000+00:00\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn write_duration_and_interval() -> Result<()> {
+    let array =
+        Int64Array::from_slice(&[1500, -2000, 0]).to(DataType::Duration(TimeUnit::Millisecond));
+    assert_eq!(
+        write_single(Arc::new(array), &SerializeOptions::default())?,
+        "PT1.5S\n-PT2S\nPT0S\n"
+    );
+
+    let array = PrimitiveArray::<months_days_ns>::from_slice(&[
+        months_days_ns::new(1, 2, 3_500_000_000),
+        months_days_ns::new(-1, 2, 0),
+    ])
+    .to(DataType::Interval(IntervalUnit::MonthDayNano));
+    assert_eq!(
+        write_single(Arc::new(array), &SerializeOptions::default())?,
+        "P1M2DT3.5S\nP-1M2D\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn write_duration_extremes() -> Result<()> {
+    let array =
+        Int64Array::from_slice(&[i64::MAX, i64::MIN]).to(DataType::Duration(TimeUnit::Second));
+    assert_eq!(
+        write_single(Arc::new(array), &SerializeOptions::default())?,
+        "PT9223372036854775807S\n-PT9223372036854775808S\n"
+    );
+
+    let array = Int64Array::from_slice(&[i64::MIN]).to(DataType::Duration(TimeUnit::Nanosecond));
+    assert_eq!(
+        write_single(Arc::new(array), &SerializeOptions::default())?,
+        "-PT9223372036.854775808S\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn write_binary_formats() -> Result<()> {
+    let array: Arc<dyn Array> = Arc::new(BinaryArray::<i32>::from_slice(&[b"ab", b"\x00\xff"]));
+
+    let options = SerializeOptions {
+        binary_format: BinaryFormat::Hex,
+        ..Default::default()
+    };
+    assert_eq!(write_single(array.clone(), &options)?, "6162\n00ff\n");
+
+    let options = SerializeOptions {
+        binary_format: BinaryFormat::Base64,
+        ..Default::default()
+    };
+    assert_eq!(write_single(array, &options)?, "YWI=\nAP8=\n");
+    Ok(())
+}
+
+#[test]
+fn write_dictionary_of_integers() -> Result<()> {
+    let keys = Int8Array::from(&[Some(1), None, Some(0)]);
+    let values = Int32Array::from_slice(&[10, 20]);
+    let array = DictionaryArray::<i8>::from_data(keys, Arc::new(values));
+    let options = SerializeOptions {
+        null: "NA".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(write_single(Arc::new(array), &options)?, "20\nNA\n10\n");
+    Ok(())
+}
+
+#[test]
+fn write_nested() -> Result<()> {
+    let data = vec![Some(vec![Some(1), None, Some(3)]), None, Some(vec![])];
+    let mut array = MutableListArray::<i32, MutablePrimitiveArray<i32>>::new();
+    array.try_extend(data)?;
+    let array: ListArray<i32> = array.into();
+    let options = SerializeOptions {
+        quote_style: QuoteStyle::Never,
+        ..Default::default()
+    };
+    assert_eq!(
+        write_single(Arc::new(array), &options)?,
+        "[1,null,3]\n\"\"\n[]\n"
+    );
+
+    let fields = vec![
+        Field::new("b", DataType::Boolean, true),
+        Field::new("c", DataType::Utf8, true),
+    ];
+    let array = StructArray::from_data(
+        DataType::Struct(fields),
+        vec![
+            Arc::new(BooleanArray::from(&[Some(true), None])),
+            Arc::new(Utf8Array::<i32>::from(&[Some("a\"b"), Some("c")])),
+        ],
+        None,
+    );
+    assert_eq!(
+        write_single(Arc::new(array), &options)?,
+        "{\"b\":true,\"c\":\"a\\\"b\"}\n{\"b\":null,\"c\":\"c\"}\n"
+    );
+
+    // nested binary values are written as JSON strings according to `binary_format`
+    let values = BinaryArray::<i32>::from_slice(&[b"ab", b"\x00\xff"]);
+    let array = FixedSizeListArray::from_data(
+        DataType::FixedSizeList(Box::new(Field::new("item", DataType::Binary, true)), 2),
+        Arc::new(values),
+        None,
+    );
+    let options = SerializeOptions {
+        binary_format: BinaryFormat::Hex,
+        quote_style: QuoteStyle::Never,
+        ..Default::default()
+    };
+    assert_eq!(
+        write_single(Arc::new(array), &options)?,
+        "[\"6162\",\"00ff\"]\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn write_quote_style() -> Result<()> {
+    let array: Arc<dyn Array> = Arc::new(Utf8Array::<i32>::from_slice(&["a", "b,c"]));
+    let options = SerializeOptions {
+        quote_style: QuoteStyle::Always,
+        ..Default::default()
+    };
+    assert_eq!(write_single(array.clone(), &options)?, "\"a\"\n\"b,c\"\n");
+
+    assert_eq!(
+        write_single(array, &SerializeOptions::default())?,
+        "a\n\"b,c\"\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn write_header_quote_style() -> Result<()> {
+    let schema = Schema::new(vec![
+        Field::new("a", DataType::Int32, true),
+        Field::new("b;c", DataType::Int32, true),
+    ]);
+    let options = SerializeOptions {
+        delimiter: b';',
+        quote_style: QuoteStyle::NonNumeric,
+        ..Default::default()
+    };
+    let mut writer = vec![];
+    write_header(&mut writer, &schema, &options)?;
+    assert_eq!(String::from_utf8(writer).unwrap(), "\"a\";\"b;c\"\n");
+    Ok(())
+}
@@ -0,0 +1,155 @@
+use std::sync::Arc;
+
+use futures::{SinkExt, StreamExt};
+
+use arrow2::array::*;
+use arrow2::error::Result;
+use arrow2::io::flight::*;
+use arrow2::io::ipc::write::WriteOptions;
+use arrow2::record_batch::RecordBatch;
+
+fn batches() -> Result<Vec<RecordBatch>> {
+    let mut dictionary = MutableDictionaryArray::<i32, MutableUtf8Array<i32>>::new();
+    dictionary.try_extend((0..100).map(|i| Some(["a", "b", "c"][i % 3])))?;
+    let dictionary: DictionaryArray<i32> = dictionary.into();
+
+    let batch = RecordBatch::try_from_iter(vec![
+        (
+            "a",
+            Arc::new(Int64Array::from_values(0..100)) as Arc<dyn Array>,
+        ),
+        ("b", Arc::new(dictionary) as Arc<dyn Array>),
+        (
+            "c",
+            Arc::new(Utf8Array::<i32>::from_iter_values(
+                (0..100).map(|i| ["a", "b", "c"][i % 3]),
+            )) as Arc<dyn Array>,
+        ),
+    ])?;
+    Ok(vec![batch.clone(), batch])
+}
+
+#[test]
+fn round_trip() -> Result<()> {
+    let batches = batches()?;
+    let schema = batches[0].schema().clone();
+    let options = WriteOptions { compression: None };
+
+    let messages = encode_iter(
+        schema.clone(),
+        batches.clone().into_iter().map(Ok),
+        options,
+        usize::MAX,
+    )
+    .collect::<Result<Vec<_>>>()?;
+    // schema, dictionary, 2 batches: the dictionary is only sent once
+    assert_eq!(messages.len(), 4);
+
+    let result = decode_iter(messages.into_iter().map(Ok)).collect::<Result<Vec<_>>>()?;
+    assert_eq!(result, batches);
+    Ok(())
+}
+
+#[test]
+fn split_batches() -> Result<()> {
+    let batches = batches()?;
+    let schema = batches[0].schema().clone();
+    let options = WriteOptions { compression: None };
+
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    for message in encode_iter(schema, batches.clone().into_iter().map(Ok), options, 1024) {
+        let message = message?;
+        assert!(message.data_header.len() + message.data_body.len() <= 1024);
+        sender.send(Ok(message)).unwrap();
+    }
+    drop(sender);
+
+    let result = decode_iter(receiver).collect::<Result<Vec<_>>>()?;
+    assert!(result.len() > batches.len());
+    assert!(result.iter().all(|batch| batch.num_rows() < 100));
+
+    let arrays = result
+        .iter()
+        .map(|batch| batch.column(0).as_ref())
+        .collect::<Vec<_>>();
+    let result = arrow2::compute::concat::concatenate(&arrays)?;
+    let expected = arrow2::compute::concat::concatenate(&[
+        batches[0].column(0).as_ref(),
+        batches[1].column(0).as_ref(),
+    ])?;
+    assert_eq!(result.as_ref(), expected.as_ref());
+    Ok(())
+}
+
+#[test]
+fn split_fixed_size_list() -> Result<()> {
+    use arrow2::datatypes::DataType;
+
+    let values = Arc::new(Int32Array::from_values(0..300));
+    let data_type = FixedSizeListArray::default_datatype(DataType::Int32, 3);
+    let array = FixedSizeListArray::from_data(data_type, values, None);
+    let batch = RecordBatch::try_from_iter(vec![("a", Arc::new(array) as Arc<dyn Array>)])?;
+    let schema = batch.schema().clone();
+    let options = WriteOptions { compression: None };
+
+    let messages = encode_iter(schema, std::iter::once(Ok(batch.clone())), options, 1024)
+        .collect::<Result<Vec<_>>>()?;
+    assert!(messages
+        .iter()
+        .all(|message| message.data_header.len() + message.data_body.len() <= 1024));
+
+    let result = decode_iter(messages.into_iter().map(Ok)).collect::<Result<Vec<_>>>()?;
+    assert!(result.len() > 1);
+    let arrays = result
+        .iter()
+        .map(|batch| batch.column(0).as_ref())
+        .collect::<Vec<_>>();
+    let result = arrow2::compute::concat::concatenate(&arrays)?;
+    assert_eq!(result.as_ref(), batch.column(0).as_ref());
+    Ok(())
+}
+
+#[test]
+fn schema_must_be_first() {
+    let batch = batches().unwrap().pop().unwrap();
+    let mut encoder = FlightDataEncoder::new(
+        batch.schema().clone(),
+        WriteOptions { compression: None },
+        usize::MAX,
+    );
+    let messages = encoder.encode(&batch).unwrap();
+
+    let mut decoder = FlightDataDecoder::new();
+    assert!(decoder.decode(messages.last().unwrap()).is_err());
+}
+
+#[tokio::test]
+async fn round_trip_stream() -> Result<()> {
+    let batches = batches()?;
+    let schema = batches[0].schema().clone();
+    let options = WriteOptions { compression: None };
+
+    let (mut sender, receiver) = futures::channel::mpsc::unbounded();
+    let messages = encode_stream(
+        schema,
+        futures::stream::iter(batches.clone().into_iter().map(Ok)),
+        options,
+        1024,
+    );
+    futures::pin_mut!(messages);
+    while let Some(message) = messages.next().await {
+        sender.send(message).await.unwrap();
+    }
+    drop(sender);
+
+    let result = decode_stream(receiver)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>>>()?;
+    assert_eq!(
+        result.iter().map(|batch| batch.num_rows()).sum::<usize>(),
+        200
+    );
+    Ok(())
+}
@@ -13,7 +13,7 @@ fn test_file(version: &str, file_name: &str) -> Result<()> {
     ))?;
 
     let metadata = read_stream_metadata(&mut file)?;
-    let reader = StreamReader::new(file, metadata);
+    let reader = StreamReader::new(file, metadata, None);
 
     // read expected JSON output
     let (schema, batches) = read_gzip_json(version, file_name)?;
@@ -69,6 +69,11 @@ fn read_generated_100_nested() -> Result<()> {
     test_file("1.0.0-littleendian", "generated_nested")
 }
 
+#[test]
+fn read_generated_100_nested_dictionary() -> Result<()> {
+    test_file("1.0.0-littleendian", "generated_nested_dictionary")
+}
+
 #[test]
 fn read_generated_100_interval() -> Result<()> {
     test_file("1.0.0-littleendian", "generated_interval")
@@ -99,3 +104,64 @@ fn read_generated_200_compression_lz4() -> Result<()> {
 fn read_generated_200_compression_zstd() -> Result<()> {
     test_file("2.0.0-compression", "generated_zstd")
 }
+
+fn test_projection(version: &str, file_name: &str, column: usize) -> Result<()> {
+    let testdata = crate::test_util::arrow_test_data();
+    let mut file = File::open(format!(
+        "{}/arrow-ipc-stream/integration/{}/{}.stream",
+        testdata, version, file_name
+    ))?;
+
+    let metadata = read_stream_metadata(&mut file)?;
+    let reader = StreamReader::new(file, metadata, Some(vec![column]));
+
+    // read expected JSON output
+    let (schema, batches) = read_gzip_json(version, file_name)?;
+
+    assert_eq!(reader.schema().fields().len(), 1);
+    assert_eq!(&schema.fields()[column], &reader.schema().fields()[0]);
+
+    batches
+        .iter()
+        .zip(reader.map(|x| x.unwrap().unwrap()))
+        .for_each(|(lhs, rhs)| {
+            assert_eq!(rhs.num_columns(), 1);
+            assert_eq!(lhs.column(column), rhs.column(0));
+        });
+    Ok(())
+}
+
+#[test]
+fn read_projected() -> Result<()> {
+    test_projection("1.0.0-littleendian", "generated_primitive", 1)?;
+    test_projection("1.0.0-littleendian", "generated_dictionary", 2)?;
+    test_projection("1.0.0-littleendian", "generated_nested", 0)
+}
+
+#[test]
+fn skip_batches() -> Result<()> {
+    let testdata = crate::test_util::arrow_test_data();
+    let mut file = File::open(format!(
+        "{}/arrow-ipc-stream/integration/1.0.0-littleendian/generated_dictionary.stream",
+        testdata
+    ))?;
+
+    let metadata = read_stream_metadata(&mut file)?;
+    let mut reader = StreamReader::new(file, metadata, None);
+
+    let (_, batches) = read_gzip_json("1.0.0-littleendian", "generated_dictionary")?;
+    assert!(batches.len() > 1);
+
+    assert_eq!(reader.skip_batches(1)?, 1);
+
+    batches
+        .iter()
+        .skip(1)
+        .zip(reader.by_ref().map(|x| x.unwrap().unwrap()))
+        .for_each(|(lhs, rhs)| {
+            assert_eq!(lhs, &rhs);
+        });
+
+    assert_eq!(reader.skip_batches(1)?, 0);
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use std::io::Cursor;
 
 use arrow2::array::*;
+use arrow2::datatypes::Schema;
 use arrow2::error::Result;
 use arrow2::io::ipc::read::{read_file_metadata, FileReader};
 use arrow2::io::ipc::write::*;
@@ -14,7 +15,7 @@ fn round_trip(batch: RecordBatch) -> Result<()> {
     // write IPC version 5
     let written_result = {
         let options = WriteOptions {
-            compression: Some(Compression::LZ4),
+            compression: Some(Compression::LZ4.into()),
         };
         let mut writer = FileWriter::try_new(result, batch.schema(), options)?;
         writer.write(&batch)?;
@@ -44,7 +45,7 @@ fn test_file(version: &str, file_name: &str, compressed: bool) -> Result<()> {
     let result = Vec::<u8>::new();
 
     let compression = if compressed {
-        Some(Compression::ZSTD)
+        Some(Compression::ZSTD.into())
     } else {
         None
     };
@@ -359,3 +360,329 @@ fn write_sliced_list() -> Result<()> {
     let batch = RecordBatch::try_from_iter(vec![("a", array)]).unwrap();
     round_trip(batch)
 }
+
+#[test]
+fn write_custom_metadata() -> Result<()> {
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+
+    let array = Arc::new(Int32Array::from_slice([1, 2, 3])) as Arc<dyn Array>;
+    let batch = RecordBatch::try_from_iter(vec![("a", array)])?;
+
+    let mut batch_metadata = BTreeMap::new();
+    batch_metadata.insert("watermark".to_string(), "1634567890".to_string());
+    let mut footer_metadata = BTreeMap::new();
+    footer_metadata.insert("provenance".to_string(), "sensor-1".to_string());
+
+    let written_result = {
+        let options = WriteOptions { compression: None };
+        let mut writer = FileWriter::try_new(vec![], batch.schema(), options)?;
+        writer.write_with_metadata(&batch, &batch_metadata)?;
+        writer.write(&batch)?;
+        writer.finish_with_metadata(&footer_metadata)?;
+        writer.into_inner()
+    };
+    let mut reader = Cursor::new(written_result);
+    let metadata = read_file_metadata(&mut reader)?;
+    assert_eq!(metadata.custom_metadata(), &footer_metadata);
+
+    let mut reader = FileReader::new(reader, metadata, None);
+
+    assert_eq!(reader.next().unwrap()?, batch);
+    assert_eq!(reader.batch_metadata(), &batch_metadata);
+    assert_eq!(reader.next().unwrap()?, batch);
+    assert!(reader.batch_metadata().is_empty());
+    Ok(())
+}
+
+fn round_trip_compressed(batch: &RecordBatch, compression: CompressionOptions) -> Result<()> {
+    let written_result = {
+        let options = WriteOptions {
+            compression: Some(compression),
+        };
+        let mut writer = FileWriter::try_new(vec![], batch.schema(), options)?;
+        writer.write(batch)?;
+        writer.finish()?;
+        writer.into_inner()
+    };
+    let mut reader = Cursor::new(written_result);
+    let metadata = read_file_metadata(&mut reader)?;
+    let reader = FileReader::new(reader, metadata, None);
+
+    let batches = reader.collect::<Result<Vec<_>>>()?;
+    assert_eq!(batches, vec![batch.clone()]);
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // compression uses FFI, which miri does not support
+fn write_compression_options() -> Result<()> {
+    use std::sync::Arc;
+    // a highly compressible column and a column where compression does not help
+    let a = Arc::new(Int32Array::from_values(std::iter::repeat(1).take(1000))) as Arc<dyn Array>;
+    let b = (0..1000)
+        .map(|x| (x % 3 != 0).then(|| x * 7919 % 1009))
+        .collect::<Int32Array>();
+    let b = Arc::new(b) as Arc<dyn Array>;
+    let batch = RecordBatch::try_from_iter(vec![("a", a), ("b", b)])?;
+
+    for codec in [Compression::LZ4, Compression::ZSTD] {
+        for level in [None, Some(1), Some(9)] {
+            for min_size in [0, 64, usize::MAX] {
+                let compression = CompressionOptions {
+                    codec,
+                    level,
+                    min_size,
+                };
+                round_trip_compressed(&batch, compression)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Writes `array` with `compression`, returning the bytes of each of its buffers
+fn write_buffers(array: &dyn Array, compression: CompressionOptions) -> Vec<Vec<u8>> {
+    let mut buffers = vec![];
+    let mut arrow_data = vec![];
+    write(
+        array,
+        &mut buffers,
+        &mut arrow_data,
+        &mut vec![],
+        &mut 0,
+        true,
+        Some(compression),
+    );
+    buffers
+        .iter()
+        .map(|buffer| {
+            let start = buffer.offset() as usize;
+            arrow_data[start..start + buffer.length() as usize].to_vec()
+        })
+        .collect()
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // compression uses FFI, which miri does not support
+fn write_compression_fallback() {
+    let small = Int32Array::from_slice([1, 2, 3]);
+    let mut uncompressed = (-1i64).to_le_bytes().to_vec();
+    uncompressed.extend_from_slice(&[1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0]);
+
+    let compressible = Int32Array::from_values(std::iter::repeat(1).take(1000));
+
+    for codec in [Compression::LZ4, Compression::ZSTD] {
+        // below `min_size`: written uncompressed
+        let compression = CompressionOptions {
+            codec,
+            level: None,
+            min_size: 64,
+        };
+        let buffer = &write_buffers(&small, compression)[1];
+        assert_eq!(buffer[..uncompressed.len()], uncompressed);
+
+        // compression does not shrink the buffer: written uncompressed
+        let compression = CompressionOptions {
+            codec,
+            level: None,
+            min_size: 0,
+        };
+        let buffer = &write_buffers(&small, compression)[1];
+        assert_eq!(buffer[..uncompressed.len()], uncompressed);
+
+        // compression shrinks the buffer: written compressed, prefixed by its length
+        let buffer = &write_buffers(&compressible, compression)[1];
+        assert_eq!(buffer[..8], 4000i64.to_le_bytes());
+        assert!(buffer.len() < 4000);
+
+        // above `min_size` only when the buffer is larger than it
+        let compression = CompressionOptions {
+            codec,
+            level: None,
+            min_size: 4001,
+        };
+        let buffer = &write_buffers(&compressible, compression)[1];
+        assert_eq!(buffer[..8], (-1i64).to_le_bytes());
+        assert_eq!(buffer.len(), 8 + 4000);
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // compression uses FFI, which miri does not support
+fn write_list_of_dictionary() -> Result<()> {
+    use std::sync::Arc;
+
+    use arrow2::buffer::Buffer;
+    use arrow2::datatypes::{DataType, Field};
+
+    let values = Arc::new(Utf8Array::<i32>::from_slice(["a", "b"]));
+    let keys = Int32Array::from(&[Some(0), None, Some(1), Some(0)]);
+    let dictionary = DictionaryArray::<i32>::from_data(keys, values);
+
+    let field = Field::new_dict("item", dictionary.data_type().clone(), true, 1, false);
+    let array = ListArray::<i32>::from_data(
+        DataType::List(Box::new(field)),
+        Buffer::from(&[0, 2, 4]),
+        Arc::new(dictionary),
+        None,
+    );
+    let batch = RecordBatch::try_from_iter(vec![("a", Arc::new(array) as Arc<dyn Array>)])?;
+    round_trip(batch)
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // compression uses FFI, which miri does not support
+fn write_struct_of_dictionary() -> Result<()> {
+    use std::sync::Arc;
+
+    use arrow2::datatypes::{DataType, Field};
+
+    let values = Arc::new(Utf8Array::<i32>::from_slice(["a", "b"]));
+    let keys = Int32Array::from(&[Some(0), None, Some(1)]);
+    let dictionary = Arc::new(DictionaryArray::<i32>::from_data(keys, values)) as Arc<dyn Array>;
+
+    let fields = vec![
+        Field::new_dict("a", dictionary.data_type().clone(), true, 3, false),
+        Field::new_dict("b", dictionary.data_type().clone(), true, 3, false),
+    ];
+    let array = StructArray::from_data(
+        DataType::Struct(fields),
+        vec![dictionary.clone(), dictionary],
+        None,
+    );
+    let batch = RecordBatch::try_from_iter(vec![("a", Arc::new(array) as Arc<dyn Array>)])?;
+    round_trip(batch)
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // compression uses FFI, which miri does not support
+fn write_dictionary_of_struct_of_dictionary() -> Result<()> {
+    use std::sync::Arc;
+
+    use arrow2::datatypes::{DataType, Field};
+
+    let values = Arc::new(Utf8Array::<i32>::from_slice(["a", "b"]));
+    let keys = Int32Array::from(&[Some(0), None, Some(1)]);
+    let inner = Arc::new(DictionaryArray::<i32>::from_data(keys, values)) as Arc<dyn Array>;
+
+    let fields = vec![Field::new_dict(
+        "a",
+        inner.data_type().clone(),
+        true,
+        2,
+        false,
+    )];
+    let values = Arc::new(StructArray::from_data(
+        DataType::Struct(fields),
+        vec![inner],
+        None,
+    ));
+    let keys = Int32Array::from(&[Some(2), Some(0), None, Some(1), Some(2)]);
+    let array = DictionaryArray::<i32>::from_data(keys, values);
+
+    let field = Field::new_dict("a", array.data_type().clone(), true, 1, false);
+    let schema = Schema::new(vec![field]);
+    let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(array)])?;
+    round_trip(batch)
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // compression uses FFI, which miri does not support
+fn write_dictionary_of_list_of_dictionary() -> Result<()> {
+    use std::sync::Arc;
+
+    use arrow2::buffer::Buffer;
+    use arrow2::datatypes::{DataType, Field};
+
+    let values = Arc::new(Utf8Array::<i32>::from_slice(["a", "b"]));
+    let keys = Int32Array::from(&[Some(0), None, Some(1), Some(0)]);
+    let inner = DictionaryArray::<i32>::from_data(keys, values);
+
+    let field = Field::new_dict("item", inner.data_type().clone(), true, 2, false);
+    let values = Arc::new(ListArray::<i32>::from_data(
+        DataType::List(Box::new(field)),
+        Buffer::from(&[0, 2, 4]),
+        Arc::new(inner),
+        None,
+    ));
+    let keys = Int32Array::from(&[Some(1), Some(0), None, Some(1)]);
+    let array = DictionaryArray::<i32>::from_data(keys, values);
+
+    let field = Field::new_dict("a", array.data_type().clone(), true, 1, false);
+    let schema = Schema::new(vec![field]);
+    let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(array)])?;
+    round_trip(batch)
+}
+
+#[test]
+#[cfg_attr(miri, ignore)] // compression uses FFI, which miri does not support
+fn write_dictionary_in_extension() -> Result<()> {
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+
+    use arrow2::buffer::Buffer;
+    use arrow2::datatypes::{DataType, Field};
+
+    let values = Arc::new(Utf8Array::<i32>::from_slice(["a", "b"]));
+    let keys = Int32Array::from(&[Some(0), None, Some(1)]);
+    let inner = Arc::new(DictionaryArray::<i32>::from_data(keys, values)) as Arc<dyn Array>;
+
+    let fields = vec![Field::new_dict(
+        "a",
+        inner.data_type().clone(),
+        true,
+        2,
+        false,
+    )];
+    let data_type = DataType::Extension(
+        "ext".to_string(),
+        Box::new(DataType::Struct(fields)),
+        Some("metadata".to_string()),
+    );
+    let extension = Arc::new(StructArray::from_data(data_type, vec![inner], None));
+
+    // the IPC reader declares the extension in the metadata of the field
+    let metadata: BTreeMap<String, String> = [
+        ("ARROW:extension:name", "ext"),
+        ("ARROW:extension:metadata", "metadata"),
+    ]
+    .iter()
+    .map(|(k, v)| (k.to_string(), v.to_string()))
+    .collect();
+
+    // an extension nested in a list
+    let field =
+        Field::new("item", extension.data_type().clone(), true).with_metadata(metadata.clone());
+    let array = ListArray::<i32>::from_data(
+        DataType::List(Box::new(field)),
+        Buffer::from(&[0, 2, 2, 3]),
+        extension.clone(),
+        None,
+    );
+
+    let schema = Schema::new(vec![
+        Field::new("a", extension.data_type().clone(), true).with_metadata(metadata),
+        Field::new("b", array.data_type().clone(), true),
+    ]);
+    let batch = RecordBatch::try_new(Arc::new(schema), vec![extension, Arc::new(array)])?;
+    round_trip(batch)
+}
+
+#[test]
+fn write_dictionary_of_dictionary() -> Result<()> {
+    use std::sync::Arc;
+
+    let values = Arc::new(Utf8Array::<i32>::from_slice(["a", "b"]));
+    let keys = Int32Array::from_slice([0, 1]);
+    let inner = Arc::new(DictionaryArray::<i32>::from_data(keys, values));
+    let keys = Int32Array::from_slice([1, 0, 1]);
+    let array = DictionaryArray::<i32>::from_data(keys, inner);
+    let batch = RecordBatch::try_from_iter(vec![("a", Arc::new(array) as Arc<dyn Array>)])?;
+
+    // an IPC field declares a single dictionary encoding
+    let options = WriteOptions { compression: None };
+    let mut writer = FileWriter::try_new(vec![], batch.schema(), options)?;
+    assert!(writer.write(&batch).is_err());
+    Ok(())
+}
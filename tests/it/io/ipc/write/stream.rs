@@ -25,7 +25,7 @@ fn test_file(version: &str, file_name: &str) {
 
     let mut reader = Cursor::new(result);
     let metadata = read_stream_metadata(&mut reader).unwrap();
-    let reader = StreamReader::new(reader, metadata);
+    let reader = StreamReader::new(reader, metadata, None);
 
     let schema = reader.schema().clone();
 
@@ -78,6 +78,11 @@ fn write_100_nested() {
     test_file("1.0.0-littleendian", "generated_nested");
 }
 
+#[test]
+fn write_100_nested_dictionary() {
+    test_file("1.0.0-littleendian", "generated_nested_dictionary");
+}
+
 #[test]
 fn write_100_nested_large_offsets() {
     test_file("1.0.0-littleendian", "generated_nested_large_offsets");
@@ -132,3 +137,37 @@ fn write_100_custom_metadata() {
 fn write_100_decimal() {
     test_file("1.0.0-littleendian", "generated_decimal");
 }
+
+#[test]
+fn write_custom_metadata() -> Result<()> {
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+
+    use arrow2::array::{Array, Int32Array};
+    use arrow2::record_batch::RecordBatch;
+
+    let array = Arc::new(Int32Array::from_slice([1, 2, 3])) as Arc<dyn Array>;
+    let batch = RecordBatch::try_from_iter(vec![("a", array)])?;
+
+    let mut batch_metadata = BTreeMap::new();
+    batch_metadata.insert("watermark".to_string(), "1634567890".to_string());
+
+    let mut result = Vec::<u8>::new();
+    {
+        let options = WriteOptions { compression: None };
+        let mut writer = StreamWriter::try_new(&mut result, batch.schema(), options)?;
+        writer.write_with_metadata(&batch, &batch_metadata)?;
+        writer.write(&batch)?;
+        writer.finish()?;
+    }
+
+    let mut reader = Cursor::new(result);
+    let metadata = read_stream_metadata(&mut reader)?;
+    let mut reader = StreamReader::new(reader, metadata, None);
+
+    assert_eq!(reader.next().unwrap()?.unwrap(), batch);
+    assert_eq!(reader.batch_metadata(), &batch_metadata);
+    assert_eq!(reader.next().unwrap()?.unwrap(), batch);
+    assert!(reader.batch_metadata().is_empty());
+    Ok(())
+}
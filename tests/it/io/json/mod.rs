@@ -8,7 +8,9 @@ use serde_json::Value;
 
 use arrow2::array::*;
 use arrow2::datatypes::*;
-use arrow2::io::json::{LineDelimitedWriter, ReaderBuilder};
+use arrow2::io::json::{BinaryFormat, LineDelimitedWriter, ReaderBuilder, SerializeOptions};
+use arrow2::record_batch::RecordBatch;
+use arrow2::types::{days_ms, months_days_ns};
 
 fn round_trip(data: String) {
     let builder = ReaderBuilder::new()
@@ -53,6 +55,116 @@ fn round_trip_list() {
     round_trip(data);
 }
 
+fn round_trip_array(array: Arc<dyn Array>, options: SerializeOptions) {
+    let binary_format = options.binary_format;
+    let field = Field::new("a", array.data_type().clone(), true);
+    let schema = Arc::new(Schema::new(vec![field]));
+    let batch = RecordBatch::try_new(schema.clone(), vec![array.clone()]).unwrap();
+
+    let mut buf = Vec::new();
+    {
+        let mut writer = LineDelimitedWriter::new(&mut buf).with_options(options);
+        writer.write_batches(&[batch]).unwrap();
+    }
+
+    let mut reader = ReaderBuilder::new()
+        .with_schema(schema)
+        .with_binary_format(binary_format)
+        .build(Cursor::new(buf))
+        .unwrap();
+    let batch = reader.next().unwrap().unwrap();
+
+    assert_eq!(batch.column(0).as_ref(), array.as_ref());
+}
+
+#[test]
+fn round_trip_temporal() {
+    let array = Int32Array::from(&[Some(18000), None, Some(-1)]).to(DataType::Date32);
+    round_trip_array(Arc::new(array), SerializeOptions::default());
+
+    let array = Int64Array::from(&[Some(1_555_200_000_000), None]).to(DataType::Date64);
+    round_trip_array(Arc::new(array), SerializeOptions::default());
+
+    let array =
+        Int32Array::from(&[Some(3_600_000), None]).to(DataType::Time32(TimeUnit::Millisecond));
+    round_trip_array(Arc::new(array), SerializeOptions::default());
+
+    let array = Int64Array::from(&[Some(3_600_000_000_001), None])
+        .to(DataType::Time64(TimeUnit::Nanosecond));
+    round_trip_array(Arc::new(array), SerializeOptions::default());
+
+    let array = Int64Array::from(&[Some(1_555_584_887_378_001), None, Some(-1)])
+        .to(DataType::Timestamp(TimeUnit::Microsecond, None));
+    round_trip_array(Arc::new(array), SerializeOptions::default());
+
+    let array = Int64Array::from(&[Some(1_555_584_887_378), None]).to(DataType::Timestamp(
+        TimeUnit::Millisecond,
+        Some("+01:00".to_string()),
+    ));
+    round_trip_array(Arc::new(array), SerializeOptions::default());
+}
+
+#[test]
+fn round_trip_intervals() {
+    let array = Int64Array::from(&[Some(-3), None]).to(DataType::Duration(TimeUnit::Second));
+    round_trip_array(Arc::new(array), SerializeOptions::default());
+
+    let array = PrimitiveArray::<days_ms>::from(&[Some(days_ms::new(1, -2)), None])
+        .to(DataType::Interval(IntervalUnit::DayTime));
+    round_trip_array(Arc::new(array), SerializeOptions::default());
+
+    let array =
+        PrimitiveArray::<months_days_ns>::from(&[Some(months_days_ns::new(1, 2, -3)), None])
+            .to(DataType::Interval(IntervalUnit::MonthDayNano));
+    round_trip_array(Arc::new(array), SerializeOptions::default());
+}
+
+#[test]
+fn round_trip_decimal() {
+    let array = Int128Array::from(&[Some(12345), None, Some(-5)]).to(DataType::Decimal(10, 2));
+    round_trip_array(Arc::new(array.clone()), SerializeOptions::default());
+
+    let options = SerializeOptions {
+        decimal_as_string: false,
+        ..Default::default()
+    };
+    round_trip_array(Arc::new(array), options);
+}
+
+#[test]
+fn round_trip_binary() {
+    let values = [Some(b"\x00\xff".as_ref()), None, Some(b"".as_ref())];
+    for format in &[BinaryFormat::Base64, BinaryFormat::Hex] {
+        let options = SerializeOptions {
+            binary_format: *format,
+            ..Default::default()
+        };
+        let array = BinaryArray::<i32>::from(&values);
+        round_trip_array(Arc::new(array), options.clone());
+
+        let array = FixedSizeBinaryArray::from_iter(vec![Some([0u8, 255]), None], 2);
+        round_trip_array(Arc::new(array), options);
+    }
+}
+
+#[test]
+fn round_trip_map() {
+    let entries = DataType::Struct(vec![
+        Field::new("key", DataType::Utf8, false),
+        Field::new("value", DataType::Int32, true),
+    ]);
+    let keys = Arc::new(Utf8Array::<i32>::from_slice(&["a", "b", "c"]));
+    let values = Arc::new(Int32Array::from(&[Some(1), None, Some(3)]));
+    let field = StructArray::from_data(entries.clone(), vec![keys, values], None);
+    let array = MapArray::from_data(
+        DataType::Map(Box::new(Field::new("entries", entries, false)), false),
+        vec![0, 2, 2, 3].into(),
+        Arc::new(field),
+        Some([true, false, true].into()),
+    );
+    round_trip_array(Arc::new(array), SerializeOptions::default());
+}
+
 fn case_list() -> (String, Schema, Vec<Box<dyn Array>>) {
     let data = r#"{"a":1, "b":[2.0, 1.3, -6.1], "c":[false, true], "d":"4"}
             {"a":-10, "b":null, "c":[true, true]}
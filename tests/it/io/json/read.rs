@@ -294,3 +294,271 @@ fn infer_schema_mixed_list() -> Result<()> {
     assert_eq!(inferred_schema, schema);
     Ok(())
 }
+
+#[test]
+fn infer_schema_nested() -> Result<()> {
+    let data = r#"{"a": [{"b": 1, "c": {"d": null}}], "e": {"f": [[1, 2]], "g": true}}
+    {"a": [{"b": 1.5, "e": "x"}], "e": {"f": [[3.5], null], "g": null}}
+    {"a": {"b": 2}, "e": null}
+    "#;
+
+    let item = Field::new(
+        "item",
+        DataType::Struct(vec![
+            Field::new("b", DataType::Float64, true),
+            Field::new(
+                "c",
+                DataType::Struct(vec![Field::new("d", DataType::Utf8, true)]),
+                true,
+            ),
+            Field::new("e", DataType::Utf8, true),
+        ]),
+        true,
+    );
+    let inner_list = DataType::List(Box::new(Field::new("item", DataType::Float64, true)));
+    let schema = Schema::new(vec![
+        Field::new("a", DataType::List(Box::new(item)), true),
+        Field::new(
+            "e",
+            DataType::Struct(vec![
+                Field::new(
+                    "f",
+                    DataType::List(Box::new(Field::new("item", inner_list, true))),
+                    true,
+                ),
+                Field::new("g", DataType::Boolean, true),
+            ]),
+            true,
+        ),
+    ]);
+
+    let inferred_schema = infer_json_schema(&mut BufReader::new(Cursor::new(data)), None)?;
+    assert_eq!(inferred_schema, schema);
+
+    // the inferred schema can be used to read the data
+    let mut reader = ReaderBuilder::new()
+        .with_schema(Arc::new(inferred_schema))
+        .build(Cursor::new(data))?;
+    let batch = reader.next()?.unwrap();
+    assert_eq!(batch.num_rows(), 3);
+    Ok(())
+}
+
+#[test]
+fn infer_schema_struct_field_order() -> Result<()> {
+    let data = r#"{"a": {"c": 1, "b": 2}}
+    {"a": {"d": true, "b": 3}}
+    {"a": null}
+    {"a": {"e": "x", "c": 1.5}}
+    "#;
+
+    let schema = Schema::new(vec![Field::new(
+        "a",
+        DataType::Struct(vec![
+            Field::new("c", DataType::Float64, true),
+            Field::new("b", DataType::Int64, true),
+            Field::new("d", DataType::Boolean, true),
+            Field::new("e", DataType::Utf8, true),
+        ]),
+        true,
+    )]);
+
+    // inference is deterministic and keeps the fields in the order they are first seen
+    for _ in 0..2 {
+        let inferred_schema = infer_json_schema(&mut BufReader::new(Cursor::new(data)), None)?;
+        assert_eq!(inferred_schema, schema);
+    }
+    Ok(())
+}
+
+fn assert_direct_equals(data: String, builder: impl Fn() -> ReaderBuilder) -> Result<()> {
+    let mut reader = builder().build(Cursor::new(data.clone()))?;
+    let mut direct = builder().build_direct(Cursor::new(data))?;
+    loop {
+        let (expected, result) = (reader.next()?, direct.next()?);
+        assert_eq!(expected, result);
+        if expected.is_none() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn direct_equals_reader() -> Result<()> {
+    let cases = [
+        case_basics(),
+        case_basics_schema(),
+        case_list(),
+        case_dict(),
+        case_struct(),
+    ];
+    for (data, schema, _) in cases.iter() {
+        let schema = Arc::new(schema.clone());
+        assert_direct_equals(data.clone(), || {
+            ReaderBuilder::new()
+                .with_schema(schema.clone())
+                .with_batch_size(2)
+        })?;
+    }
+    Ok(())
+}
+
+#[test]
+fn direct_projection() -> Result<()> {
+    let data = r#"{"a":1, "b":{"c": [1, {"d": 2}]}, "c":"x", "a2": "ignored"}
+    {"c":"y", "a":2.5, "unknown": [[]]}
+    {"a":true, "c":3, "c":"duplicated"}
+    "#;
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("a", DataType::Int64, true),
+        Field::new("b", DataType::Utf8, true),
+        Field::new("c", DataType::Utf8, true),
+    ]));
+
+    let mut reader = DirectReader::new(
+        Cursor::new(data),
+        schema,
+        1024,
+        Some(vec!["c".to_string(), "a".to_string()]),
+    );
+    let batch = reader.next()?.unwrap();
+    assert!(reader.next()?.is_none());
+
+    let expected = Schema::new(vec![
+        Field::new("c", DataType::Utf8, true),
+        Field::new("a", DataType::Int64, true),
+    ]);
+    assert_eq!(batch.schema().as_ref(), &expected);
+    assert_eq!(
+        batch.column(0).as_ref(),
+        &Utf8Array::<i32>::from(&[Some("x"), Some("y"), Some("duplicated")]) as &dyn Array
+    );
+    assert_eq!(
+        batch.column(1).as_ref(),
+        &Int64Array::from(&[Some(1), None, Some(1)]) as &dyn Array
+    );
+    Ok(())
+}
+
+#[test]
+fn direct_duplicate_keys() -> Result<()> {
+    let data = r#"{"a":1, "b":{"c":1, "c":[1, 2]}, "a":2, "d":{"x":1, "y":2, "x":3}}
+    {"a":3, "b":{"c":[3]}, "d":{"x":4}}
+    {"a":4, "a":5, "a":null}
+    "#;
+    let entries = DataType::Struct(vec![
+        Field::new("key", DataType::Utf8, false),
+        Field::new("value", DataType::Int64, true),
+    ]);
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("a", DataType::Int64, true),
+        Field::new(
+            "b",
+            DataType::Struct(vec![Field::new(
+                "c",
+                DataType::List(Box::new(Field::new("item", DataType::Int64, true))),
+                true,
+            )]),
+            true,
+        ),
+        Field::new(
+            "d",
+            DataType::Map(Box::new(Field::new("entries", entries, false)), false),
+            true,
+        ),
+    ]));
+    assert_direct_equals(data.to_string(), || {
+        ReaderBuilder::new()
+            .with_schema(schema.clone())
+            .with_batch_size(2)
+    })?;
+
+    // the last value of a key is read
+    let mut reader = DirectReader::new(Cursor::new(data), schema, 1024, None);
+    let batch = reader.next()?.unwrap();
+    assert_eq!(
+        batch.column(0).as_ref(),
+        &Int64Array::from(&[Some(2), Some(3), None]) as &dyn Array
+    );
+    Ok(())
+}
+
+#[test]
+fn direct_typed_columns() -> Result<()> {
+    let data = r#"{"date":"2021-01-02", "ts":"2021-01-02T03:04:05.006", "dec":"1.5", "bin":"YWI=", "fixed":"YWI=", "list":[1, 2], "map":{"1":"a", "x":"b"}, "dict":"a"}
+    {"date":1, "ts":1000, "dec":2, "bin":1, "fixed":"YQ==", "list":[1], "map":{}, "dict":1}
+    {"date":true, "ts":1.5, "dec":0.125, "bin":null, "fixed":"YWJj", "list":[1, 2, 3], "map":[], "dict":"a"}
+    {"date":null, "dec":true, "list":"a", "map":null, "dict":1.0}
+    "#;
+    let entries = DataType::Struct(vec![
+        Field::new("key", DataType::Int64, false),
+        Field::new("value", DataType::Utf8, true),
+    ]);
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("date", DataType::Date32, true),
+        Field::new("ts", DataType::Timestamp(TimeUnit::Millisecond, None), true),
+        Field::new("dec", DataType::Decimal(10, 2), true),
+        Field::new("bin", DataType::Binary, true),
+        Field::new("fixed", DataType::FixedSizeBinary(2), true),
+        Field::new(
+            "list",
+            DataType::FixedSizeList(Box::new(Field::new("item", DataType::Int64, true)), 2),
+            true,
+        ),
+        Field::new(
+            "map",
+            DataType::Map(Box::new(Field::new("entries", entries, false)), false),
+            true,
+        ),
+        Field::new(
+            "dict",
+            DataType::Dictionary(IntegerType::Int32, Box::new(DataType::Utf8)),
+            true,
+        ),
+    ]));
+    assert_direct_equals(data.to_string(), || {
+        ReaderBuilder::new()
+            .with_schema(schema.clone())
+            .with_binary_format(BinaryFormat::Base64)
+    })
+}
+
+#[test]
+fn direct_unsupported_type() {
+    let fields = vec![Field::new("a", DataType::Int64, true)];
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "a",
+        DataType::Union(fields, None, UnionMode::Dense),
+        true,
+    )]));
+    let mut reader = DirectReader::new(Cursor::new(r#"{"a":1}"#), schema, 1024, None);
+    assert!(reader.next().is_err());
+}
+
+#[test]
+fn direct_row_type_validation() {
+    let data = r#"[1, "hello"]
+"world""#;
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]));
+    let mut reader = DirectReader::new(Cursor::new(data), schema, 1024, None);
+    assert!(reader.next().is_err());
+}
+
+#[test]
+fn direct_deserialize() -> Result<()> {
+    let (data, schema, columns) = case_list();
+    let rows = data
+        .lines()
+        .map(|x| x.trim())
+        .filter(|x| !x.is_empty())
+        .collect::<Vec<_>>();
+
+    let batch = deserialize(&rows, Arc::new(schema), BinaryFormat::Utf8)?;
+
+    columns
+        .iter()
+        .zip(batch.columns())
+        .for_each(|(expected, result)| assert_eq!(expected.as_ref(), result.as_ref()));
+    Ok(())
+}
@@ -4,8 +4,8 @@ use arrow2::{
     array::*,
     bitmap::Bitmap,
     buffer::Buffer,
-    datatypes::{DataType, Field, Schema},
-    io::json::LineDelimitedWriter,
+    datatypes::{DataType, Field, Schema, TimeUnit, UnionMode},
+    io::json::{self, ArrayWriter, LineDelimitedWriter},
     record_batch::RecordBatch,
 };
 
@@ -286,3 +286,245 @@ fn write_list_of_struct() {
 "#
     );
 }
+
+#[test]
+fn write_sliced_list() {
+    let data = vec![
+        Some(vec![Some(1), Some(2)]),
+        None,
+        Some(vec![Some(3), None]),
+        Some(vec![]),
+    ];
+    let mut array = MutableListArray::<i32, MutablePrimitiveArray<i32>>::new();
+    array.try_extend(data).unwrap();
+    let array: ListArray<i32> = array.into();
+    let array = array.slice(1, 3);
+
+    let schema = Schema::new(vec![Field::new("a", array.data_type().clone(), true)]);
+    let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(array)]).unwrap();
+
+    let mut buf = Vec::new();
+    {
+        let mut writer = LineDelimitedWriter::new(&mut buf);
+        writer.write_batches(&[batch]).unwrap();
+    }
+
+    assert_eq!(
+        String::from_utf8(buf).unwrap(),
+        r#"{"a":null}
+{"a":[3,null]}
+{"a":[]}
+"#
+    );
+}
+
+#[test]
+fn write_dictionary() {
+    let data = vec![Some("a"), None, Some("b\"c"), Some("a")];
+    let mut array = MutableDictionaryArray::<i32, MutableUtf8Array<i32>>::new();
+    array.try_extend(data).unwrap();
+    let array: DictionaryArray<i32> = array.into();
+
+    let schema = Schema::new(vec![Field::new("a", array.data_type().clone(), true)]);
+    let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(array)]).unwrap();
+
+    let mut buf = Vec::new();
+    {
+        let mut writer = LineDelimitedWriter::new(&mut buf);
+        writer.write_batches(&[batch]).unwrap();
+    }
+
+    assert_eq!(
+        String::from_utf8(buf).unwrap(),
+        r#"{"a":"a"}
+{"a":null}
+{"a":"b\"c"}
+{"a":"a"}
+"#
+    );
+}
+
+#[test]
+fn write_array_multiple_batches() {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("a", DataType::Int32, true),
+        Field::new("b", DataType::Null, true),
+    ]));
+    let batch = |values: &[i32]| {
+        RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from_slice(values)),
+                Arc::new(NullArray::from_data(DataType::Null, values.len())),
+            ],
+        )
+        .unwrap()
+    };
+
+    let mut writer = ArrayWriter::new(vec![]);
+    writer
+        .write_batches(&[batch(&[1, 2]), batch(&[]), batch(&[3])])
+        .unwrap();
+    writer.finish().unwrap();
+
+    assert_eq!(
+        String::from_utf8(writer.into_inner()).unwrap(),
+        r#"[{"a":1},{"a":2},{"a":3}]"#
+    );
+}
+
+#[test]
+fn serialize_equals_values() {
+    let options = json::SerializeOptions::default();
+    let columns: Vec<Arc<dyn Array>> = vec![
+        Arc::new(Int64Array::from(&[Some(1), None, Some(-3)])),
+        Arc::new(Float32Array::from(&[Some(0.1), Some(f32::NAN), None])),
+        Arc::new(Float64Array::from(&[Some(1.0), Some(1e20), None])),
+        Arc::new(Utf8Array::<i64>::from(&[Some("a\n"), None, Some("é")])),
+        Arc::new(BooleanArray::from(&[Some(true), None, Some(false)])),
+        Arc::new(BinaryArray::<i32>::from(&[Some(b"a"), None, Some(b"c")])),
+        Arc::new(Int32Array::from(&[Some(1), None, Some(-1)]).to(DataType::Date32)),
+        Arc::new(
+            Int64Array::from(&[Some(1), None, Some(-1)])
+                .to(DataType::Timestamp(TimeUnit::Millisecond, None)),
+        ),
+        Arc::new(
+            Int64Array::from(&[Some(1), None, Some(-1)]).to(DataType::Timestamp(
+                TimeUnit::Second,
+                Some("-03:00".to_string()),
+            )),
+        ),
+        Arc::new(Int128Array::from(&[Some(1), None, Some(-123)]).to(DataType::Decimal(5, 2))),
+    ];
+    let fields = columns
+        .iter()
+        .enumerate()
+        .map(|(i, c)| Field::new(&format!("c{}", i), c.data_type().clone(), true))
+        .collect();
+    let batch = RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).unwrap();
+
+    let mut buf = vec![];
+    json::serialize(&batch, &json::LineDelimited::default(), &options, &mut buf).unwrap();
+
+    let expected = json::write_record_batches_with_options(&[batch], &options);
+    let result = String::from_utf8(buf)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .collect::<Vec<_>>();
+    let expected = expected
+        .into_iter()
+        .map(serde_json::Value::Object)
+        .collect::<Vec<_>>();
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn serialize_map_and_union() {
+    let entries = Field::new(
+        "entries",
+        DataType::Struct(vec![
+            Field::new("key", DataType::Int32, false),
+            Field::new("value", DataType::Utf8, true),
+        ]),
+        false,
+    );
+    let field = StructArray::from_data(
+        entries.data_type().clone(),
+        vec![
+            Arc::new(Int32Array::from_slice(&[0, 1, 2, 3])),
+            Arc::new(Utf8Array::<i32>::from(&[
+                Some("a"),
+                None,
+                Some("b"),
+                Some("c"),
+            ])),
+        ],
+        None,
+    );
+    let map = MapArray::from_data(
+        DataType::Map(Box::new(entries), false),
+        Buffer::from(&[0, 1, 1, 3, 4]),
+        Arc::new(field),
+        Some(Bitmap::from([true, false, true, true])),
+    )
+    // the first entry does not belong to the array
+    .slice(1, 3);
+
+    let fields = vec![
+        Field::new("a", DataType::Int32, true),
+        Field::new("b", DataType::Utf8, true),
+    ];
+    let union_fields = || -> Vec<Arc<dyn Array>> {
+        vec![
+            Arc::new(Int32Array::from(&[Some(1), None, Some(3)])),
+            Arc::new(Utf8Array::<i32>::from(&[Some("x"), Some("y"), Some("z")])),
+        ]
+    };
+    let dense = UnionArray::from_data(
+        DataType::Union(fields.clone(), None, UnionMode::Dense),
+        Buffer::from(&[1, 0, 0]),
+        union_fields(),
+        Some(Buffer::from(&[2, 0, 1])),
+    );
+    let sparse = UnionArray::from_data(
+        DataType::Union(fields, None, UnionMode::Sparse),
+        Buffer::from(&[0, 1, 0]),
+        union_fields(),
+        None,
+    );
+
+    let columns: Vec<Arc<dyn Array>> = vec![Arc::new(map), Arc::new(dense), Arc::new(sparse)];
+    let fields = columns
+        .iter()
+        .enumerate()
+        .map(|(i, c)| Field::new(&format!("c{}", i), c.data_type().clone(), true))
+        .collect();
+    let batch = RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).unwrap();
+
+    let mut buf = vec![];
+    let options = json::SerializeOptions::default();
+    json::serialize(&batch, &json::LineDelimited::default(), &options, &mut buf).unwrap();
+
+    assert_eq!(
+        String::from_utf8(buf).unwrap(),
+        r#"{"c0":null,"c1":"z","c2":1}
+{"c0":{"1":null,"2":"b"},"c1":1,"c2":"y"}
+{"c0":{"3":"c"},"c1":null,"c2":3}
+"#
+    );
+}
+
+#[test]
+fn write_serialized_in_parallel() {
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+    let batches = (0..3)
+        .map(|i| {
+            let array = Int32Array::from_slice(&[i * 2, i * 2 + 1]);
+            RecordBatch::try_new(schema.clone(), vec![Arc::new(array)]).unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let handles = batches
+        .into_iter()
+        .map(|batch| {
+            std::thread::spawn(move || {
+                let mut block = vec![];
+                let options = json::SerializeOptions::default();
+                json::serialize(&batch, &json::JsonArray::default(), &options, &mut block).unwrap();
+                block
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut writer = ArrayWriter::new(vec![]);
+    for handle in handles {
+        writer.write_serialized(&handle.join().unwrap()).unwrap();
+    }
+    writer.finish().unwrap();
+
+    assert_eq!(
+        String::from_utf8(writer.into_inner()).unwrap(),
+        r#"[{"a":0},{"a":1},{"a":2},{"a":3},{"a":4},{"a":5}]"#
+    );
+}
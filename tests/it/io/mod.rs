@@ -7,6 +7,9 @@ mod json;
 #[cfg(feature = "io_ipc")]
 mod ipc;
 
+#[cfg(feature = "io_flight")]
+mod flight;
+
 #[cfg(feature = "io_parquet")]
 mod parquet;
 
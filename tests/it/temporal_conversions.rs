@@ -139,3 +139,23 @@ fn add_interval_timezone() {
     let r = temporal_conversions::timestamp_to_datetime(r, timeunit, &timezone);
     assert_eq!("2020-10-29 01:00:00 WET", format!("{}", r));
 }
+
+#[test]
+fn pre_epoch_sub_second() {
+    assert_eq!(
+        temporal_conversions::timestamp_us_to_datetime(-1).to_string(),
+        "1969-12-31 23:59:59.999999"
+    );
+    assert_eq!(
+        temporal_conversions::timestamp_ms_to_datetime(-1).to_string(),
+        "1969-12-31 23:59:59.999"
+    );
+    assert_eq!(
+        temporal_conversions::timestamp_ns_to_datetime(-1).to_string(),
+        "1969-12-31 23:59:59.999999999"
+    );
+    assert_eq!(
+        temporal_conversions::date64_to_datetime(-1).to_string(),
+        "1969-12-31 23:59:59.999"
+    );
+}